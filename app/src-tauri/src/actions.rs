@@ -3,7 +3,9 @@ use ghui_app::{
     telemetry::{self, TelemetryEvent},
     DataState, ResolvedUrl,
 };
-use github_graphql::data::{Change, Changes, WorkItemId};
+use github_graphql::data::{
+    Change, Changes, ConflictChoice, IntegrityFix, IntegrityIssue, SanitizeRuleInfo, WorkItemId,
+};
 use tauri::State;
 
 #[tauri::command]
@@ -20,9 +22,28 @@ pub async fn convert_tracked_to_sub_issues(
     Ok(())
 }
 
+/// Stages converting every tracked issue in the project to a sub-issue.
+/// Returns the number of changes staged.
+#[tauri::command]
+pub async fn convert_all_tracked_to_sub_issues(
+    data_state: State<'_, DataState>,
+) -> TauriCommandResult<usize> {
+    let (count, conflicts_count) = data_state.convert_all_tracked_to_sub_issues().await?;
+    telemetry::record(TelemetryEvent::ConvertAllTracked {
+        changes_count: count,
+        conflicts_count,
+    });
+    Ok(count)
+}
+
+/// Runs sanitize and stages its changes.  `rules` limits the run to the given
+/// rule ids; by default every rule enabled in the sanitize config runs.
 #[tauri::command]
-pub async fn sanitize(data_state: State<'_, DataState>) -> TauriCommandResult<usize> {
-    let (count, conflicts_count) = data_state.sanitize().await?;
+pub async fn sanitize(
+    data_state: State<'_, DataState>,
+    rules: Option<Vec<String>>,
+) -> TauriCommandResult<usize> {
+    let (count, conflicts_count) = data_state.sanitize(rules).await?;
     telemetry::record(TelemetryEvent::Sanitize {
         changes_count: count,
         conflicts_count,
@@ -30,21 +51,57 @@ pub async fn sanitize(data_state: State<'_, DataState>) -> TauriCommandResult<us
     Ok(count)
 }
 
-/// Stages Epic override changes for items that had conflicts during sanitize.
+#[tauri::command]
+pub async fn get_sanitize_rules() -> TauriCommandResult<Vec<SanitizeRuleInfo>> {
+    Ok(ghui_app::get_sanitize_rules()?)
+}
+
+/// Resolves conflicts found during sanitize.
 ///
-/// Each item in `item_ids` that has a matching entry in the stored epic
-/// conflict list will have its override staged as a normal pending change
-/// (undo-tracked) and removed from the conflict list.  IDs that are not
-/// present in the conflict list are silently ignored.
+/// Each choice that matches a stored conflict removes it from the conflict
+/// list; choices that use the proposed value have it staged as a normal
+/// pending change (undo-tracked).  Choices that don't match a conflict are
+/// silently ignored.  Returns the number of changes staged.
+#[tauri::command]
+pub async fn stage_conflict_resolutions(
+    data_state: State<'_, DataState>,
+    choices: Vec<ConflictChoice>,
+) -> TauriCommandResult<usize> {
+    Ok(data_state.stage_conflict_resolutions(choices).await?)
+}
+
+/// Loads every work item and reports problems with the shape of the
+/// hierarchy.
 #[tauri::command]
-pub async fn stage_epic_overrides(
+pub async fn check_integrity(
     data_state: State<'_, DataState>,
-    item_ids: Vec<WorkItemId>,
+) -> TauriCommandResult<Vec<IntegrityIssue>> {
+    Ok(data_state.check_integrity().await?)
+}
+
+#[tauri::command]
+pub async fn apply_integrity_fix(
+    data_state: State<'_, DataState>,
+    fix: IntegrityFix,
 ) -> TauriCommandResult<()> {
-    data_state.stage_epic_overrides(item_ids).await?;
+    data_state.apply_integrity_fix(fix).await?;
     Ok(())
 }
 
+/// Stages the changes that undo a previous save on GitHub.  They are added as
+/// normal pending changes so they can be reviewed before being saved.
+#[tauri::command]
+pub async fn revert_save(
+    data_state: State<'_, DataState>,
+    number: usize,
+) -> TauriCommandResult<usize> {
+    let count = data_state.lock().await.revert_save(number).await?;
+    telemetry::record(TelemetryEvent::RevertSave {
+        changes_count: count,
+    });
+    Ok(count)
+}
+
 #[tauri::command]
 pub async fn add_change(
     data_state: State<'_, DataState>,
@@ -115,3 +172,16 @@ pub async fn resolve_url(
     let resolved = data_state.lock().await.resolve_url(url).await?;
     Ok(resolved)
 }
+
+/// Returns the issue types valid for the repository of the given work item.
+///
+/// Results are cached per repository, so the issue type picker can call this
+/// each time it opens without re-querying GitHub.
+#[tauri::command]
+pub async fn get_issue_types(
+    data_state: State<'_, DataState>,
+    id: WorkItemId,
+) -> TauriCommandResult<Vec<String>> {
+    let issue_types = data_state.lock().await.get_issue_types(&id).await?;
+    Ok(issue_types)
+}
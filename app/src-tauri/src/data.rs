@@ -2,9 +2,13 @@ use crate::TauriCommandResult;
 use ghui_app::{
     load_work_items_extra_data, save_work_items_extra_data,
     telemetry::{self, TelemetryEvent},
-    DataState, DataUpdate, Filters, ItemToUpdate, RefreshSummary,
+    views::ViewRegistry,
+    CrossTab, DataState, DataUpdate, Filters, ItemToUpdate, RefreshSummary, SearchResult,
+};
+use github_graphql::{
+    data::SaveReport,
+    pivot::{Axis, CrossTabConfig, CrossTabValue, PivotConfig},
 };
-use github_graphql::pivot::{Axis, PivotConfig};
 use tauri::{ipc::Channel, State};
 
 #[tauri::command]
@@ -76,7 +80,7 @@ pub async fn set_preview_changes(
 pub async fn save_changes(
     data_state: State<'_, DataState>,
     progress: Channel<(usize, usize)>,
-) -> TauriCommandResult<()> {
+) -> TauriCommandResult<SaveReport> {
     let start = std::time::Instant::now();
 
     let report_progress = |c, t| {
@@ -86,12 +90,12 @@ pub async fn save_changes(
     let result = data_state.save_changes(&report_progress).await;
 
     telemetry::record(TelemetryEvent::Save {
-        changes_count: result.as_ref().copied().unwrap_or(0),
+        changes_count: result.as_ref().map(|r| r.results.len()).unwrap_or(0),
         duration_ms: start.elapsed().as_millis() as u64,
-        success: result.is_ok(),
+        success: result.as_ref().is_ok_and(|r| r.is_success()),
     });
 
-    Ok(result.map(|_| ())?)
+    Ok(result?)
 }
 
 #[tauri::command]
@@ -107,6 +111,19 @@ pub async fn set_filters(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn set_filter_query(
+    data_state: State<'_, DataState>,
+    query: String,
+) -> TauriCommandResult<()> {
+    let mut data_state = data_state.lock().await;
+    data_state.set_filter_query(&query).await?;
+    telemetry::record(TelemetryEvent::FilterChanged {
+        active_filters: data_state.active_filter_count(),
+    });
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_pivot_config(data_state: State<'_, DataState>) -> TauriCommandResult<PivotConfig> {
     Ok(data_state.lock().await.get_pivot_config())
@@ -121,6 +138,67 @@ pub async fn set_pivot_config(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_views(data_state: State<'_, DataState>) -> TauriCommandResult<ViewRegistry> {
+    Ok(data_state.lock().await.get_views())
+}
+
+#[tauri::command]
+pub async fn save_view(
+    data_state: State<'_, DataState>,
+    name: String,
+) -> TauriCommandResult<ViewRegistry> {
+    Ok(data_state.lock().await.save_view(&name)?)
+}
+
+#[tauri::command]
+pub async fn apply_view(data_state: State<'_, DataState>, name: String) -> TauriCommandResult<()> {
+    data_state.lock().await.apply_view(&name).await?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn rename_view(
+    data_state: State<'_, DataState>,
+    name: String,
+    new_name: String,
+) -> TauriCommandResult<ViewRegistry> {
+    Ok(data_state.lock().await.rename_view(&name, &new_name)?)
+}
+
+#[tauri::command]
+pub async fn delete_view(
+    data_state: State<'_, DataState>,
+    name: String,
+) -> TauriCommandResult<ViewRegistry> {
+    Ok(data_state.lock().await.delete_view(&name)?)
+}
+
+#[tauri::command]
+pub async fn set_default_view(
+    data_state: State<'_, DataState>,
+    name: Option<String>,
+) -> TauriCommandResult<ViewRegistry> {
+    Ok(data_state.lock().await.set_default_view(name.as_deref())?)
+}
+
+#[tauri::command]
+pub async fn export_view(
+    data_state: State<'_, DataState>,
+    name: String,
+) -> TauriCommandResult<String> {
+    let path = data_state.lock().await.export_view(&name)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[tauri::command]
+pub async fn import_view(
+    data_state: State<'_, DataState>,
+    json: String,
+) -> TauriCommandResult<ViewRegistry> {
+    Ok(data_state.lock().await.import_view(&json)?)
+}
+
 #[tauri::command]
 pub async fn set_work_items_extra_data(extra_data: String) -> TauriCommandResult<()> {
     Ok(save_work_items_extra_data(extra_data.as_str())?)
@@ -157,6 +235,33 @@ pub async fn capture_view(data_state: State<'_, DataState>) -> TauriCommandResul
     Ok(path.to_string_lossy().into_owned())
 }
 
+#[tauri::command]
+pub async fn search_work_items(
+    data_state: State<'_, DataState>,
+    text: String,
+    limit: usize,
+) -> TauriCommandResult<Vec<SearchResult>> {
+    Ok(data_state.search(&text, limit).await)
+}
+
+#[tauri::command]
+pub async fn get_cross_tab(
+    data_state: State<'_, DataState>,
+    cfg: CrossTabConfig,
+) -> TauriCommandResult<CrossTab> {
+    Ok(data_state.lock().await.cross_tab(&cfg))
+}
+
+#[tauri::command]
+pub async fn export_cross_tab(
+    data_state: State<'_, DataState>,
+    cfg: CrossTabConfig,
+    value: CrossTabValue,
+) -> TauriCommandResult<String> {
+    let path = data_state.lock().await.export_cross_tab(&cfg, value)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
 #[tauri::command]
 pub async fn parse_recipe(text: String) -> TauriCommandResult<Vec<Axis>> {
     Ok(github_graphql::pivot::parse_recipe(&text)?)
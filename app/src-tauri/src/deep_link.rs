@@ -0,0 +1,79 @@
+use crate::TauriCommandResult;
+use ghui_app::{deep_link, DataState};
+use github_graphql::data::WorkItemId;
+use log::{error, info};
+use tauri::{App, AppHandle, Emitter, Manager, State};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+/// Sent to the frontend after a link has been applied, so that it can focus
+/// the link's work item.
+const DEEP_LINK_OPENED: &str = "deep-link-opened";
+
+/// Opens the `ghui://` link that ghui was launched with, if any, and any
+/// opened while it is running.  `tauri-plugin-single-instance` forwards
+/// links that would otherwise start a second instance.
+pub fn setup(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    // Installers register the scheme; this covers development builds.
+    #[cfg(any(windows, target_os = "linux"))]
+    app.deep_link().register_all()?;
+
+    if let Some(urls) = app.deep_link().get_current()? {
+        open_urls(
+            app.handle(),
+            urls.iter().map(|url| url.to_string()).collect(),
+        );
+    }
+
+    let handle = app.handle().clone();
+    app.deep_link().on_open_url(move |event| {
+        open_urls(
+            &handle,
+            event.urls().iter().map(|url| url.to_string()).collect(),
+        );
+    });
+
+    Ok(())
+}
+
+fn open_urls(app: &AppHandle, urls: Vec<String>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for url in urls {
+            match open_url(&app, &url).await {
+                Ok(()) => info!("Opened {url}"),
+                Err(e) => error!("Could not open {url}: {e}"),
+            }
+        }
+    });
+}
+
+async fn open_url(app: &AppHandle, url: &str) -> anyhow::Result<()> {
+    let link = deep_link::parse(url)?;
+    app.state::<DataState>()
+        .lock()
+        .await
+        .open_deep_link(link)
+        .await?;
+
+    app.emit(DEEP_LINK_OPENED, ())?;
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn take_focus_item(
+    data_state: State<'_, DataState>,
+) -> TauriCommandResult<Option<WorkItemId>> {
+    Ok(data_state.lock().await.take_focus_item())
+}
+
+#[tauri::command]
+pub async fn get_deep_link(
+    data_state: State<'_, DataState>,
+    item: Option<WorkItemId>,
+) -> TauriCommandResult<String> {
+    Ok(data_state.lock().await.deep_link(item.as_ref()))
+}
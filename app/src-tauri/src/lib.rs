@@ -4,6 +4,7 @@ use tauri::Manager;
 mod actions;
 mod auth;
 mod data;
+mod deep_link;
 mod update;
 
 pub type TauriCommandResult<T> = core::result::Result<T, TauriCommandError>;
@@ -29,11 +30,20 @@ pub fn run() {
     ghui_app::telemetry::init();
 
     tauri::Builder::default()
+        // Must be registered first so that a second launch (e.g. from a
+        // `ghui://` link) is handed to the running instance.
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_window_state::Builder::default().build())
         .setup(|app| {
             app.manage(DataState::default());
+            deep_link::setup(app)?;
 
             // The window starts invisible (visible: false in tauri.conf.json) to
             // prevent a flash of white background at the default position before
@@ -56,25 +66,45 @@ pub fn run() {
             data::set_preview_changes,
             data::save_changes,
             data::set_filters,
+            data::set_filter_query,
             data::get_pivot_config,
             data::set_pivot_config,
+            data::get_views,
+            data::save_view,
+            data::apply_view,
+            data::rename_view,
+            data::delete_view,
+            data::set_default_view,
+            data::export_view,
+            data::import_view,
+            deep_link::take_focus_item,
+            deep_link::get_deep_link,
             data::set_work_items_extra_data,
             data::get_work_items_extra_data,
             data::get_log_file_path,
             data::get_telemetry_file_path,
             data::record_telemetry,
             data::capture_view,
+            data::search_work_items,
+            data::get_cross_tab,
+            data::export_cross_tab,
             data::parse_recipe,
             data::recipe_to_string,
             actions::convert_tracked_to_sub_issues,
+            actions::convert_all_tracked_to_sub_issues,
             actions::sanitize,
-            actions::stage_epic_overrides,
+            actions::get_sanitize_rules,
+            actions::stage_conflict_resolutions,
+            actions::check_integrity,
+            actions::apply_integrity_fix,
+            actions::revert_save,
             actions::add_change,
             actions::add_changes,
             actions::remove_change,
             actions::undo_change,
             actions::redo_change,
             actions::resolve_url,
+            actions::get_issue_types,
             update::check_for_update,
             update::install_update,
         ])
@@ -4,9 +4,11 @@ import type { Filters } from "$lib/bindings/Filters";
 import type { PivotConfig } from "$lib/bindings/PivotConfig";
 import {
   applyText,
+  getAggregateChecked,
   getFilterToggleChecked,
   getRenderToggleChecked,
   getToggleChecked,
+  setAggregate,
   setFilterToggle,
   setRenderToggle,
   setToggle,
@@ -18,6 +20,7 @@ function makeConfig(recipe: Axis[] = []): PivotConfig {
     recipe,
     multiValueStrategy: "combined",
     showGhostAncestors: true,
+    aggregates: [],
   };
 }
 
@@ -33,6 +36,7 @@ function makeFilters(overrides: Partial<Filters> = {}): Filters {
     priority: [],
     assignee: [],
     hideClosed: false,
+    query: null,
     ...overrides,
   };
 }
@@ -168,6 +172,28 @@ describe("setToggle", () => {
   });
 });
 
+describe("setAggregate", () => {
+  it("adds aggregates in the order they are selected", () => {
+    const config = setAggregate(
+      setAggregate(makeConfig(), "estimate", true),
+      "count",
+      true
+    );
+    expect(config.aggregates).toEqual(["estimate", "count"]);
+    expect(getAggregateChecked(config, "count")).toBe(true);
+    expect(getAggregateChecked(config, "open")).toBe(false);
+  });
+
+  it("removes a deselected aggregate without duplicating others", () => {
+    const config: PivotConfig = {
+      ...makeConfig(),
+      aggregates: ["count", "open"],
+    };
+    const next = setAggregate(setAggregate(config, "count", false), "open", true);
+    expect(next.aggregates).toEqual(["open"]);
+  });
+});
+
 describe("getToggleChecked — initial state reflects bound PivotConfig", () => {
   it("reports explodeMulti as checked when multiValueStrategy is 'explode'", () => {
     const config: PivotConfig = {
@@ -252,6 +278,7 @@ describe("RecipeBar Explode checkbox — onApply wiring", () => {
       recipe,
       multiValueStrategy: "combined",
       showGhostAncestors: false,
+      aggregates: [],
     };
 
     simulateCheckboxChange(initial, "explodeMulti", true, onApply);
@@ -260,6 +287,7 @@ describe("RecipeBar Explode checkbox — onApply wiring", () => {
       recipe,
       multiValueStrategy: "explode",
       showGhostAncestors: false,
+      aggregates: [],
     });
   });
 });
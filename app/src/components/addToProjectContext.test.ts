@@ -13,8 +13,11 @@ function makeIssue(opts: {
     id: opts.id as WorkItemId,
     title: opts.title ?? `Item ${opts.id}`,
     updatedAt: "2024-01-01T00:00:00Z",
+    createdAt: "2024-01-01T00:00:00Z",
     resourcePath: `/owner/repo/issues/${opts.id}`,
     repoNameWithOwner: "owner/repo",
+    body: { loadState: "notLoaded" },
+    comments: { loadState: "notLoaded" },
     data: {
       type: "issue",
       parentId: (opts.parentId ?? null) as WorkItemId | null,
@@ -92,12 +95,16 @@ describe("findAddToProjectContext", () => {
       id: "pr1" as WorkItemId,
       title: "A PR",
       updatedAt: "2024-01-01T00:00:00Z",
+      createdAt: "2024-01-01T00:00:00Z",
       resourcePath: "/owner/repo/pull/1",
       repoNameWithOwner: "owner/repo",
+      body: { loadState: "notLoaded" },
+      comments: { loadState: "notLoaded" },
       data: {
         type: "pullRequest",
         state: { loadState: "loaded", value: "OPEN" },
         assignees: [],
+        linkedIssues: { loadState: "loaded", value: [] },
       },
       projectItem: {
         id: "pi-pr1",
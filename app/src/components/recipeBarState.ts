@@ -1,3 +1,4 @@
+import type { Aggregate } from "$lib/bindings/Aggregate";
 import type { Axis } from "$lib/bindings/Axis";
 import type { Filters } from "$lib/bindings/Filters";
 import type { PivotConfig } from "$lib/bindings/PivotConfig";
@@ -88,6 +89,30 @@ export function getToggleChecked(
   }
 }
 
+/**
+ * Select or deselect an aggregate on a PivotConfig, returning the updated
+ * config. Aggregates stay in the order they were selected.
+ */
+export function setAggregate(
+  config: PivotConfig,
+  aggregate: Aggregate,
+  checked: boolean
+): PivotConfig {
+  const aggregates = config.aggregates.filter((a) => a !== aggregate);
+  return {
+    ...config,
+    aggregates: checked ? [...aggregates, aggregate] : aggregates,
+  };
+}
+
+/** Whether `aggregate` is selected on the PivotConfig. */
+export function getAggregateChecked(
+  config: PivotConfig,
+  aggregate: Aggregate
+): boolean {
+  return config.aggregates.includes(aggregate);
+}
+
 /**
  * Apply a filter-bound toggle to a `Filters` value, returning a new copy.
  *
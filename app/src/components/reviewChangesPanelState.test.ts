@@ -1,5 +1,9 @@
 import { describe, expect, it } from "vitest";
-import { getInitialActiveTab } from "./reviewChangesPanelState";
+import {
+  describeOutcome,
+  getInitialActiveTab,
+  isSuccessfulOutcome,
+} from "./reviewChangesPanelState";
 
 describe("getInitialActiveTab", () => {
   it("defaults to pending changes tab when both lists are empty", () => {
@@ -26,3 +30,38 @@ describe("getInitialActiveTab", () => {
     expect(getInitialActiveTab(3, 0, "conflicts")).toBe("changes");
   });
 });
+
+describe("isSuccessfulOutcome", () => {
+  it("treats saved and dry-run changes as successful", () => {
+    expect(isSuccessfulOutcome({ status: "succeeded" })).toBe(true);
+    expect(isSuccessfulOutcome({ status: "dryRun" })).toBe(true);
+  });
+
+  it("treats skipped changes as unsuccessful", () => {
+    expect(isSuccessfulOutcome({ status: "skippedConnectivity" })).toBe(false);
+  });
+});
+
+describe("describeOutcome", () => {
+  it("prefers GraphQL messages over the raw error", () => {
+    expect(
+      describeOutcome({
+        status: "failed",
+        kind: "graphQl",
+        message: "GraphQL response errors: [...]",
+        graphqlMessages: ["Could not resolve to a node"],
+      })
+    ).toBe("Failed (graphQl): Could not resolve to a node");
+  });
+
+  it("falls back to the error message", () => {
+    expect(
+      describeOutcome({
+        status: "failed",
+        kind: "connectivity",
+        message: "Connectivity error: dial tcp",
+        graphqlMessages: [],
+      })
+    ).toBe("Failed (connectivity): Connectivity error: dial tcp");
+  });
+});
@@ -1,3 +1,5 @@
+import type { ChangeOutcome } from "$lib/bindings/ChangeOutcome";
+
 export type Tab = "changes" | "conflicts";
 
 export function getInitialActiveTab(
@@ -10,3 +12,26 @@ export function getInitialActiveTab(
   if (changeCount === 0 && conflictCount > 0) return "conflicts";
   return "changes";
 }
+
+export function isSuccessfulOutcome(outcome: ChangeOutcome): boolean {
+  return outcome.status === "succeeded" || outcome.status === "dryRun";
+}
+
+export function describeOutcome(outcome: ChangeOutcome): string {
+  switch (outcome.status) {
+    case "succeeded":
+      return "Saved";
+    case "dryRun":
+      return "Dry run";
+    case "failed":
+      return outcome.graphqlMessages.length > 0
+        ? `Failed (${outcome.kind}): ${outcome.graphqlMessages.join("; ")}`
+        : `Failed (${outcome.kind}): ${outcome.message}`;
+    case "skippedConnectivity":
+      return "Skipped: network unavailable";
+    case "skippedDependency":
+      return "Skipped: adding the item to the project failed";
+    case "invalid":
+      return "Skipped: change is invalid";
+  }
+}
@@ -1,10 +1,12 @@
 import { describe, expect, it } from "vitest";
 import type { Node } from "$lib/bindings/Node";
 import {
+  ancestorRowIds,
   computeGroupChildCounts,
   computeGroupSiblingCounts,
   computeRootGroupIds,
   computeVisibleRows,
+  formatAggregates,
 } from "./workItemTreeRows";
 
 function group(id: string, level: number): Node {
@@ -15,6 +17,7 @@ function group(id: string, level: number): Node {
     hasChildren: true,
     isModified: false,
     isGhost: false,
+    aggregates: null,
   };
 }
 
@@ -26,6 +29,7 @@ function item(id: string, level: number): Node {
     hasChildren: false,
     isModified: false,
     isGhost: false,
+    aggregates: null,
   };
 }
 
@@ -200,3 +204,52 @@ describe("computeVisibleRows", () => {
     expect(rows.find((r) => r.id === "a")?.isGroup).toBe(false);
   });
 });
+
+describe("ancestorRowIds", () => {
+  const nodes = [
+    group("g1", 0),
+    item("parent", 1),
+    item("child", 2),
+    item("sibling", 1),
+    group("g2", 0),
+    item("other", 1),
+  ];
+
+  it("lists enclosing rows outermost first", () => {
+    expect(ancestorRowIds(nodes, "child")).toEqual(["g1", "parent"]);
+    expect(ancestorRowIds(nodes, "sibling")).toEqual(["g1"]);
+    expect(ancestorRowIds(nodes, "other")).toEqual(["g2"]);
+  });
+
+  it("is empty for root rows and unknown ids", () => {
+    expect(ancestorRowIds(nodes, "g1")).toEqual([]);
+    expect(ancestorRowIds(nodes, "missing")).toEqual([]);
+  });
+});
+
+describe("formatAggregates", () => {
+  it("returns an empty string when there are no aggregates", () => {
+    expect(formatAggregates(null)).toBe("");
+  });
+
+  it("renders only the aggregates that were computed", () => {
+    expect(
+      formatAggregates({
+        count: 3,
+        open: 2,
+        closed: 1,
+        estimate: 2.5,
+        percentDone: 33,
+      })
+    ).toBe("3 items · 2 open · 1 closed · 2.5d · 33% done");
+    expect(
+      formatAggregates({
+        count: 1,
+        open: null,
+        closed: null,
+        estimate: null,
+        percentDone: 100,
+      })
+    ).toBe("1 item · 100% done");
+  });
+});
@@ -1,3 +1,4 @@
+import type { Aggregates } from "$lib/bindings/Aggregates";
 import type { Node } from "$lib/bindings/Node";
 
 /** A tree node decorated with whether it is a group (bucket) header row. */
@@ -153,3 +154,50 @@ export function computeVisibleRows(
 
   return rows;
 }
+
+/**
+ * Ids of the rows enclosing the row `rowId` — the nearest preceding row at
+ * each smaller level — outermost first. These are the rows that must be
+ * expanded for `rowId` to be shown. Empty if `rowId` isn't in `rows`.
+ */
+export function ancestorRowIds(
+  rows: { id: string; level: number }[],
+  rowId: string
+): string[] {
+  const ancestors: { id: string; level: number }[] = [];
+
+  for (const row of rows) {
+    while (
+      ancestors.length > 0 &&
+      ancestors[ancestors.length - 1].level >= row.level
+    ) {
+      ancestors.pop();
+    }
+    if (row.id === rowId) return ancestors.map((a) => a.id);
+    ancestors.push(row);
+  }
+
+  return [];
+}
+
+/**
+ * Render a node's aggregates as a short summary, e.g.
+ * `3 items · 2 open · 1 closed · 2.5d · 33% done`. Only the aggregates that
+ * were computed appear; returns an empty string when there are none.
+ */
+export function formatAggregates(aggregates: Aggregates | null): string {
+  if (!aggregates) return "";
+  const parts: string[] = [];
+  if (aggregates.count !== null) {
+    parts.push(`${aggregates.count} ${aggregates.count === 1 ? "item" : "items"}`);
+  }
+  if (aggregates.open !== null) parts.push(`${aggregates.open} open`);
+  if (aggregates.closed !== null) parts.push(`${aggregates.closed} closed`);
+  if (aggregates.estimate !== null) {
+    parts.push(`${Number(aggregates.estimate.toFixed(2))}d`);
+  }
+  if (aggregates.percentDone !== null) {
+    parts.push(`${aggregates.percentDone}% done`);
+  }
+  return parts.join(" · ");
+}
@@ -17,7 +17,19 @@ import type { TelemetryEvent } from "./bindings/TelemetryEvent";
 import type { ResolvedUrl } from "./bindings/ResolvedUrl";
 import type { RefreshSummary } from "./bindings/RefreshSummary";
 import type { PivotConfig } from "./bindings/PivotConfig";
+import type { SaveReport } from "./bindings/SaveReport";
+import type { SanitizeRuleInfo } from "./bindings/SanitizeRuleInfo";
+import type { ConflictChoice } from "./bindings/ConflictChoice";
+import type { IntegrityIssue } from "./bindings/IntegrityIssue";
+import type { IntegrityFix } from "./bindings/IntegrityFix";
+import type { CrossTab } from "./bindings/CrossTab";
+import type { CrossTabConfig } from "./bindings/CrossTabConfig";
+import type { CrossTabValue } from "./bindings/CrossTabValue";
+import type { ViewRegistry } from "./bindings/ViewRegistry";
+import type { DataPatch } from "./bindings/DataPatch";
+import type { SearchResult } from "./bindings/SearchResult";
 import { upsertWorkItem } from "./workItems";
+import { applyNodeEdits } from "./nodeEdits";
 import * as filterableFields from "./filterableFields";
 import type { FilterableField } from "./filterableFields";
 
@@ -53,17 +65,23 @@ export class WorkItemContext {
       priority: [],
       assignee: [],
       hideClosed: false,
+      query: null,
     },
     pivotConfig: {
       recipe: [{ kind: "pivot", field: "epic" }, { kind: "hierarchy" }],
       multiValueStrategy: "combined",
       showGhostAncestors: true,
+      aggregates: [],
     },
     originalWorkItems: {},
     changes: { data: {} },
     canUndo: false,
     canRedo: false,
-    epicConflicts: [],
+    sanitizeConflicts: [],
+    sanitizeProblems: [],
+    changeDiagnostics: [],
+    lastSaveReport: null,
+    saveHistory: [],
   });
 
   workItemTreeExpandedItems = $state<string[]>([]);
@@ -123,6 +141,9 @@ export class WorkItemContext {
       case "data":
         this.onDataUpdateData(dataUpdate.value);
         break;
+      case "patch":
+        this.onDataUpdatePatch(dataUpdate.value);
+        break;
       case "progress":
         this.onDataUpdateProgress(dataUpdate.value);
         break;
@@ -146,6 +167,16 @@ export class WorkItemContext {
     this.data = data;
   }
 
+  onDataUpdatePatch(patch: DataPatch) {
+    let workItems = this.data.workItems;
+    for (const workItem of patch.workItems) {
+      workItems = upsertWorkItem(workItems, workItem);
+    }
+    this.data.workItems = workItems;
+    this.data.nodes = applyNodeEdits(this.data.nodes, patch.nodeEdits);
+    this.data.changeDiagnostics = patch.changeDiagnostics;
+  }
+
   onDataUpdateWorkItem(workItem: WorkItem) {
     this.data.workItems = upsertWorkItem(this.data.workItems, workItem);
   }
@@ -195,12 +226,106 @@ export class WorkItemContext {
     });
   }
 
-  public async sanitize() {
-    await invoke("sanitize");
+  /** Stages converting every tracked issue to a sub-issue; returns how many. */
+  public async convertAllTrackedIssuesToSubIssues(): Promise<number> {
+    return await invoke<number>("convert_all_tracked_to_sub_issues");
+  }
+
+  /** Runs the enabled sanitize rules, or only `rules` if given. */
+  public async sanitize(rules?: string[]) {
+    await invoke("sanitize", { rules: rules ?? null });
+  }
+
+  public async getSanitizeRules(): Promise<SanitizeRuleInfo[]> {
+    return await invoke<SanitizeRuleInfo[]>("get_sanitize_rules");
+  }
+
+  /** Resolves sanitize conflicts; returns how many changes were staged. */
+  public async stageConflictResolutions(
+    choices: ConflictChoice[]
+  ): Promise<number> {
+    return await invoke<number>("stage_conflict_resolutions", { choices });
+  }
+
+  /** Loads every item and reports problems with the hierarchy. */
+  public async checkIntegrity(): Promise<IntegrityIssue[]> {
+    return await invoke<IntegrityIssue[]>("check_integrity");
+  }
+
+  public async applyIntegrityFix(fix: IntegrityFix) {
+    await invoke("apply_integrity_fix", { fix });
+  }
+
+  public async getViews(): Promise<ViewRegistry> {
+    return await invoke<ViewRegistry>("get_views");
+  }
+
+  /** Saves the current filters and recipe as `name`, replacing any view with that name. */
+  public async saveView(name: string): Promise<ViewRegistry> {
+    return await invoke<ViewRegistry>("save_view", { name });
+  }
+
+  public async applyView(name: string) {
+    await invoke("apply_view", { name });
+  }
+
+  public async renameView(name: string, newName: string): Promise<ViewRegistry> {
+    return await invoke<ViewRegistry>("rename_view", { name, newName });
+  }
+
+  public async deleteView(name: string): Promise<ViewRegistry> {
+    return await invoke<ViewRegistry>("delete_view", { name });
+  }
+
+  /** Sets the view applied at startup; `null` clears it. */
+  public async setDefaultView(name: string | null): Promise<ViewRegistry> {
+    return await invoke<ViewRegistry>("set_default_view", { name });
   }
 
-  public async stageEpicOverrides(itemIds: WorkItemId[]) {
-    await invoke("stage_epic_overrides", { itemIds });
+  /** Writes the view to a file for sharing; returns the file's path. */
+  public async exportView(name: string): Promise<string> {
+    return await invoke<string>("export_view", { name });
+  }
+
+  /** Adds a view from the contents of an exported view file. */
+  public async importView(json: string): Promise<ViewRegistry> {
+    return await invoke<ViewRegistry>("import_view", { json });
+  }
+
+  /** Returns the work item a `ghui://` link asked to focus, if any, clearing it. */
+  public async takeFocusItem(): Promise<WorkItemId | null> {
+    return await invoke<WorkItemId | null>("take_focus_item");
+  }
+
+  /** Builds a `ghui://` link to the current view, focusing `item` if given. */
+  public async getDeepLink(item?: WorkItemId): Promise<string> {
+    return await invoke<string>("get_deep_link", { item: item ?? null });
+  }
+
+  /** Searches titles, bodies and comments; best matches first. */
+  public async searchWorkItems(
+    text: string,
+    limit: number = 200
+  ): Promise<SearchResult[]> {
+    return await invoke<SearchResult[]>("search_work_items", { text, limit });
+  }
+
+  /** Pivots the filtered items by two fields. */
+  public async getCrossTab(cfg: CrossTabConfig): Promise<CrossTab> {
+    return await invoke<CrossTab>("get_cross_tab", { cfg });
+  }
+
+  /** Saves the cross-tab as CSV; returns the path of the file. */
+  public async exportCrossTab(
+    cfg: CrossTabConfig,
+    value: CrossTabValue
+  ): Promise<string> {
+    return await invoke<string>("export_cross_tab", { cfg, value });
+  }
+
+  /** Stages the changes that undo save `number`; returns how many. */
+  public async revertSave(number: number): Promise<number> {
+    return await invoke<number>("revert_save", { number });
   }
 
   public getFieldOption(
@@ -324,6 +449,15 @@ export class WorkItemContext {
     invoke("set_filters", { filters: this.data.filters });
   }
 
+  /**
+   * Set the filter query from its text; an empty string clears it. Unlike
+   * the other filter setters this waits for the backend, which parses the
+   * text, so that parse errors can be shown to the user.
+   */
+  public async setFilterQuery(query: string): Promise<void> {
+    await invoke("set_filter_query", { query });
+  }
+
   public setPivotConfig(cfg: PivotConfig): void {
     invoke("set_pivot_config", { cfg });
   }
@@ -370,8 +504,8 @@ export class WorkItemContext {
     await invoke("set_preview_changes", { preview });
   }
 
-  public async saveChanges(progress: Channel<Progress>) {
-    await invoke("save_changes", { progress });
+  public async saveChanges(progress: Channel<Progress>): Promise<SaveReport> {
+    return await invoke<SaveReport>("save_changes", { progress });
   }
 
   public async addChange(change: Change) {
@@ -398,6 +532,21 @@ export class WorkItemContext {
     return await invoke<ResolvedUrl>("resolve_url", { url });
   }
 
+  /**
+   * Returns the issue type names valid for the repository of the given work
+   * item.  Empty for items that don't belong to a repository.
+   */
+  public async getIssueTypes(id: WorkItemId): Promise<string[]> {
+    return await invoke<string[]>("get_issue_types", { id });
+  }
+
+  public async setIssueType(item: WorkItem, value: string | undefined) {
+    await this.addChange({
+      workItemId: item.id,
+      data: { type: "issueType", value: value || null },
+    });
+  }
+
   // #endregion
 
   workItemExtraData: { [key in WorkItemId]?: any } = $state({});
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A rollup computed over the work items under each group and parent node.
+ */
+export type Aggregate =
+  | "count"
+  | "open"
+  | "closed"
+  | "estimate"
+  | "percentDone";
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Only the aggregates selected in `PivotConfig::aggregates` are set.
+ */
+export type Aggregates = {
+  count: number | null;
+  open: number | null;
+  closed: number | null;
+  /**
+   * Sum of the items' Estimate values, in days.
+   */
+  estimate: number | null;
+  /**
+   * Percentage of items whose Status is Done or Closed, rounded down.
+   */
+  percentDone: number | null;
+};
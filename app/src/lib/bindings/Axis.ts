@@ -1,8 +1,11 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
-import type { PivotField } from "./PivotField";
+import type { Bucketing } from "./Bucketing";
+import type { HierarchyOptions } from "./HierarchyOptions";
+import type { SortKey } from "./SortKey";
 
 export type Axis =
-  | { "kind": "pivot"; "field": PivotField }
-  | { "kind": "group"; "field": PivotField }
-  | { "kind": "hierarchy" }
-  | { "kind": "sort"; "field": PivotField };
+  | { "kind": "pivot"; field: Bucketing }
+  | { "kind": "group"; field: Bucketing }
+  | { "kind": "hierarchy"; field?: HierarchyOptions }
+  | { "kind": "sort"; field: Array<SortKey> }
+  | { "kind": "filter"; field: string };
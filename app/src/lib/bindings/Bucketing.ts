@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PivotField } from "./PivotField";
+
+/**
+ * The field a `Pivot` or `Group` axis buckets items by, and how its buckets
+ * are shown.
+ */
+export type Bucketing =
+  | PivotField
+  | { field: PivotField; showEmpty: boolean; collapse: boolean };
@@ -13,4 +13,6 @@ export type ChangeData =
   | { "type": "estimate"; "value": FieldOptionId | null }
   | { "type": "priority"; "value": FieldOptionId | null }
   | { "type": "setParent"; "value": WorkItemId }
-  | { "type": "addToProject" };
+  | { "type": "removeParent"; "value": WorkItemId }
+  | { "type": "addToProject" }
+  | { "type": "removeFromProject" };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Change } from "./Change";
+import type { ChangeProblem } from "./ChangeProblem";
+
+/**
+ * A problem with a specific staged change.
+ */
+export type ChangeDiagnostic = { change: Change; problem: ChangeProblem };
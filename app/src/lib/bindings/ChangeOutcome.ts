@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangeProblem } from "./ChangeProblem";
+import type { SaveErrorKind } from "./SaveErrorKind";
+import type { WorkItemId } from "./WorkItemId";
+
+/**
+ * What happened to a single change during a save.
+ */
+export type ChangeOutcome =
+  | { "status": "succeeded" }
+  | { "status": "dryRun" }
+  | {
+    "status": "failed";
+    kind: SaveErrorKind;
+    message: string;
+    /**
+     * Messages from GitHub's GraphQL `errors` array, if any.
+     */
+    graphqlMessages: Array<string>;
+  }
+  | { "status": "skippedConnectivity" }
+  | { "status": "skippedDependency"; dependency: WorkItemId }
+  | { "status": "invalid"; problem: ChangeProblem };
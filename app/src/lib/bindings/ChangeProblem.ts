@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FieldOptionId } from "./FieldOptionId";
+
+/**
+ * A problem found with a staged change that would prevent it from saving
+ * correctly.
+ */
+export type ChangeProblem =
+  | { "type": "workItemNotFound" }
+  | { "type": "notAnIssue" }
+  | { "type": "parentNotAnIssue" }
+  | { "type": "unknownIssueType"; name: string }
+  | { "type": "unknownFieldOption"; field: string; option: FieldOptionId }
+  | { "type": "parentCycle" };
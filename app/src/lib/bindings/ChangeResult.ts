@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Change } from "./Change";
+import type { ChangeOutcome } from "./ChangeOutcome";
+
+export type ChangeResult = {
+  change: Change;
+  outcome: ChangeOutcome;
+  durationMs: number;
+};
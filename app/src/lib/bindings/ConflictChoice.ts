@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConflictResolution } from "./ConflictResolution";
+import type { WorkItemId } from "./WorkItemId";
+
+/**
+ * The resolution chosen for the conflict on `field` of `work_item_id`.
+ *
+ * An item can have several conflicts on the same field, e.g. when it is
+ * tracked by more than one issue, so `source_item_id` identifies which
+ * proposal the choice is for.
+ */
+export type ConflictChoice = {
+  workItemId: WorkItemId;
+  field: string;
+  sourceItemId: WorkItemId | null;
+  resolution: ConflictResolution;
+};
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CrossTabHeader } from "./CrossTabHeader";
+import type { WorkItemId } from "./WorkItemId";
+
+/**
+ * A two dimensional pivot of the work items that pass the active filters.
+ * `cells[row][column]` lists the items with both values.  With
+ * `MultiValueStrategy::Explode` an item can appear in more than one cell.
+ */
+export type CrossTab = {
+  rows: Array<CrossTabHeader>;
+  columns: Array<CrossTabHeader>;
+  cells: Array<Array<Array<WorkItemId>>>;
+};
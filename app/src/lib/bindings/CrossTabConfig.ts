@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PivotField } from "./PivotField";
+
+/**
+ * A two dimensional pivot: one row per value of `rows` and one column per
+ * value of `columns`.
+ */
+export type CrossTabConfig = { rows: PivotField; columns: PivotField };
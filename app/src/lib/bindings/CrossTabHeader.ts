@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FieldOptionId } from "./FieldOptionId";
+
+export type CrossTabHeader = {
+  key: string;
+  label: string;
+  fieldOptionId: FieldOptionId | null;
+};
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What each cell of an exported cross-tab contains.
+ */
+export type CrossTabValue =
+  | /**
+   * The number of work items in the cell.
+   */
+  "count"
+  | /**
+   * The titles of the work items in the cell.
+   */
+  "items";
@@ -1,10 +1,14 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangeDiagnostic } from "./ChangeDiagnostic";
 import type { Changes } from "./Changes";
 import type { Fields } from "./Fields";
 import type { Filters } from "./Filters";
 import type { Node } from "./Node";
 import type { PivotConfig } from "./PivotConfig";
+import type { SaveRecord } from "./SaveRecord";
+import type { SaveReport } from "./SaveReport";
 import type { SanitizeConflict } from "./SanitizeConflict";
+import type { SanitizeProblem } from "./SanitizeProblem";
 
 export type Data = {
   fields: Fields;
@@ -21,9 +25,27 @@ export type Data = {
   canUndo: boolean;
   canRedo: boolean;
   /**
-   * Epic conflicts found during the last sanitize run.  Each entry
-   * represents an item whose existing Epic was not overwritten; the user
-   * can review these and selectively stage the override.
+   * Conflicts found during the last sanitize run.  Each entry represents
+   * a value that was already set and so was not overwritten; the user can
+   * review these and choose which proposed values to stage.
    */
-  epicConflicts: Array<SanitizeConflict>;
+  sanitizeConflicts: Array<SanitizeConflict>;
+  /**
+   * Problems found during the last sanitize run that no change can fix.
+   */
+  sanitizeProblems: Array<SanitizeProblem>;
+  /**
+   * Problems with staged changes that would prevent them from saving.
+   * Changes listed here are skipped by save.
+   */
+  changeDiagnostics: Array<ChangeDiagnostic>;
+  /**
+   * Per-change outcomes of the most recent save.
+   */
+  lastSaveReport: SaveReport | null;
+  /**
+   * Changes committed by each save in this session, oldest first, along
+   * with what they overwrote.
+   */
+  saveHistory: Array<SaveRecord>;
 };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangeDiagnostic } from "./ChangeDiagnostic";
+import type { NodeEdit } from "./NodeEdit";
+import type { WorkItem } from "./WorkItem";
+
+/**
+ * The parts of `Data` that change when some work items are refetched.
+ */
+export type DataPatch = {
+  /**
+   * The refetched items, which replace the ones with the same id.
+   */
+  workItems: Array<WorkItem>;
+  /**
+   * Applied, in order, to `Data::nodes`.
+   */
+  nodeEdits: Array<NodeEdit>;
+  changeDiagnostics: Array<ChangeDiagnostic>;
+};
@@ -1,5 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Data } from "./Data";
+import type { DataPatch } from "./DataPatch";
 import type { LogEntry } from "./LogEntry";
 import type { WorkItem } from "./WorkItem";
 
@@ -7,6 +8,7 @@ export type DataUpdate =
   | { "type": "progress"; "value": { done: number; total: number } }
   | { "type": "workItem"; "value": WorkItem }
   | { "type": "data"; "value": Data }
+  | { "type": "patch"; "value": DataPatch }
   | { "type": "log"; "value": LogEntry }
   | {
     "type": "inconsistentPagination";
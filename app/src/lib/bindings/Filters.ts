@@ -29,4 +29,10 @@ export type Filters = {
    * files (which predate this field) deserializable.
    */
   hideClosed: boolean;
+  /**
+   * Filter expression that items must match, e.g. `epic:X AND (NOT
+   * status:Done OR assignee:@me)`.  Applied in addition to the exclusion
+   * lists above.  See `github_graphql::query::Query` for the grammar.
+   */
+  query: string | null;
 };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Options of a `Hierarchy` axis.
+ */
+export type HierarchyOptions = {
+  /**
+   * How many levels of the tree to show; items below them are left out.
+   */
+  depth: number | null;
+  /**
+   * Whether to show the ancestors of items in scope that aren't in scope
+   * themselves, overriding `PivotConfig::show_ghost_ancestors`.
+   */
+  ghosts: boolean | null;
+};
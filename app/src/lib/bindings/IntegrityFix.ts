@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WorkItemId } from "./WorkItemId";
+
+/**
+ * A suggested way of fixing an [`IntegrityProblem`].
+ */
+export type IntegrityFix =
+  | { "type": "addToProject"; workItemId: WorkItemId }
+  | { "type": "reload"; workItemIds: Array<WorkItemId> }
+  | { "type": "convertTrackedToSubIssues"; workItemId: WorkItemId };
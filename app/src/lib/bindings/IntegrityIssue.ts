@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IntegrityFix } from "./IntegrityFix";
+import type { IntegrityProblem } from "./IntegrityProblem";
+import type { WorkItemId } from "./WorkItemId";
+
+/**
+ * A problem found by [`WorkItems::check_integrity`].
+ */
+export type IntegrityIssue = {
+  workItemId: WorkItemId;
+  problem: IntegrityProblem;
+  /**
+   * `None` if there's nothing ghui can do about it.
+   */
+  fix: IntegrityFix | null;
+};
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WorkItemId } from "./WorkItemId";
+
+/**
+ * Something wrong with the shape of the hierarchy loaded from GitHub.
+ * Everything that walks the hierarchy assumes a tree, so these can make items
+ * appear in the wrong place or not at all.
+ */
+export type IntegrityProblem =
+  | { "type": "parentNotInProject"; parentId: WorkItemId }
+  | { "type": "subIssueNotInProject"; subIssueId: WorkItemId }
+  | { "type": "notListedByParent"; parentId: WorkItemId }
+  | { "type": "subIssueHasOtherParent"; subIssueId: WorkItemId }
+  | { "type": "crossRepoParent"; parentId: WorkItemId; parentRepo: string }
+  | { "type": "trackedNotSubIssues"; trackedIds: Array<WorkItemId> }
+  | { "type": "parentCycle"; cycle: Array<WorkItemId> };
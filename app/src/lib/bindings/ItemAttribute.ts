@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Properties of a work item that can be sorted on but aren't pivot fields.
+ */
+export type ItemAttribute =
+  | "title"
+  | "updatedAt"
+  | /**
+   * The issue or pull request number.
+   */
+  "number";
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Aggregates } from "./Aggregates";
 import type { NodeData } from "./NodeData";
 
 export type Node = {
@@ -8,4 +9,9 @@ export type Node = {
   hasChildren: boolean;
   isModified: boolean;
   isGhost: boolean;
+  /**
+   * Rollups over the work items under this node, for groups and parents
+   * when `PivotConfig::aggregates` isn't empty.
+   */
+  aggregates: Aggregates | null;
 };
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Node } from "./Node";
+
+/**
+ * One step in turning the nodes the frontend has into the current ones.
+ * Edits are applied in order, and each index refers to the nodes as they
+ * are after the previous edits.
+ */
+export type NodeEdit =
+  | { "type": "remove"; index: number; count: number }
+  | { "type": "insert"; index: number; nodes: Array<Node> }
+  | { "type": "move"; from: number; to: number }
+  | { "type": "update"; index: number; node: Node };
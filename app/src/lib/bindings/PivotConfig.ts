@@ -1,9 +1,18 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Aggregate } from "./Aggregate";
 import type { Axis } from "./Axis";
 import type { MultiValueStrategy } from "./MultiValueStrategy";
 
 export type PivotConfig = {
   recipe: Array<Axis>;
   multiValueStrategy: MultiValueStrategy;
+  /**
+   * Whether `Hierarchy` axes show the ancestors of items in scope that
+   * aren't in scope themselves, unless an axis says otherwise.
+   */
   showGhostAncestors: boolean;
+  /**
+   * Rollups to compute for group and parent nodes.
+   */
+  aggregates: Array<Aggregate>;
 };
@@ -13,4 +13,12 @@ export type PivotField =
   | "repository"
   | "issueType"
   | "type"
-  | "state";
+  | "state"
+  | "updatedWeek"
+  | "updatedMonth"
+  | "createdMonth"
+  | "updatedAge"
+  | "iterationStart"
+  | "parent"
+  | "root"
+  | { "ancestor": PivotField };
@@ -1,8 +1,13 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { DelayLoad } from "./DelayLoad";
 import type { PullRequestState } from "./PullRequestState";
+import type { WorkItemId } from "./WorkItemId";
 
 export type PullRequest = {
   state: DelayLoad<PullRequestState>;
   assignees: Array<string>;
+  /**
+   * Issues that this pull request closes when merged.
+   */
+  linkedIssues: DelayLoad<Array<WorkItemId>>;
 };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How serious the problems found by a sanitize rule are.
+ */
+export type RuleSeverity = "info" | "warning" | "error";
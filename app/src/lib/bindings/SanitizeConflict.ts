@@ -1,19 +1,36 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
-import type { FieldOptionId } from "./FieldOptionId";
+import type { ChangeData } from "./ChangeData";
 import type { WorkItemId } from "./WorkItemId";
 
 /**
- * A single Epic conflict found during sanitize: the item already has an Epic
- * set, but the hierarchy says it should inherit a different one.
+ * A conflict found during sanitize: a rule wants to change a value that is
+ * already set, so the change is left for the user to review.
  */
 export type SanitizeConflict = {
   workItemId: WorkItemId;
   /**
-   * The Epic currently set on the item.
+   * The field in conflict, as named by `Change::field_name`.
    */
-  currentEpic: FieldOptionId;
+  field: string;
   /**
-   * The Epic the hierarchy says this item should have.
+   * The value currently set on the item.
    */
-  proposedEpic: FieldOptionId;
+  current: ChangeData;
+  /**
+   * The value the rule says this item should have.
+   */
+  proposed: ChangeData;
+  /**
+   * Why the rule proposes the new value.
+   */
+  reason: string;
+  /**
+   * The item the proposed value comes from, e.g. the parent it is
+   * inherited from.
+   */
+  sourceItemId: WorkItemId | null;
+  /**
+   * Id of the rule that found the conflict.
+   */
+  rule: string;
 };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Change } from "./Change";
+import type { RuleSeverity } from "./RuleSeverity";
+
+/**
+ * A change proposed by a sanitize rule.
+ */
+export type SanitizeEntry = {
+  rule: string;
+  severity: RuleSeverity;
+  /**
+   * Whether the rule is marked safe to apply without review.
+   */
+  safe: boolean;
+  change: Change;
+};
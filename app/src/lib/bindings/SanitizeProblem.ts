@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuleSeverity } from "./RuleSeverity";
+import type { WorkItemId } from "./WorkItemId";
+
+/**
+ * An item flagged by a sanitize rule that needs a human to fix it.
+ */
+export type SanitizeProblem = {
+  workItemId: WorkItemId;
+  rule: string;
+  severity: RuleSeverity;
+  /**
+   * What's wrong with the item, e.g. "missing Kind, Priority".
+   */
+  description: string;
+};
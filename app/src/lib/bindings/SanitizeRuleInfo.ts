@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuleSeverity } from "./RuleSeverity";
+
+/**
+ * Describes a rule and its effective settings, for display.
+ */
+export type SanitizeRuleInfo = {
+  id: string;
+  description: string;
+  severity: RuleSeverity;
+  enabled: boolean;
+  safe: boolean;
+};
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Broad classification of a save failure, so callers can decide whether a
+ * retry is likely to help.
+ */
+export type SaveErrorKind =
+  | "connectivity"
+  | "graphQl"
+  | "unexpectedResponse"
+  | "unexpectedData"
+  | "ghCli"
+  | "other";
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SavedChange } from "./SavedChange";
+
+/**
+ * The changes committed by a single save.
+ */
+export type SaveRecord = {
+  /**
+   * Sequential number identifying this save, starting at 1.
+   */
+  number: number;
+  changes: Array<SavedChange>;
+};
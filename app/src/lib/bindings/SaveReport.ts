@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangeResult } from "./ChangeResult";
+
+/**
+ * The result of `Changes::save`: one entry per change that was
+ * pending when the save started.
+ */
+export type SaveReport = { results: Array<ChangeResult>; durationMs: number };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Change } from "./Change";
+
+/**
+ * A change that was committed to GitHub, along with the change that would
+ * undo it.
+ */
+export type SavedChange = {
+  change: Change;
+  /**
+   * `None` if the value that was overwritten isn't known (for example,
+   * because the field hadn't been loaded yet).
+   */
+  inverse: Change | null;
+};
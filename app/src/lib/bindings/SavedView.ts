@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Filters } from "./Filters";
+import type { PivotConfig } from "./PivotConfig";
+
+export type SavedView = {
+  name: string;
+  filters: Filters;
+  pivotConfig: PivotConfig;
+};
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WorkItemId } from "./WorkItemId";
+
+export type SearchResult = {
+  workItemId: WorkItemId;
+  score: number;
+  /**
+   * For each node that shows the item in the current view, the ids of the
+   * nodes from the top of the tree down to it.  Empty if the view doesn't
+   * show the item.
+   */
+  paths: Array<Array<string>>;
+};
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ItemAttribute } from "./ItemAttribute";
+import type { PivotField } from "./PivotField";
+
+export type SortField = PivotField | ItemAttribute;
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SortField } from "./SortField";
+
+/**
+ * One key of a `Sort` axis.  Keys are applied in order, each breaking ties
+ * left by the ones before it.
+ */
+export type SortKey = { field: SortField; descending: boolean };
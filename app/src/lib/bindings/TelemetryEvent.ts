@@ -23,6 +23,12 @@ export type TelemetryEvent =
   | { "event": "redo" }
   | { "event": "sanitize"; changes_count: number; conflicts_count: number }
   | { "event": "convert_tracked" }
+  | {
+    "event": "convert_all_tracked";
+    changes_count: number;
+    conflicts_count: number;
+  }
+  | { "event": "revert_save"; changes_count: number }
   | { "event": "add_item_from_url"; has_parent: boolean }
   | { "event": "mode_switched"; to: string }
   | { "event": "log_panel_toggled"; open: boolean }
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WorkItemId } from "./WorkItemId";
+
+/**
+ * A tracked issue that wasn't made a sub-issue of its tracker because it
+ * already has a parent.
+ */
+export type TrackedConflict = {
+  /**
+   * The tracked issue.
+   */
+  workItemId: WorkItemId;
+  /**
+   * The issue that tracks it.
+   */
+  trackerId: WorkItemId;
+  /**
+   * The issue's current parent, or the tracker it has already been given
+   * to earlier in the same conversion.
+   */
+  parentId: WorkItemId;
+};
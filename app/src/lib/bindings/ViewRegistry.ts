@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SavedView } from "./SavedView";
+
+export type ViewRegistry = {
+  views: Array<SavedView>;
+  /**
+   * The view applied when ghui starts.
+   */
+  defaultView: string | null;
+};
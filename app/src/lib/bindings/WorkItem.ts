@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DelayLoad } from "./DelayLoad";
 import type { ProjectItem } from "./ProjectItem";
 import type { WorkItemData } from "./WorkItemData";
 import type { WorkItemId } from "./WorkItemId";
@@ -7,8 +8,21 @@ export type WorkItem = {
   id: WorkItemId;
   title: string;
   updatedAt: string;
+  /**
+   * Items cached before this was fetched don't have it.
+   */
+  createdAt: string;
   resourcePath: string | null;
   repoNameWithOwner: string | null;
+  /**
+   * Fetched separately from the rest of the item, when it is first
+   * searched for.
+   */
+  body: DelayLoad<string>;
+  /**
+   * The text of the most recent comments, oldest first.
+   */
+  comments: DelayLoad<Array<string>>;
   data: WorkItemData;
   projectItem: ProjectItem;
 };
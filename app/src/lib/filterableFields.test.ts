@@ -58,6 +58,7 @@ function makeData(): Data {
       priority: [],
       assignee: [],
       hideClosed: false,
+      query: null,
     },
   } as unknown as Data;
 }
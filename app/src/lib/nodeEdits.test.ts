@@ -0,0 +1,38 @@
+import { describe, expect, it } from "vitest";
+import type { Node } from "./bindings/Node";
+import { applyNodeEdits } from "./nodeEdits";
+
+function node(id: string, level: number): Node {
+  return {
+    id,
+    level,
+    data: { type: "workItem", workItemId: id },
+    hasChildren: false,
+    isModified: false,
+    isGhost: false,
+    aggregates: null,
+  };
+}
+
+describe("applyNodeEdits", () => {
+  it("applies edits in order", () => {
+    const old = [node("a", 0), node("b", 1), node("c", 1), node("d", 0)];
+    const updated = { ...node("c", 1), hasChildren: true };
+
+    const result = applyNodeEdits(old, [
+      { type: "remove", index: 1, count: 1 },
+      { type: "move", from: 2, to: 0 },
+      { type: "insert", index: 1, nodes: [node("x", 1)] },
+      { type: "update", index: 3, node: updated },
+    ]);
+
+    expect(result.map((n) => n.id)).toEqual(["d", "x", "a", "c"]);
+    expect(result[3]).toBe(updated);
+  });
+
+  it("leaves the original nodes unchanged", () => {
+    const old = [node("a", 0), node("b", 0)];
+    applyNodeEdits(old, [{ type: "remove", index: 0, count: 2 }]);
+    expect(old.map((n) => n.id)).toEqual(["a", "b"]);
+  });
+});
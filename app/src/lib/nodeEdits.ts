@@ -0,0 +1,29 @@
+import type { Node } from "./bindings/Node";
+import type { NodeEdit } from "./bindings/NodeEdit";
+
+/**
+ * Returns `nodes` with `edits` applied, in order, as sent by the backend in a
+ * `DataPatch`. `nodes` itself is left unchanged.
+ */
+export function applyNodeEdits(nodes: Node[], edits: NodeEdit[]): Node[] {
+  const result = [...nodes];
+  for (const edit of edits) {
+    switch (edit.type) {
+      case "remove":
+        result.splice(edit.index, edit.count);
+        break;
+      case "insert":
+        result.splice(edit.index, 0, ...edit.nodes);
+        break;
+      case "move": {
+        const [node] = result.splice(edit.from, 1);
+        result.splice(edit.to, 0, node);
+        break;
+      }
+      case "update":
+        result[edit.index] = edit.node;
+        break;
+    }
+  }
+  return result;
+}
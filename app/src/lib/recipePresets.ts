@@ -41,4 +41,12 @@ export const PRESETS: ReadonlyArray<{ label: string; recipe: string }> = [
   },
   { label: "Just sort by epic (no grouping)", recipe: "Sort(Epic)" },
   { label: "PRs vs Issues", recipe: "Pivot(Type) → Group(Status)" },
+  {
+    label: "Stale items, by time since last update",
+    recipe: "Pivot(UpdatedAge) → Group(Status)",
+  },
+  {
+    label: "Iteration plan, including empty iterations",
+    recipe: "Pivot(Iteration, empty) → Hierarchy",
+  },
 ];
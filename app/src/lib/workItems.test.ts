@@ -7,6 +7,7 @@ function makeWorkItem(id: string, title: string): WorkItem {
     id,
     title,
     updatedAt: "2026-01-01T00:00:00Z",
+    createdAt: "2026-01-01T00:00:00Z",
     projectItem: {
       id: `project-${id}`,
       databaseId: "1",
@@ -23,6 +24,8 @@ function makeWorkItem(id: string, title: string): WorkItem {
     data: { type: "draftIssue" },
     resourcePath: null,
     repoNameWithOwner: null,
+    body: { loadState: "notLoaded" },
+    comments: { loadState: "notLoaded" },
   };
 }
 
@@ -0,0 +1,231 @@
+//! The JSON caches kept in the user's home directory.  Each file wraps its
+//! data in an envelope recording the version of the format it was written
+//! with, so that a cache from an older ghui can be migrated forward, and is
+//! replaced atomically so that a crash mid-write leaves the previous cache
+//! intact.
+
+use anyhow::Result;
+use dirs::home_dir;
+use log::warn;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Upgrades the data of a cache by one version.
+pub type Migration = fn(Value) -> Result<Value>;
+
+/// Describes one kind of cache file.
+pub struct CacheFormat {
+    /// Used in log messages.
+    pub name: &'static str,
+    /// `migrations[n]` upgrades data from version `n` to `n + 1`, so the
+    /// current version is the number of migrations.  Version 0 is the
+    /// unversioned format written before caches had an envelope.
+    pub migrations: &'static [Migration],
+}
+
+impl CacheFormat {
+    pub fn version(&self) -> usize {
+        self.migrations.len()
+    }
+}
+
+/// The migration from the unversioned format, whose data was the whole file.
+pub fn from_unversioned(data: Value) -> Result<Value> {
+    Ok(data)
+}
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    version: usize,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Envelope {
+    version: usize,
+    data: Value,
+}
+
+pub fn get_appdata_path(name: &str) -> PathBuf {
+    let mut path = home_dir().unwrap();
+    path.push(format!("{name}.ghui.json"));
+    path
+}
+
+/// Loads the cache at `path`, migrating it to the current version of
+/// `format`.  Returns `None` if there is no cache, or if it had to be
+/// discarded because it couldn't be read.
+pub fn load<T: DeserializeOwned>(path: &Path, format: &CacheFormat) -> Result<Option<T>> {
+    let contents = match fs::read(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    match decode(&contents, format) {
+        Ok(data) => Ok(Some(data)),
+        Err(reason) => {
+            warn!("Discarding {} cache {path:?}: {reason}", format.name);
+            Ok(None)
+        }
+    }
+}
+
+fn decode<T: DeserializeOwned>(contents: &[u8], format: &CacheFormat) -> Result<T, String> {
+    let value: Value =
+        serde_json::from_slice(contents).map_err(|e| format!("it isn't valid JSON: {e}"))?;
+
+    let (version, data) = match serde_json::from_value::<Envelope>(value.clone()) {
+        Ok(envelope) => (envelope.version, envelope.data),
+        Err(_) => (0, value),
+    };
+    upgrade(version, data, format)
+}
+
+/// Migrates `data`, written with `version` of `format`, to the current
+/// version.  For data kept outside an envelope, such as the rows of the work
+/// item cache.  On failure, returns why the data can't be used.
+pub fn upgrade<T: DeserializeOwned>(
+    version: usize,
+    mut data: Value,
+    format: &CacheFormat,
+) -> Result<T, String> {
+    if version > format.version() {
+        return Err(format!(
+            "it was written by a newer version of ghui (version {version}, expected {})",
+            format.version()
+        ));
+    }
+
+    for (from, migration) in format.migrations.iter().enumerate().skip(version) {
+        data = migration(data)
+            .map_err(|e| format!("failed to migrate it from version {from}: {e}"))?;
+    }
+
+    serde_json::from_value(data)
+        .map_err(|e| format!("it doesn't match version {}: {e}", format.version()))
+}
+
+/// Saves `data` to the cache at `path`, tagged with the current version of
+/// `format`.
+pub fn save<T: Serialize>(path: &Path, format: &CacheFormat, data: &T) -> Result<()> {
+    let envelope = EnvelopeRef {
+        version: format.version(),
+        data,
+    };
+    write_atomically(path, &serde_json::to_vec_pretty(&envelope)?)
+}
+
+/// Writes `contents` to a temporary file next to `path` and then renames it
+/// over `path`, so that readers see either the old or the new contents.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    let result = (|| -> Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const FORMAT_V1: CacheFormat = CacheFormat {
+        name: "test",
+        migrations: &[from_unversioned],
+    };
+
+    fn rename_count(mut data: Value) -> Result<Value> {
+        let count = data
+            .as_object_mut()
+            .and_then(|object| object.remove("count"))
+            .ok_or_else(|| anyhow::anyhow!("missing count"))?;
+        Ok(json!({ "total": count }))
+    }
+
+    const FORMAT_V2: CacheFormat = CacheFormat {
+        name: "test",
+        migrations: &[from_unversioned, rename_count],
+    };
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Counted {
+        count: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Totalled {
+        total: u32,
+    }
+
+    #[test]
+    fn test_appdata_round_trip_writes_envelope() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.ghui.json");
+
+        save(&path, &FORMAT_V1, &Counted { count: 3 }).unwrap();
+
+        let written: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(written, json!({ "version": 1, "data": { "count": 3 } }));
+        assert_eq!(
+            load::<Counted>(&path, &FORMAT_V1).unwrap(),
+            Some(Counted { count: 3 })
+        );
+        assert!(!dir.path().join("test.ghui.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_appdata_migrates_older_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.ghui.json");
+
+        fs::write(&path, r#"{ "count": 4 }"#).unwrap();
+        assert_eq!(
+            load::<Totalled>(&path, &FORMAT_V2).unwrap(),
+            Some(Totalled { total: 4 })
+        );
+
+        save(&path, &FORMAT_V1, &Counted { count: 5 }).unwrap();
+        assert_eq!(
+            load::<Totalled>(&path, &FORMAT_V2).unwrap(),
+            Some(Totalled { total: 5 })
+        );
+    }
+
+    #[test]
+    fn test_appdata_discards_unreadable_caches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.ghui.json");
+
+        assert_eq!(load::<Counted>(&path, &FORMAT_V1).unwrap(), None);
+
+        fs::write(&path, r#"{ "count": "#).unwrap();
+        assert_eq!(load::<Counted>(&path, &FORMAT_V1).unwrap(), None);
+
+        fs::write(&path, r#"{ "version": 1, "data": { "total": 1 } }"#).unwrap();
+        assert_eq!(load::<Counted>(&path, &FORMAT_V1).unwrap(), None);
+
+        fs::write(&path, r#"{ "version": 2, "data": { "total": 1 } }"#).unwrap();
+        assert_eq!(load::<Counted>(&path, &FORMAT_V1).unwrap(), None);
+
+        fs::write(&path, r#"{ "total": 1 }"#).unwrap();
+        assert_eq!(load::<Totalled>(&path, &FORMAT_V2).unwrap(), None);
+    }
+}
@@ -0,0 +1,386 @@
+//! The local cache of work items: an SQLite database with a row per item, so
+//! that refreshing a few items only writes those rows rather than the whole
+//! cache.
+
+use crate::appdata::{self, CacheFormat};
+use anyhow::{Result, bail};
+use dirs::home_dir;
+use github_graphql::data::{WorkItem, WorkItemId, WorkItems};
+use log::{info, warn};
+use rusqlite::{Connection, OptionalExtension, Transaction, params};
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// The version of the tables below.  Bump it, and add a step to `migrate`,
+/// whenever they change.
+const SCHEMA_VERSION: i64 = 2;
+
+/// The format of each item's `data`, whose version is kept alongside it in
+/// `data_version`.  Add a migration whenever the JSON of `WorkItem` changes
+/// in a way that older rows can't be read as.
+const WORK_ITEM_FORMAT: CacheFormat = CacheFormat {
+    name: "work item",
+    migrations: &[appdata::from_unversioned],
+};
+
+const CACHE_FILENAME: &str = "work_items.ghui.sqlite";
+
+pub fn get_cache_path() -> PathBuf {
+    let mut path = home_dir().unwrap();
+    path.push(CACHE_FILENAME);
+    path
+}
+
+pub struct WorkItemCache {
+    connection: Connection,
+}
+
+impl WorkItemCache {
+    /// Opens the cache at `path`, creating it if needed.  A new cache is
+    /// filled from the JSON snapshot at `legacy_path`, which earlier versions
+    /// cached work items in, and the snapshot is then deleted.
+    pub fn open(path: &Path, legacy_path: &Path) -> Result<Self> {
+        let mut cache = WorkItemCache {
+            connection: Connection::open(path)?,
+        };
+        cache.migrate(legacy_path)?;
+        Ok(cache)
+    }
+
+    fn migrate(&mut self, legacy_path: &Path) -> Result<()> {
+        let tx = self.connection.transaction()?;
+        tx.execute_batch("CREATE TABLE IF NOT EXISTS schema_version (version INTEGER NOT NULL);")?;
+        let version: i64 = tx
+            .query_row("SELECT version FROM schema_version", [], |row| row.get(0))
+            .optional()?
+            .unwrap_or(0);
+
+        if version > SCHEMA_VERSION {
+            bail!(
+                "the work item cache was written by a newer version of ghui (schema {version}, expected {SCHEMA_VERSION})"
+            );
+        }
+        if version == SCHEMA_VERSION {
+            return Ok(());
+        }
+
+        if version < 1 {
+            tx.execute_batch(
+                "CREATE TABLE work_items (
+                    id TEXT PRIMARY KEY NOT NULL,
+                    position INTEGER NOT NULL,
+                    data TEXT NOT NULL
+                );
+                CREATE INDEX work_items_position ON work_items (position);",
+            )?;
+        }
+        if version < 2 {
+            // Rows written before this are data version 0.
+            tx.execute_batch(
+                "ALTER TABLE work_items ADD COLUMN data_version INTEGER NOT NULL DEFAULT 0;",
+            )?;
+        }
+        let imported_legacy = version < 1 && import_legacy(&tx, legacy_path)?;
+
+        tx.execute("DELETE FROM schema_version", [])?;
+        tx.execute(
+            "INSERT INTO schema_version (version) VALUES (?1)",
+            [SCHEMA_VERSION],
+        )?;
+        tx.commit()?;
+
+        if imported_legacy && let Err(e) = fs::remove_file(legacy_path) {
+            warn!("failed to delete migrated work item cache {legacy_path:?}: {e}");
+        }
+        Ok(())
+    }
+
+    /// Loads the cached items, in the order they were first cached.  Returns
+    /// `None` if nothing has been cached yet, or if any item can't be read:
+    /// the items are only fetched again when the cache is empty, so loading
+    /// the rest would leave that item missing.
+    pub fn load(&self) -> Result<Option<WorkItems>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, data_version, data FROM work_items ORDER BY position")?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut items = Vec::new();
+        for row in rows {
+            let (id, version, data) = row?;
+            match decode_item(version, &data) {
+                Ok(item) => items.push(item),
+                Err(reason) => {
+                    warn!("Discarding the work item cache, as item {id} can't be read: {reason}");
+                    return Ok(None);
+                }
+            }
+        }
+
+        if items.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(WorkItems::from_iter(items)))
+    }
+
+    /// Replaces everything in the cache with `work_items`.
+    pub fn replace_all(&mut self, work_items: &WorkItems) -> Result<()> {
+        let tx = self.connection.transaction()?;
+        tx.execute("DELETE FROM work_items", [])?;
+        upsert_items(&tx, work_items.iter().filter_map(|id| work_items.get(id)))?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Adds or updates `items`, leaving the rest of the cache as it is.  New
+    /// items go after those already cached.
+    pub fn upsert<'a>(&mut self, items: impl IntoIterator<Item = &'a WorkItem>) -> Result<()> {
+        let tx = self.connection.transaction()?;
+        upsert_items(&tx, items)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, ids: &[WorkItemId]) -> Result<()> {
+        let tx = self.connection.transaction()?;
+        for id in ids {
+            tx.execute("DELETE FROM work_items WHERE id = ?1", [&id.0])?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn upsert_items<'a>(tx: &Transaction, items: impl IntoIterator<Item = &'a WorkItem>) -> Result<()> {
+    let mut statement = tx.prepare(
+        "INSERT INTO work_items (id, position, data_version, data)
+         VALUES (?1, (SELECT COALESCE(MAX(position), -1) + 1 FROM work_items), ?2, ?3)
+         ON CONFLICT (id) DO UPDATE SET
+            data_version = excluded.data_version,
+            data = excluded.data",
+    )?;
+    let data_version = WORK_ITEM_FORMAT.version() as i64;
+    for item in items {
+        statement.execute(params![
+            item.id.0,
+            data_version,
+            serde_json::to_string(item)?
+        ])?;
+    }
+    Ok(())
+}
+
+fn decode_item(version: i64, data: &str) -> Result<WorkItem, String> {
+    let version =
+        usize::try_from(version).map_err(|_| format!("its version {version} is invalid"))?;
+    let data = serde_json::from_str(data).map_err(|e| format!("it isn't valid JSON: {e}"))?;
+    appdata::upgrade(version, data, &WORK_ITEM_FORMAT)
+}
+
+/// Copies the items in the JSON snapshot at `path` into the cache.  Returns
+/// whether there was a snapshot to copy.  A snapshot that can't be read is
+/// skipped, since the items will be fetched again anyway.
+fn import_legacy(tx: &Transaction, path: &Path) -> Result<bool> {
+    let reader = match fs::File::open(path) {
+        Ok(reader) => reader,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+
+    info!("Migrating work item cache from {path:?}");
+    let work_items: WorkItems = match serde_json::from_reader(BufReader::new(reader)) {
+        Ok(work_items) => work_items,
+        Err(e) => {
+            warn!("failed to read work item cache {path:?}, skipping it: {e}");
+            return Ok(true);
+        }
+    };
+    upsert_items(tx, work_items.iter().filter_map(|id| work_items.get(id)))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use github_graphql::data::test_helpers::TestData;
+    use std::io::BufWriter;
+
+    fn ids(work_items: &WorkItems) -> Vec<&WorkItemId> {
+        work_items.iter().collect()
+    }
+
+    #[test]
+    fn test_cache_upsert_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILENAME);
+        let legacy_path = dir.path().join("work_items.ghui.json");
+
+        let mut data = TestData::default();
+        let a = data.build().add();
+        let b = data.build().add();
+
+        let mut cache = WorkItemCache::open(&path, &legacy_path).unwrap();
+        assert_eq!(cache.load().unwrap(), None);
+
+        cache.replace_all(&data.work_items).unwrap();
+        assert_eq!(cache.load().unwrap().as_ref(), Some(&data.work_items));
+
+        // Updated items keep their place; new ones go at the end.
+        let c = data.build().add();
+        data.work_items.get_mut(&a).unwrap().title = "Updated".to_owned();
+        let updated = [
+            data.work_items.get(&c).unwrap(),
+            data.work_items.get(&a).unwrap(),
+        ];
+        cache.upsert(updated).unwrap();
+
+        let reopened = WorkItemCache::open(&path, &legacy_path).unwrap();
+        let loaded = reopened.load().unwrap().unwrap();
+        assert_eq!(ids(&loaded), vec![&a, &b, &c]);
+        assert_eq!(loaded.get(&a).unwrap().title, "Updated");
+
+        cache.remove(std::slice::from_ref(&b)).unwrap();
+        let loaded = cache.load().unwrap().unwrap();
+        assert_eq!(ids(&loaded), vec![&a, &c]);
+
+        let only_b = WorkItems::from_iter(data.work_items.get(&b).cloned());
+        cache.replace_all(&only_b).unwrap();
+        assert_eq!(ids(&cache.load().unwrap().unwrap()), vec![&b]);
+    }
+
+    #[test]
+    fn test_cache_migrates_json_snapshot_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILENAME);
+        let legacy_path = dir.path().join("work_items.ghui.json");
+
+        let mut data = TestData::default();
+        data.build().add();
+        data.build().add();
+        let writer = fs::File::create(&legacy_path).unwrap();
+        serde_json::to_writer_pretty(BufWriter::new(writer), &data.work_items).unwrap();
+
+        let cache = WorkItemCache::open(&path, &legacy_path).unwrap();
+        assert_eq!(cache.load().unwrap(), Some(data.work_items.clone()));
+        assert!(!legacy_path.exists());
+
+        // A snapshot that turns up later is left alone.
+        fs::write(&legacy_path, "[]").unwrap();
+        drop(cache);
+        let cache = WorkItemCache::open(&path, &legacy_path).unwrap();
+        assert_eq!(cache.load().unwrap(), Some(data.work_items));
+        assert!(legacy_path.exists());
+    }
+
+    #[test]
+    fn test_cache_skips_unreadable_json_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILENAME);
+        let legacy_path = dir.path().join("work_items.ghui.json");
+        fs::write(&legacy_path, "not json").unwrap();
+
+        let cache = WorkItemCache::open(&path, &legacy_path).unwrap();
+        assert_eq!(cache.load().unwrap(), None);
+        assert!(!legacy_path.exists());
+    }
+
+    #[test]
+    fn test_cache_migrates_schema_1() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILENAME);
+        let legacy_path = dir.path().join("work_items.ghui.json");
+
+        let mut data = TestData::default();
+        let a = data.build().add();
+        let item = data.work_items.get(&a).unwrap();
+
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE schema_version (version INTEGER NOT NULL);
+                INSERT INTO schema_version (version) VALUES (1);
+                CREATE TABLE work_items (
+                    id TEXT PRIMARY KEY NOT NULL,
+                    position INTEGER NOT NULL,
+                    data TEXT NOT NULL
+                );",
+            )
+            .unwrap();
+        connection
+            .execute(
+                "INSERT INTO work_items (id, position, data) VALUES (?1, 0, ?2)",
+                params![a.0, serde_json::to_string(item).unwrap()],
+            )
+            .unwrap();
+        drop(connection);
+
+        let cache = WorkItemCache::open(&path, &legacy_path).unwrap();
+        assert_eq!(cache.load().unwrap(), Some(data.work_items));
+    }
+
+    #[test]
+    fn test_cache_discards_unreadable_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILENAME);
+        let legacy_path = dir.path().join("work_items.ghui.json");
+
+        let mut data = TestData::default();
+        data.build().add();
+        let b = data.build().add();
+
+        let mut cache = WorkItemCache::open(&path, &legacy_path).unwrap();
+        cache.replace_all(&data.work_items).unwrap();
+        let set_data_version = |version: usize| {
+            cache
+                .connection
+                .execute(
+                    "UPDATE work_items SET data_version = ?1 WHERE id = ?2",
+                    params![version as i64, b.0],
+                )
+                .unwrap();
+        };
+
+        set_data_version(WORK_ITEM_FORMAT.version() + 1);
+        assert_eq!(cache.load().unwrap(), None);
+
+        set_data_version(WORK_ITEM_FORMAT.version());
+        cache
+            .connection
+            .execute("UPDATE work_items SET data = '{}' WHERE id = ?1", [&b.0])
+            .unwrap();
+        assert_eq!(cache.load().unwrap(), None);
+
+        // Replacing the items makes the cache readable again.
+        cache.replace_all(&data.work_items).unwrap();
+        assert_eq!(cache.load().unwrap(), Some(data.work_items));
+    }
+
+    #[test]
+    fn test_cache_rejects_newer_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILENAME);
+        let legacy_path = dir.path().join("work_items.ghui.json");
+
+        WorkItemCache::open(&path, &legacy_path).unwrap();
+        Connection::open(&path)
+            .unwrap()
+            .execute(
+                "UPDATE schema_version SET version = ?1",
+                [SCHEMA_VERSION + 1],
+            )
+            .unwrap();
+
+        let error = WorkItemCache::open(&path, &legacy_path)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("newer version of ghui"), "{error}");
+    }
+}
@@ -0,0 +1,195 @@
+//! `ghui://` links that open a view of the project, and optionally focus a
+//! work item in it, e.g.
+//!
+//! `ghui://open?project=llvm/4&recipe=Pivot(Status)&query=epic:X&item=I_kwDO`
+//!
+//! * `project` (required): `owner/number` of the project the link is for.
+//! * `recipe`: a recipe, as written by `recipe_to_string`.
+//! * `filters`: the full `Filters`, as JSON.
+//! * `query`: a filter query; replaces the query in `filters`, if both are
+//!   given.
+//! * `item`: the id of the work item to focus.
+
+use crate::Filters;
+use anyhow::{Result, bail};
+use github_graphql::{
+    data::WorkItemId,
+    pivot::{Axis, parse_recipe, recipe_to_string},
+    query::{Query, parse_query},
+};
+use url::Url;
+
+pub const SCHEME: &str = "ghui";
+
+/// The project ghui reads; see `custom_fields_query.graphql`.
+pub const PROJECT: &str = "llvm/4";
+
+#[derive(Debug, Default, Clone)]
+pub struct DeepLink {
+    pub recipe: Option<Vec<Axis>>,
+    pub filters: Option<Filters>,
+    pub query: Option<Query>,
+    pub item: Option<WorkItemId>,
+}
+
+impl DeepLink {
+    /// Returns `filters` with this link's filters applied.
+    pub fn apply_filters(&self, filters: &Filters) -> Filters {
+        let mut filters = self.filters.clone().unwrap_or_else(|| filters.clone());
+        if let Some(query) = &self.query {
+            filters.query = Some(query.clone());
+        }
+        filters
+    }
+}
+
+pub fn parse(text: &str) -> Result<DeepLink> {
+    let url = Url::parse(text).map_err(|e| anyhow::anyhow!("Not a valid link: {e}"))?;
+    if url.scheme() != SCHEME {
+        bail!("Not a {SCHEME}:// link: {text}");
+    }
+    if url.host_str() != Some("open") {
+        bail!("Unknown {SCHEME}:// link: {text}");
+    }
+
+    let mut link = DeepLink::default();
+    let mut project = None;
+    let mut seen = Vec::new();
+
+    for (key, value) in url.query_pairs() {
+        if seen.contains(&key) {
+            bail!("{key} is given more than once");
+        }
+        seen.push(key.clone());
+
+        match key.as_ref() {
+            "project" => project = Some(value.into_owned()),
+            "recipe" => {
+                link.recipe =
+                    Some(parse_recipe(&value).map_err(|e| anyhow::anyhow!("recipe: {e}"))?)
+            }
+            "filters" => {
+                link.filters = Some(
+                    serde_json::from_str(&value).map_err(|e| anyhow::anyhow!("filters: {e}"))?,
+                )
+            }
+            "query" => {
+                link.query = Some(parse_query(&value).map_err(|e| anyhow::anyhow!("query: {e}"))?)
+            }
+            "item" => {
+                if value.is_empty() {
+                    bail!("item must not be empty");
+                }
+                link.item = Some(WorkItemId(value.into_owned()));
+            }
+            _ => bail!("Unknown parameter: {key}"),
+        }
+    }
+
+    match project {
+        None => bail!("The link doesn't say which project it is for"),
+        Some(project) if project != PROJECT => {
+            bail!("The link is for project {project}, but ghui shows {PROJECT}")
+        }
+        Some(_) => Ok(link),
+    }
+}
+
+/// Builds a link that opens the given view, focusing `item` if given.
+pub fn to_url(recipe: &[Axis], filters: &Filters, item: Option<&WorkItemId>) -> String {
+    let mut url = Url::parse(&format!("{SCHEME}://open")).expect("valid base URL");
+    {
+        let mut query = url.query_pairs_mut();
+        query.append_pair("project", PROJECT);
+        query.append_pair("recipe", &recipe_to_string(recipe));
+        query.append_pair(
+            "filters",
+            &serde_json::to_string(filters).expect("filters serialize to JSON"),
+        );
+        if let Some(item) = item {
+            query.append_pair("item", &item.0);
+        }
+    }
+    url.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use github_graphql::{data::FieldOptionId, pivot::PivotField};
+
+    #[test]
+    fn test_round_trip() {
+        let filters = Filters {
+            status: vec![Some(FieldOptionId("done".to_owned())), None],
+            hide_closed: true,
+            query: Some(parse_query("epic:\"DML Demo\"").unwrap()),
+            ..Default::default()
+        };
+        let recipe = vec![
+            Axis::Pivot(PivotField::Status.into()),
+            Axis::Hierarchy(None),
+        ];
+        let item = WorkItemId("I_kwDO123".to_owned());
+
+        let url = to_url(&recipe, &filters, Some(&item));
+        assert!(url.starts_with("ghui://open?project=llvm%2F4&recipe=Pivot%28Status%29"));
+
+        let link = parse(&url).unwrap();
+        assert_eq!(link.recipe, Some(recipe));
+        assert_eq!(link.item, Some(item));
+        let parsed_filters = link.filters.unwrap();
+        assert_eq!(parsed_filters.status, filters.status);
+        assert!(parsed_filters.hide_closed);
+        assert_eq!(parsed_filters.query, filters.query);
+    }
+
+    #[test]
+    fn test_query_replaces_current_query() {
+        let link = parse("ghui://open?project=llvm/4&query=is:open").unwrap();
+        assert!(link.recipe.is_none());
+
+        let current = Filters {
+            hide_closed: true,
+            query: Some(parse_query("epic:X").unwrap()),
+            ..Default::default()
+        };
+        let filters = link.apply_filters(&current);
+        assert!(filters.hide_closed);
+        assert_eq!(filters.query, Some(parse_query("is:open").unwrap()));
+    }
+
+    #[test]
+    fn test_invalid_links() {
+        let error = |text: &str| parse(text).unwrap_err().to_string();
+
+        assert_eq!(
+            error("https://open?project=llvm/4"),
+            "Not a ghui:// link: https://open?project=llvm/4"
+        );
+        assert_eq!(
+            error("ghui://view?project=llvm/4"),
+            "Unknown ghui:// link: ghui://view?project=llvm/4"
+        );
+        assert_eq!(
+            error("ghui://open?recipe=Hierarchy"),
+            "The link doesn't say which project it is for"
+        );
+        assert_eq!(
+            error("ghui://open?project=octo/1"),
+            "The link is for project octo/1, but ghui shows llvm/4"
+        );
+        assert_eq!(
+            error("ghui://open?project=llvm/4&recipe=Pivot(Nope)"),
+            "recipe: Unknown field: Nope"
+        );
+        assert_eq!(
+            error("ghui://open?project=llvm/4&item=a&item=b"),
+            "item is given more than once"
+        );
+        assert_eq!(
+            error("ghui://open?project=llvm/4&focus=a"),
+            "Unknown parameter: focus"
+        );
+    }
+}
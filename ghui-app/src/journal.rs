@@ -0,0 +1,302 @@
+//! Append-only record of every change committed to GitHub through ghui.
+//!
+//! Each line of the journal file is one [`JournalEntry`] serialized as JSON.
+//! Unlike telemetry, the journal is never truncated and stores human-readable
+//! values so that it can answer "who changed this, and when" long after the
+//! option ids have been forgotten.
+
+use crate::{appdata, telemetry::format_iso_timestamp};
+use anyhow::Result;
+use github_graphql::data::{ChangeOutcome, Fields, SaveReport, WorkItems};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// ISO 8601 UTC timestamp of the save.
+    pub ts: String,
+    /// GitHub login of the user that made the change.
+    pub login: String,
+    /// URL of the changed item (or its id when no URL is known).
+    pub item: String,
+    pub title: String,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// Builds journal entries for every change in `report` that was committed
+/// successfully.  `work_items` must be the state from *before* the save so that
+/// old values can be recorded.
+pub fn entries_for_report(
+    report: &SaveReport,
+    login: &str,
+    fields: &Fields,
+    work_items: &WorkItems,
+) -> Vec<JournalEntry> {
+    let ts = format_iso_timestamp();
+
+    report
+        .results
+        .iter()
+        .filter(|result| result.outcome == ChangeOutcome::Succeeded)
+        .map(|result| {
+            let change = &result.change;
+            let work_item = work_items.get(&change.work_item_id);
+            JournalEntry {
+                ts: ts.clone(),
+                login: login.to_owned(),
+                item: work_item
+                    .map(|w| w.describe())
+                    .unwrap_or_else(|| change.work_item_id.0.clone()),
+                title: work_item.map(|w| w.title.clone()).unwrap_or_default(),
+                field: change.field_name().to_owned(),
+                old_value: change.old_value_name(fields, work_items),
+                new_value: change.new_value_name(fields, work_items),
+            }
+        })
+        .collect()
+}
+
+/// Appends the changes that `report` committed successfully to the journal,
+/// as made by `login`.  Callers only need the login when
+/// `report.succeeded_count()` is non-zero.
+///
+/// Errors are logged rather than returned: the changes are already on GitHub,
+/// so failing to journal them shouldn't look like a failed save.
+pub fn record_save(report: &SaveReport, login: &str, fields: &Fields, work_items: &WorkItems) {
+    let entries = entries_for_report(report, login, fields, work_items);
+    if let Err(e) = append(&get_journal_file_path(), &entries) {
+        warn!("failed to write journal: {e}");
+    }
+}
+
+/// Returns the path to the journal file (`~/journal.ghui.jsonl`).
+pub fn get_journal_file_path() -> PathBuf {
+    appdata::get_appdata_path("journal").with_extension("jsonl")
+}
+
+/// Appends `entries` to the journal at `path`, creating it if necessary.
+pub fn append(path: &Path, entries: &[JournalEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry)?);
+        lines.push('\n');
+    }
+    // A single write keeps a batch together even if another process is
+    // appending at the same time.
+    file.write_all(lines.as_bytes())?;
+    Ok(())
+}
+
+/// Reads all entries from the journal at `path`.  Lines that can't be parsed
+/// are skipped with a warning.  A missing journal is treated as empty.
+pub fn read(path: &Path) -> Result<Vec<JournalEntry>> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut entries = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => warn!("skipping journal line {}: {e}", number + 1),
+        }
+    }
+    Ok(entries)
+}
+
+/// Filters for [`read`] results.  Empty filters match everything.
+#[derive(Debug, Default, Clone)]
+pub struct JournalQuery {
+    /// Only entries at or after this timestamp prefix (e.g. `2026-03-01`).
+    pub since: Option<String>,
+    /// Only entries at or before this timestamp prefix.  `2026-03-01` includes
+    /// the whole day.
+    pub until: Option<String>,
+    /// Case-insensitive substring of the item URL or title.
+    pub item: Option<String>,
+    /// Field name, e.g. `epic` or `status`.
+    pub field: Option<String>,
+}
+
+impl JournalQuery {
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        if let Some(since) = &self.since
+            && entry.ts.as_str() < since.as_str()
+        {
+            return false;
+        }
+
+        if let Some(until) = &self.until {
+            let prefix = entry.ts.get(..until.len()).unwrap_or(entry.ts.as_str());
+            if prefix > until.as_str() {
+                return false;
+            }
+        }
+
+        if let Some(item) = &self.item {
+            let item = item.to_lowercase();
+            if !entry.item.to_lowercase().contains(&item)
+                && !entry.title.to_lowercase().contains(&item)
+            {
+                return false;
+            }
+        }
+
+        if let Some(field) = &self.field
+            && !entry.field.eq_ignore_ascii_case(field)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use github_graphql::data::{
+        Change, ChangeData, ChangeResult, SaveErrorKind, test_helpers::TestData,
+    };
+
+    fn entry(ts: &str, item: &str, field: &str) -> JournalEntry {
+        JournalEntry {
+            ts: ts.to_owned(),
+            login: "octocat".to_owned(),
+            item: item.to_owned(),
+            title: "A title".to_owned(),
+            field: field.to_owned(),
+            old_value: None,
+            new_value: Some("Closed".to_owned()),
+        }
+    }
+
+    #[test]
+    fn test_append_and_read_round_trip() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let first = entry(
+            "2026-01-01T00:00:00.000Z",
+            "https://github.com/o/r/1",
+            "status",
+        );
+        let second = entry(
+            "2026-01-02T00:00:00.000Z",
+            "https://github.com/o/r/2",
+            "epic",
+        );
+
+        append(file.path(), std::slice::from_ref(&first)).unwrap();
+        append(file.path(), std::slice::from_ref(&second)).unwrap();
+
+        assert_eq!(read(file.path()).unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn test_read_skips_malformed_lines() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let good = entry("2026-01-01T00:00:00.000Z", "item", "status");
+        fs::write(
+            file.path(),
+            format!("not json\n{}\n", serde_json::to_string(&good).unwrap()),
+        )
+        .unwrap();
+
+        assert_eq!(read(file.path()).unwrap(), vec![good]);
+    }
+
+    #[test]
+    fn test_journal_file_is_in_appdata() {
+        let path = get_journal_file_path();
+        assert_eq!(path.file_name().unwrap(), "journal.ghui.jsonl");
+        assert_eq!(path.parent(), appdata::get_appdata_path("journal").parent());
+    }
+
+    #[test]
+    fn test_read_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read(&dir.path().join("missing.jsonl")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_query_filters_by_date_range() {
+        let query = JournalQuery {
+            since: Some("2026-01-02".to_owned()),
+            until: Some("2026-01-02".to_owned()),
+            ..Default::default()
+        };
+
+        assert!(!query.matches(&entry("2026-01-01T23:59:59.000Z", "i", "epic")));
+        assert!(query.matches(&entry("2026-01-02T12:00:00.000Z", "i", "epic")));
+        assert!(!query.matches(&entry("2026-01-03T00:00:00.000Z", "i", "epic")));
+    }
+
+    #[test]
+    fn test_query_filters_by_item_and_field() {
+        let query = JournalQuery {
+            item: Some("R/42".to_owned()),
+            field: Some("Epic".to_owned()),
+            ..Default::default()
+        };
+
+        let ts = "2026-01-01T00:00:00.000Z";
+        assert!(query.matches(&entry(ts, "https://github.com/o/r/42", "epic")));
+        assert!(!query.matches(&entry(ts, "https://github.com/o/r/42", "status")));
+        assert!(!query.matches(&entry(ts, "https://github.com/o/r/7", "epic")));
+    }
+
+    #[test]
+    fn test_entries_for_report_only_includes_succeeded_changes() {
+        let mut data = TestData::default();
+        let saved = data.build().status("Active").add();
+        let failed = data.build().status("Active").add();
+        let closed = data.fields.status.option_id(Some("Closed")).cloned();
+
+        let result = |work_item_id, outcome| ChangeResult {
+            change: Change {
+                work_item_id,
+                data: ChangeData::Status(closed.clone()),
+            },
+            outcome,
+            duration_ms: 0,
+        };
+
+        let report = SaveReport {
+            results: vec![
+                result(saved, ChangeOutcome::Succeeded),
+                result(
+                    failed,
+                    ChangeOutcome::Failed {
+                        kind: SaveErrorKind::GraphQl,
+                        message: "error".to_owned(),
+                        graphql_messages: Vec::new(),
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let entries = entries_for_report(&report, "octocat", &data.fields, &data.work_items);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].login, "octocat");
+        assert_eq!(entries[0].field, "status");
+        assert_eq!(entries[0].old_value.as_deref(), Some("Active"));
+        assert_eq!(entries[0].new_value.as_deref(), Some("Closed"));
+    }
+}
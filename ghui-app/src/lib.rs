@@ -3,26 +3,35 @@ use dirs::home_dir;
 use github_graphql::{
     client::{
         graphql::{
-            TotalCountInconsistency, custom_fields_query::get_fields, get_all_items,
-            get_items::get_items, get_resource_id,
+            TotalCountInconsistency,
+            custom_fields_query::get_fields,
+            get_all_items,
+            get_item_text::{WorkItemText, get_work_item_text},
+            get_items::get_items,
+            get_resource_id, get_viewer_info,
         },
         transport::GhCliClient,
     },
     data::{
-        Change, ChangeData, Changes, DelayLoad, FieldOptionId, Fields, ProjectItemId,
-        SanitizeConflict, SaveMode, UndoHistory, UpdateType, WorkItem, WorkItemId, WorkItems,
+        Change, ChangeDiagnostic, Changes, ConflictChoice, DelayLoad, FieldOptionId, Fields,
+        IntegrityFix, IntegrityIssue, IssueTypeRegistry, ProjectItemId, SanitizeConfig,
+        SanitizeConflict, SanitizeProblem, SanitizeRuleInfo, SanitizeRules, SaveHistory, SaveMode,
+        SaveRecord, SaveReport, UndoHistory, UpdateType, WorkItem, WorkItemId, WorkItems,
+        resolve_conflicts,
     },
-    pivot::PivotConfig,
+    pivot::{CrossTabConfig, CrossTabValue, PivotConfig},
+    query::{Query, QueryContext, parse_query},
 };
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs,
-    io::{BufReader, BufWriter, Read, Write},
+    io::BufReader,
     ops::Deref,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 use tokio::{
     sync::Mutex,
@@ -30,12 +39,22 @@ use tokio::{
 };
 use ts_rs::TS;
 
+pub mod appdata;
+pub mod cache;
+pub mod deep_link;
+pub mod journal;
 pub mod logger;
 pub mod telemetry;
 pub mod updater;
+pub mod views;
 
 mod nodes;
+mod search;
+use appdata::{CacheFormat, get_appdata_path};
+use cache::WorkItemCache;
 use nodes::*;
+pub use nodes::{CrossTab, CrossTabHeader};
+pub use search::SearchResult;
 
 /// The result of resolving a GitHub URL to a work item identifier.
 ///
@@ -83,6 +102,13 @@ pub struct Filters {
     /// files (which predate this field) deserializable.
     #[serde(default)]
     hide_closed: bool,
+
+    /// Filter expression that items must match, e.g. `epic:X AND (NOT
+    /// status:Done OR assignee:@me)`.  Applied in addition to the exclusion
+    /// lists above.  See `github_graphql::query::Query` for the grammar.
+    #[serde(default)]
+    #[ts(type = "string | null")]
+    query: Option<Query>,
 }
 
 impl Filters {
@@ -127,9 +153,17 @@ impl Filters {
         }
     }
 
+    /// Returns true when `work_item` matches the filter query, or there is no
+    /// query.
+    fn matches_query(&self, work_item: &WorkItem, context: &QueryContext) -> bool {
+        self.query
+            .as_ref()
+            .is_none_or(|query| query.matches(work_item, context))
+    }
+
     /// Returns the total number of individual filter values that are active
-    /// across all fields. `hide_closed` counts as one active filter when
-    /// enabled.
+    /// across all fields. `hide_closed` and the query each count as one
+    /// active filter when set.
     pub fn active_filter_count(&self) -> usize {
         self.status.len()
             + self.blocked.len()
@@ -141,6 +175,7 @@ impl Filters {
             + self.priority.len()
             + self.assignee.len()
             + usize::from(self.hide_closed)
+            + usize::from(self.query.is_some())
     }
 }
 
@@ -171,10 +206,36 @@ pub struct Data {
     can_undo: bool,
     can_redo: bool,
 
-    /// Epic conflicts found during the last sanitize run.  Each entry
-    /// represents an item whose existing Epic was not overwritten; the user
-    /// can review these and selectively stage the override.
-    epic_conflicts: Vec<SanitizeConflict>,
+    /// Conflicts found during the last sanitize run.  Each entry represents
+    /// a value that was already set and so was not overwritten; the user can
+    /// review these and choose which proposed values to stage.
+    sanitize_conflicts: Vec<SanitizeConflict>,
+
+    /// Problems found during the last sanitize run that no change can fix.
+    sanitize_problems: Vec<SanitizeProblem>,
+
+    /// Problems with staged changes that would prevent them from saving.
+    /// Changes listed here are skipped by save.
+    change_diagnostics: Vec<ChangeDiagnostic>,
+
+    /// Per-change outcomes of the most recent save.
+    last_save_report: Option<SaveReport>,
+
+    /// Changes committed by each save in this session, oldest first, along
+    /// with what they overwrote.
+    save_history: Vec<SaveRecord>,
+}
+
+/// The parts of `Data` that change when some work items are refetched.
+#[derive(Debug, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DataPatch {
+    /// The refetched items, which replace the ones with the same id.
+    work_items: Vec<WorkItem>,
+    /// Applied, in order, to `Data::nodes`.
+    node_edits: Vec<NodeEdit>,
+    change_diagnostics: Vec<ChangeDiagnostic>,
 }
 
 #[derive(Default, Serialize, TS, Debug, Clone, PartialEq)]
@@ -206,6 +267,7 @@ pub enum DataUpdate {
     },
     WorkItem(Box<WorkItem>),
     Data(Box<Data>),
+    Patch(Box<DataPatch>),
     Log(LogEntry),
     /// GitHub reported a different `totalCount` on a later page of a paginated
     /// query than on the first page, so the fetched items may be inconsistent.
@@ -255,8 +317,42 @@ pub struct AppState {
     changes: Changes,
     undo_history: UndoHistory,
     preview_changes: bool,
-    /// Epic conflicts from the most recent sanitize run.
-    epic_conflicts: Vec<SanitizeConflict>,
+    /// Conflicts from the most recent sanitize run.
+    sanitize_conflicts: Vec<SanitizeConflict>,
+    /// Problems from the most recent sanitize run.
+    sanitize_problems: Vec<SanitizeProblem>,
+    /// Issue types per repository, loaded on demand.
+    issue_types: IssueTypeRegistry,
+    /// Outcome of the most recent save, if any.
+    last_save_report: Option<SaveReport>,
+    /// Values overwritten by each save, so that saves can be reverted.
+    save_history: SaveHistory,
+    /// The signed in user's login, fetched when a filter query uses `@me`.
+    viewer_login: Option<String>,
+    /// Named views the user has saved.
+    views: views::ViewRegistry,
+    /// Work item that the most recently opened deep link asked to focus.
+    focus_item: Option<WorkItemId>,
+    /// The nodes last sent to the frontend, kept so that `refresh_nodes` can
+    /// send just what changed.  `None` when they were built with previewed
+    /// changes applied.
+    nodes: Option<Vec<Node>>,
+    /// Built by the first search after the work items are loaded, and then
+    /// kept up to date as items are refetched.
+    search_index: Option<search::SearchIndex>,
+    /// Progress of fetching the bodies and comments that search looks in.
+    text_load: TextLoad,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum TextLoad {
+    /// Not started yet, or finished.
+    #[default]
+    Idle,
+    Loading,
+    /// The last fetch failed.  It isn't tried again until the work items are
+    /// reloaded, so that every search doesn't wait on the same error.
+    Failed,
 }
 
 impl Default for AppState {
@@ -275,14 +371,25 @@ impl AppState {
             }
         };
 
-        let filters = view_config
-            .as_ref()
-            .map(|cache| cache.filters.clone())
-            .unwrap_or_default();
-        let pivot_config = view_config
-            .as_ref()
-            .map(|cache| cache.pivot_config.clone())
-            .unwrap_or_default();
+        let views = views::load(&views::get_views_file_path()).unwrap_or_else(|error| {
+            warn!("failed to load saved views: {error}");
+            views::ViewRegistry::default()
+        });
+
+        // A default view takes precedence over whatever was last used.
+        let (filters, pivot_config) = match views.default_view() {
+            Some(view) => (view.filters.clone(), view.pivot_config.clone()),
+            None => (
+                view_config
+                    .as_ref()
+                    .map(|cache| cache.filters.clone())
+                    .unwrap_or_default(),
+                view_config
+                    .as_ref()
+                    .map(|cache| cache.pivot_config.clone())
+                    .unwrap_or_default(),
+            ),
+        };
 
         Self {
             watcher: Arc::new(Box::new(|_| {
@@ -295,7 +402,23 @@ impl AppState {
             changes: Changes::default(),
             undo_history: UndoHistory::default(),
             preview_changes: true,
-            epic_conflicts: Vec::new(),
+            sanitize_conflicts: Vec::new(),
+            sanitize_problems: Vec::new(),
+            issue_types: load_issue_types_from_appdata().unwrap_or_else(|error| {
+                debug!("failed to load issue types cache during initialization: {error}");
+                IssueTypeRegistry::default()
+            }),
+            last_save_report: None,
+            save_history: load_save_history().unwrap_or_else(|error| {
+                warn!("failed to load save history: {error}");
+                SaveHistory::default()
+            }),
+            viewer_login: None,
+            views,
+            focus_item: None,
+            nodes: None,
+            search_index: None,
+            text_load: TextLoad::default(),
         }
     }
 
@@ -312,12 +435,23 @@ impl AppState {
     pub async fn refresh(&mut self, force_refresh: bool) -> Result<()> {
         let fields = self.refresh_fields(force_refresh).await?;
         let mut work_items = self.refresh_work_items(force_refresh).await?;
+        let change_diagnostics = self
+            .changes
+            .validate_with_load(
+                &GhCliClient::default(),
+                &fields,
+                &work_items,
+                &mut self.issue_types,
+            )
+            .await;
         let original_work_items = if self.preview_changes {
             self.apply_changes(&mut work_items)
         } else {
             HashMap::default()
         };
 
+        self.refresh_viewer_login().await;
+
         let nodes = RecipeNodeBuilder::new(
             &fields,
             &work_items,
@@ -325,7 +459,9 @@ impl AppState {
             &original_work_items,
             &self.pivot_config,
         )
+        .with_viewer(self.viewer_login.as_deref())
         .build();
+        self.nodes = original_work_items.is_empty().then(|| nodes.clone());
 
         (self.watcher)(DataUpdate::Data(Box::new(Data {
             nodes,
@@ -337,11 +473,67 @@ impl AppState {
             changes: self.changes.clone(),
             can_undo: self.undo_history.can_undo(),
             can_redo: self.undo_history.can_redo(),
-            epic_conflicts: self.epic_conflicts.clone(),
+            sanitize_conflicts: self.sanitize_conflicts.clone(),
+            sanitize_problems: self.sanitize_problems.clone(),
+            change_diagnostics,
+            last_save_report: self.last_save_report.clone(),
+            save_history: self.save_history.records().to_vec(),
         })));
         Ok(())
     }
 
+    /// Sends `updated`, which have just been refetched, to the frontend along
+    /// with the nodes that changed.  Only the parts of the tree that involve
+    /// `affected` are rebuilt.  Previewed changes can alter any item, so when
+    /// they are applied this falls back to `refresh`.
+    async fn refresh_nodes(
+        &mut self,
+        updated: Vec<WorkItem>,
+        affected: &HashSet<WorkItemId>,
+    ) -> Result<()> {
+        let previewing = self.preview_changes && !self.changes.is_empty();
+        let (Some(fields), Some(work_items), Some(previous), false) =
+            (&self.fields, &self.work_items, &self.nodes, previewing)
+        else {
+            return self.refresh(false).await;
+        };
+
+        let original_work_items = HashMap::default();
+        let nodes = RecipeNodeBuilder::new(
+            fields,
+            work_items,
+            &self.filters,
+            &original_work_items,
+            &self.pivot_config,
+        )
+        .with_viewer(self.viewer_login.as_deref())
+        .rebuild(previous, affected);
+
+        let node_edits = diff_nodes(previous, &nodes);
+        let change_diagnostics = self
+            .changes
+            .validate_with_load(
+                &GhCliClient::default(),
+                fields,
+                work_items,
+                &mut self.issue_types,
+            )
+            .await;
+        debug!(
+            "refresh_nodes: {} edit(s) for {} affected item(s)",
+            node_edits.len(),
+            affected.len()
+        );
+
+        (self.watcher)(DataUpdate::Patch(Box::new(DataPatch {
+            work_items: updated,
+            node_edits,
+            change_diagnostics,
+        })));
+        self.nodes = Some(nodes);
+        Ok(())
+    }
+
     pub async fn force_refresh(&mut self) -> Result<RefreshSummary> {
         let previous_work_items = self.work_items.as_ref().map(|work_items| {
             HashMap::from_iter(
@@ -351,6 +543,7 @@ impl AppState {
                     .map(|(id, item)| (id.clone(), item.updated_at.clone())),
             )
         });
+        self.issue_types = IssueTypeRegistry::default();
         self.refresh(true).await?;
 
         if let Some(work_items) = &self.work_items {
@@ -363,6 +556,25 @@ impl AppState {
         }
     }
 
+    /// Fetches the signed in user's login the first time the filter query
+    /// refers to `@me`.  On failure `@me` matches nothing.
+    async fn refresh_viewer_login(&mut self) {
+        if self.filters.query.as_ref().is_some_and(Query::uses_viewer) {
+            self.get_viewer_login().await;
+        }
+    }
+
+    /// The signed in user's login, fetched the first time it's needed.
+    async fn get_viewer_login(&mut self) -> Option<&str> {
+        if self.viewer_login.is_none() {
+            match get_viewer_info(&GhCliClient::default()).await {
+                Ok(viewer) => self.viewer_login = Some(viewer.login),
+                Err(error) => warn!("failed to get login: {error}"),
+            }
+        }
+        self.viewer_login.as_deref()
+    }
+
     pub async fn refresh_fields(&mut self, force: bool) -> Result<Fields> {
         if !force {
             if let Some(fields) = &self.fields {
@@ -403,6 +615,8 @@ impl AppState {
 
             if let Ok(work_items) = load_result {
                 self.work_items = Some(work_items.clone());
+                self.search_index = None;
+                self.text_load = TextLoad::Idle;
                 return Ok(work_items);
             } else {
                 warn!(
@@ -441,6 +655,8 @@ impl AppState {
         report_progress(0, 0);
 
         self.work_items = Some(work_items.clone());
+        self.search_index = None;
+        self.text_load = TextLoad::Idle;
         Ok(work_items)
     }
 
@@ -472,6 +688,26 @@ impl AppState {
         self.refresh(false).await
     }
 
+    /// Parses `text` and sets it as the filter query.  Empty text clears the
+    /// query.
+    pub async fn set_filter_query(&mut self, text: &str) -> Result<()> {
+        let query = if text.trim().is_empty() {
+            None
+        } else {
+            Some(parse_query(text)?)
+        };
+
+        self.set_filters(Filters {
+            query,
+            ..self.filters.clone()
+        })
+        .await
+    }
+
+    pub fn active_filter_count(&self) -> usize {
+        self.filters.active_filter_count()
+    }
+
     pub fn get_pivot_config(&self) -> PivotConfig {
         self.pivot_config.clone()
     }
@@ -485,6 +721,108 @@ impl AppState {
         self.refresh(false).await
     }
 
+    pub fn get_views(&self) -> views::ViewRegistry {
+        self.views.clone()
+    }
+
+    /// Saves the current filters and pivot config as the view `name`,
+    /// replacing any existing view with that name.
+    pub fn save_view(&mut self, name: &str) -> Result<views::ViewRegistry> {
+        self.views.save(views::SavedView {
+            name: name.trim().to_owned(),
+            filters: self.filters.clone(),
+            pivot_config: self.pivot_config.clone(),
+        })?;
+        self.save_views()
+    }
+
+    pub async fn apply_view(&mut self, name: &str) -> Result<()> {
+        let Some(view) = self.views.get(name).cloned() else {
+            anyhow::bail!("No view named {name:?}");
+        };
+
+        self.filters = view.filters;
+        self.pivot_config = view.pivot_config;
+        let save_result = save_view_config_to_appdata(&self.view_config_cache());
+        if let Err(error) = save_result {
+            warn!("failed to save cached view config: {error}");
+        }
+        self.refresh(false).await
+    }
+
+    pub fn rename_view(&mut self, name: &str, new_name: &str) -> Result<views::ViewRegistry> {
+        self.views.rename(name, new_name.trim())?;
+        self.save_views()
+    }
+
+    pub fn delete_view(&mut self, name: &str) -> Result<views::ViewRegistry> {
+        self.views.delete(name)?;
+        self.save_views()
+    }
+
+    pub fn set_default_view(&mut self, name: Option<&str>) -> Result<views::ViewRegistry> {
+        self.views.set_default(name)?;
+        self.save_views()
+    }
+
+    /// Writes the view `name` to the home directory so it can be shared, and
+    /// returns the file's path.
+    pub fn export_view(&self, name: &str) -> Result<PathBuf> {
+        let Some(view) = self.views.get(name) else {
+            anyhow::bail!("No view named {name:?}");
+        };
+        let dir =
+            home_dir().ok_or_else(|| anyhow::anyhow!("could not determine home directory"))?;
+        let path = views::export(&dir, view)?;
+        info!("View {name:?} exported to {path:?}");
+        Ok(path)
+    }
+
+    /// Adds a view from the contents of a file written by
+    /// [`Self::export_view`].
+    pub fn import_view(&mut self, json: &str) -> Result<views::ViewRegistry> {
+        let name = self.views.import(views::parse(json)?)?;
+        info!("Imported view {name:?}");
+        self.save_views()
+    }
+
+    fn save_views(&self) -> Result<views::ViewRegistry> {
+        views::save(&views::get_views_file_path(), &self.views)?;
+        Ok(self.views.clone())
+    }
+
+    /// Applies the view described by a `ghui://` link.  The data is only
+    /// refreshed if it has been loaded already; when ghui is launched from a
+    /// link the first refresh happens once the frontend is watching.
+    pub async fn open_deep_link(&mut self, link: deep_link::DeepLink) -> Result<()> {
+        self.filters = link.apply_filters(&self.filters);
+        if let Some(recipe) = link.recipe {
+            self.pivot_config.recipe = recipe;
+        }
+        self.focus_item = link.item;
+
+        let save_result = save_view_config_to_appdata(&self.view_config_cache());
+        if let Err(error) = save_result {
+            warn!("failed to save cached view config: {error}");
+        }
+
+        if self.fields.is_some() {
+            self.refresh(false).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the work item a deep link asked to focus, if the frontend
+    /// hasn't taken it already.
+    pub fn take_focus_item(&mut self) -> Option<WorkItemId> {
+        self.focus_item.take()
+    }
+
+    /// Builds a `ghui://` link to the current view, focusing `item` if given.
+    pub fn deep_link(&self, item: Option<&WorkItemId>) -> String {
+        deep_link::to_url(&self.pivot_config.recipe, &self.filters, item)
+    }
+
     fn view_config_cache(&self) -> ViewConfigCache {
         ViewConfigCache {
             filters: self.filters.clone(),
@@ -537,28 +875,112 @@ impl AppState {
     async fn save_changes(
         &mut self,
         report_progress: &impl Fn(usize, usize),
-    ) -> Result<(Vec<ProjectItemId>, usize)> {
+    ) -> Result<SaveReport> {
         let client = GhCliClient::default();
 
         let fields = self.refresh_fields(false).await?;
 
         let pre_save = self.changes.clone();
-        let changes_count = pre_save.len();
 
-        let result = self
+        let report = self
             .changes
             .save(
                 &client,
                 &fields,
                 self.work_items.as_ref().unwrap(),
+                &mut self.issue_types,
                 SaveMode::Commit,
                 &|_, a, b| report_progress(a, b),
             )
             .await?;
 
         self.undo_history.track_save(&self.changes, pre_save);
+        self.save_issue_types();
+
+        let login = if report.succeeded_count() > 0 {
+            self.get_viewer_login()
+                .await
+                .unwrap_or("unknown")
+                .to_owned()
+        } else {
+            String::new()
+        };
+        if let Some(work_items) = &self.work_items {
+            journal::record_save(&report, &login, &fields, work_items);
+            match record_save_history(&report, work_items) {
+                Ok((history, _)) => self.save_history = history,
+                Err(error) => {
+                    warn!("failed to record save history: {error}");
+                    self.save_history.record(&report, work_items);
+                }
+            }
+        }
+
+        if let Some(work_items) = &mut self.work_items {
+            for id in &report.removed_work_item_ids {
+                work_items.remove(id);
+                if let Some(search_index) = &mut self.search_index {
+                    search_index.remove(id);
+                }
+            }
+            if let Err(error) = remove_workitems_from_appdata(&report.removed_work_item_ids) {
+                warn!("failed to remove work items from the cache: {error}");
+            }
+        }
+
+        self.last_save_report = Some(report.clone());
+
+        Ok(report)
+    }
+
+    /// Stages the changes that undo save `number` on GitHub, as a single
+    /// undoable batch.  Returns the number of changes staged.
+    pub async fn revert_save(&mut self, number: usize) -> Result<usize> {
+        // Pick up saves made by ghui-util since the history was loaded.
+        match load_save_history() {
+            Ok(history) => self.save_history = history,
+            Err(error) => warn!("failed to reload save history: {error}"),
+        }
+
+        let changes = self
+            .save_history
+            .get(number)
+            .ok_or_else(|| anyhow::anyhow!("Save #{number} not found"))?
+            .revert_changes();
+        let count = changes.len();
+        self.add_changes(changes).await?;
+        Ok(count)
+    }
+
+    /// Returns the names of the issue types valid for the repository that the
+    /// given work item belongs to, so the issue type picker only offers types
+    /// that can actually be saved.  Returns an empty list for items without a
+    /// repository (e.g. drafts).
+    pub async fn get_issue_types(&mut self, work_item_id: &WorkItemId) -> Result<Vec<String>> {
+        let Some((owner, name)) = self
+            .work_items
+            .as_ref()
+            .and_then(|work_items| work_items.get(work_item_id))
+            .and_then(|work_item| work_item.get_repository_info())
+        else {
+            return Ok(Vec::new());
+        };
+
+        let was_cached = self.issue_types.get(&owner, &name).is_some();
+
+        let client = GhCliClient::default();
+        let names = self.issue_types.names(&client, &owner, &name).await?;
+
+        if !was_cached {
+            self.save_issue_types();
+        }
+        Ok(names)
+    }
 
-        Ok((result, changes_count))
+    fn save_issue_types(&self) {
+        if let Err(error) = save_issue_types_to_appdata(&self.issue_types) {
+            warn!("failed to save cached issue types: {error}");
+        }
     }
 
     pub async fn convert_tracked_to_sub_issues(&mut self, id: WorkItemId) -> Result<()> {
@@ -595,40 +1017,11 @@ impl AppState {
     /// to the snapshot so captured nodes match the current UI view. Returns the
     /// path of the saved file so the caller can reveal it in the file system.
     pub fn capture_view(&self) -> Result<PathBuf> {
-        use time::OffsetDateTime;
-
-        let nodes = if let (Some(fields), Some(work_items)) = (&self.fields, &self.work_items) {
-            let mut work_items = work_items.clone();
-            let original_work_items = if self.preview_changes {
-                self.apply_changes(&mut work_items)
-            } else {
-                HashMap::default()
-            };
-
-            RecipeNodeBuilder::new(
-                fields,
-                &work_items,
-                &self.filters,
-                &original_work_items,
-                &self.pivot_config,
-            )
-            .build()
-        } else {
-            Vec::new()
-        };
-
-        let now = OffsetDateTime::now_utc();
-        let timestamp = format!(
-            "{:04}-{:02}-{:02}T{:02}-{:02}-{:02}.{:03}",
-            now.year(),
-            now.month() as u8,
-            now.day(),
-            now.hour(),
-            now.minute(),
-            now.second(),
-            now.millisecond(),
-        );
+        let nodes = self
+            .with_view_builder(|mut builder| builder.build())
+            .unwrap_or_default();
 
+        let timestamp = file_timestamp();
         let capture = ViewCapture {
             captured_at: timestamp.clone(),
             filters: self.filters.clone(),
@@ -647,9 +1040,211 @@ impl AppState {
         info!("View captured to {path:?}");
         Ok(path)
     }
+
+    /// Searches the titles, bodies and comments of all the work items, best
+    /// matches first, and finds where the current view shows them.  Bodies
+    /// and comments that haven't been fetched yet aren't searched.
+    pub fn search(&mut self, text: &str, limit: usize) -> Vec<SearchResult> {
+        let Some(work_items) = &self.work_items else {
+            return Vec::new();
+        };
+        let hits = self
+            .search_index
+            .get_or_insert_with(|| search::SearchIndex::new(work_items))
+            .search(text, limit);
+
+        let built;
+        let nodes = match &self.nodes {
+            Some(nodes) => nodes,
+            None => {
+                built = self
+                    .with_view_builder(|mut builder| builder.build())
+                    .unwrap_or_default();
+                &built
+            }
+        };
+
+        let ids: HashSet<&WorkItemId> = hits.iter().map(|(id, _)| id).collect();
+        let mut paths = search::node_paths(nodes, &ids);
+        hits.iter()
+            .map(|(id, score)| SearchResult {
+                work_item_id: id.clone(),
+                score: *score,
+                paths: paths.remove(id).unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Returns the work items whose bodies and comments still need fetching,
+    /// and marks the fetch as started.  Returns `None` when a fetch is already
+    /// running, the last one failed, or there's nothing to fetch.
+    fn start_text_load(&mut self) -> Option<Vec<WorkItemId>> {
+        if self.text_load != TextLoad::Idle {
+            return None;
+        }
+        let work_items = self.work_items.as_ref()?;
+        let ids: Vec<WorkItemId> = work_items
+            .iter()
+            .filter(|id| {
+                work_items
+                    .get(id)
+                    .is_some_and(|item| !item.body.is_loaded())
+            })
+            .cloned()
+            .collect();
+        if ids.is_empty() {
+            return None;
+        }
+
+        self.text_load = TextLoad::Loading;
+        Some(ids)
+    }
+
+    /// Stores the bodies and comments fetched for `ids` and adds them to the
+    /// search index.
+    fn finish_text_load(
+        &mut self,
+        ids: &[WorkItemId],
+        texts: github_graphql::Result<HashMap<WorkItemId, WorkItemText>>,
+    ) {
+        let mut texts = match texts {
+            Ok(texts) => texts,
+            Err(e) => {
+                warn!("Failed to fetch work item bodies and comments: {e}");
+                self.text_load = TextLoad::Failed;
+                return;
+            }
+        };
+        self.text_load = TextLoad::Idle;
+
+        let Some(work_items) = &mut self.work_items else {
+            return;
+        };
+        let mut loaded = Vec::new();
+        for id in ids {
+            let Some(item) = work_items.get_mut(id) else {
+                continue;
+            };
+            // Items GitHub no longer has get no text, rather than being
+            // asked for again by every search.
+            let text = texts.remove(id).unwrap_or_default();
+            item.body = text.body.into();
+            item.comments = text.comments.into();
+            if let Some(search_index) = &mut self.search_index {
+                search_index.update(item);
+            }
+            loaded.push(item.clone());
+        }
+
+        if let Err(e) = upsert_workitems_to_appdata(&loaded) {
+            warn!("failed to save cached work items: {e}");
+        }
+    }
+
+    /// Pivots the items in the current view (filters applied, and pending
+    /// edits too when previewing changes) into a cross-tab.
+    pub fn cross_tab(&self, config: &CrossTabConfig) -> CrossTab {
+        self.with_view_builder(|builder| builder.build_cross_tab(&config.rows, &config.columns))
+            .unwrap_or_default()
+    }
+
+    /// Saves the cross-tab as CSV to a timestamped file in the home
+    /// directory and returns its path.
+    pub fn export_cross_tab(
+        &self,
+        config: &CrossTabConfig,
+        value: CrossTabValue,
+    ) -> Result<PathBuf> {
+        let csv = self
+            .with_view_builder(|builder| {
+                builder
+                    .build_cross_tab(&config.rows, &config.columns)
+                    .to_csv(value, builder.work_items())
+            })
+            .unwrap_or_default();
+
+        let filename = format!("cross_tab_{}.csv", file_timestamp());
+        let path = home_dir()
+            .ok_or_else(|| anyhow::anyhow!("could not determine home directory"))?
+            .join(filename);
+        fs::write(&path, csv)?;
+
+        info!("Cross-tab exported to {path:?}");
+        Ok(path)
+    }
+
+    /// Runs `f` with a builder over the current view's work items, or returns
+    /// `None` if the data hasn't been loaded yet.
+    fn with_view_builder<R>(&self, f: impl FnOnce(RecipeNodeBuilder<'_>) -> R) -> Option<R> {
+        let (Some(fields), Some(work_items)) = (&self.fields, &self.work_items) else {
+            return None;
+        };
+
+        let mut work_items = work_items.clone();
+        let original_work_items = if self.preview_changes {
+            self.apply_changes(&mut work_items)
+        } else {
+            HashMap::default()
+        };
+
+        let builder = RecipeNodeBuilder::new(
+            fields,
+            &work_items,
+            &self.filters,
+            &original_work_items,
+            &self.pivot_config,
+        )
+        .with_viewer(self.viewer_login.as_deref());
+        Some(f(builder))
+    }
+}
+
+/// The current UTC time, formatted for use in a file name.
+fn file_timestamp() -> String {
+    use time::OffsetDateTime;
+
+    let now = OffsetDateTime::now_utc();
+    format!(
+        "{:04}-{:02}-{:02}T{:02}-{:02}-{:02}.{:03}",
+        now.year(),
+        now.month() as u8,
+        now.day(),
+        now.hour(),
+        now.minute(),
+        now.second(),
+        now.millisecond(),
+    )
+}
+
+/// The items whose place in the tree can change when `item`'s does: the item
+/// itself, its parent and its sub-issues.
+fn related_items(item: &WorkItem) -> impl Iterator<Item = WorkItemId> + '_ {
+    std::iter::once(&item.id)
+        .chain(item.get_parent())
+        .chain(item.get_sub_issues().into_iter().flatten())
+        .cloned()
 }
 
 impl DataState {
+    /// Searches the work items, starting a background fetch of their bodies
+    /// and comments the first time.  Until that finishes, searches only see
+    /// titles.
+    pub async fn search(&self, text: &str, limit: usize) -> Vec<SearchResult> {
+        let mut state = self.lock().await;
+        if let Some(ids) = state.start_text_load() {
+            self.request_work_item_text(ids);
+        }
+        state.search(text, limit)
+    }
+
+    fn request_work_item_text(&self, ids: Vec<WorkItemId>) -> JoinHandle<()> {
+        let app_state = Arc::clone(&self.0);
+        tokio::spawn(async move {
+            let texts = get_work_item_text(&GhCliClient::default(), ids.clone()).await;
+            app_state.lock().await.finish_text_load(&ids, texts);
+        })
+    }
+
     pub fn request_update_items(&self, project_item_ids: Vec<ProjectItemId>) -> JoinHandle<()> {
         if project_item_ids.is_empty() {
             return tokio::spawn(async {});
@@ -663,7 +1258,7 @@ impl DataState {
 
             let client = GhCliClient::default();
 
-            let updated_work_items = match get_items(&client, project_item_ids).await {
+            let mut updated_work_items = match get_items(&client, project_item_ids).await {
                 Ok(items) => items,
                 Err(e) => {
                     error!("Failed to get items (batch of {batch_size}): {e}");
@@ -673,15 +1268,45 @@ impl DataState {
 
             let mut state = app_state.lock().await;
             let watcher = state.watcher.clone();
+            let reads_dates = state.pivot_config.reads_dates();
             if let Some(work_items) = &mut state.work_items {
                 let mut update_type = UpdateType::NoUpdate;
-
-                for item in &updated_work_items {
+                let mut affected = HashSet::new();
+
+                for item in &mut updated_work_items {
+                    if let Some(old_item) = work_items.get(&item.id) {
+                        affected.extend(related_items(old_item));
+                        // The items' dates only place them in the tree when
+                        // the recipe pivots or sorts by them.
+                        if reads_dates
+                            && (old_item.updated_at != item.updated_at
+                                || old_item.created_at != item.created_at)
+                        {
+                            update_type = UpdateType::ChangesHierarchy;
+                        }
+                        // get_items doesn't fetch bodies and comments, so
+                        // keep the ones search already has.
+                        if !item.body.is_loaded() {
+                            item.body = old_item.body.clone();
+                            item.comments = old_item.comments.clone();
+                        }
+                    }
+                    affected.extend(related_items(item));
                     update_type = std::cmp::max(update_type, work_items.update(item.clone()));
                 }
 
+                if let Err(e) = upsert_workitems_to_appdata(&updated_work_items) {
+                    warn!("failed to save cached work items: {e}");
+                }
+
+                if let Some(search_index) = &mut state.search_index {
+                    for item in &updated_work_items {
+                        search_index.update(item);
+                    }
+                }
+
                 if update_type == UpdateType::ChangesHierarchy {
-                    let r = state.refresh(false).await;
+                    let r = state.refresh_nodes(updated_work_items, &affected).await;
                     if let Err(r) = r {
                         error!("Refresh failed: {r:?}");
                     }
@@ -692,13 +1317,6 @@ impl DataState {
                 }
             }
 
-            // Persist updated work items to disk cache
-            if let Some(work_items) = &state.work_items
-                && let Err(e) = save_workitems_to_appdata(work_items)
-            {
-                warn!("failed to save cached work items: {e}");
-            }
-
             debug!(
                 "request_update_items: completed batch of {batch_size} item(s) in {}ms",
                 started.elapsed().as_millis()
@@ -706,58 +1324,123 @@ impl DataState {
         })
     }
 
-    pub async fn save_changes(&self, report_progress: &impl Fn(usize, usize)) -> Result<usize> {
-        let (project_item_ids, changes_count) =
-            self.lock().await.save_changes(report_progress).await?;
+    pub async fn save_changes(
+        &self,
+        report_progress: &impl Fn(usize, usize),
+    ) -> Result<SaveReport> {
+        let report = self.lock().await.save_changes(report_progress).await?;
 
-        if !project_item_ids.is_empty() {
-            self.request_update_items(project_item_ids).await?;
+        if !report.project_item_ids.is_empty() {
+            self.request_update_items(report.project_item_ids.clone())
+                .await?;
         }
 
         self.lock().await.refresh(false).await?;
-        Ok(changes_count)
+        Ok(report)
     }
 
-    pub async fn sanitize(&self) -> Result<(usize, usize)> {
+    /// Runs the sanitize rules enabled in the sanitize config file and stages
+    /// the resulting changes.  If `rules` is given, only those rules are run.
+    pub async fn sanitize(&self, rules: Option<Vec<String>>) -> Result<(usize, usize)> {
+        let config = load_sanitize_config(&get_sanitize_config_path())?;
+
         self.load_all_work_items(false).await?;
 
         let mut app_state = self.lock().await;
         if let Some(work_items) = app_state.work_items.as_ref()
             && let Some(fields) = app_state.fields.as_ref()
         {
-            let report = work_items.sanitize(fields);
+            let mut report = work_items.sanitize_with(fields, &config);
+            if let Some(rules) = rules {
+                report.retain_rules(|rule| rules.iter().any(|r| r == rule));
+            }
             let num_changes = report.changes.len();
-            let num_conflicts = report.epic_conflicts.len();
-            app_state.epic_conflicts = report.epic_conflicts;
+            let num_conflicts = report.conflicts.len();
+            app_state.sanitize_conflicts = report.conflicts;
+            app_state.sanitize_problems = report.problems;
             app_state.add_changes(report.changes).await?;
             return Ok((num_changes, num_conflicts));
         }
         Ok((0, 0))
     }
 
-    /// Stages Epic override changes for the given item IDs, pulling the
-    /// proposed Epic from the stored conflict list.  Removes the staged items
-    /// from the conflict list and triggers a UI refresh.
-    pub async fn stage_epic_overrides(&self, ids: Vec<WorkItemId>) -> Result<()> {
-        let mut app_state = self.lock().await;
+    /// Stages converting every tracked issue in the project to a sub-issue of
+    /// its tracker, as a single undoable batch.  Tracked issues that already
+    /// have a parent are added to the sanitize conflicts so they can be
+    /// reviewed.  Returns the number of changes and conflicts.
+    pub async fn convert_all_tracked_to_sub_issues(&self) -> Result<(usize, usize)> {
+        self.load_all_work_items(false).await?;
 
-        let id_set: std::collections::HashSet<&WorkItemId> = ids.iter().collect();
-        let mut changes = Changes::default();
+        let mut app_state = self.lock().await;
+        let Some(work_items) = app_state.work_items.as_ref() else {
+            return Ok((0, 0));
+        };
 
-        for conflict in &app_state.epic_conflicts {
-            if id_set.contains(&conflict.work_item_id) {
-                changes.add(Change {
-                    work_item_id: conflict.work_item_id.clone(),
-                    data: ChangeData::Epic(Some(conflict.proposed_epic.clone())),
-                });
-            }
-        }
+        let conversion = work_items.convert_all_tracked_to_sub_issues();
+        let num_changes = conversion.changes.len();
+        let num_conflicts = conversion.conflicts.len();
 
         app_state
-            .epic_conflicts
-            .retain(|c| !id_set.contains(&c.work_item_id));
+            .sanitize_conflicts
+            .retain(|conflict| conflict.rule != "convert-tracked");
+        app_state
+            .sanitize_conflicts
+            .extend(conversion.conflicts.into_iter().map(SanitizeConflict::from));
+        app_state.add_changes(conversion.changes).await?;
+        Ok((num_changes, num_conflicts))
+    }
+
+    /// Applies the user's choices to the stored sanitize conflicts.  Resolved
+    /// conflicts are removed from the list, and those resolved with the
+    /// proposed value have it staged as a normal pending change.  Returns the
+    /// number of changes staged.
+    pub async fn stage_conflict_resolutions(&self, choices: Vec<ConflictChoice>) -> Result<usize> {
+        let mut app_state = self.lock().await;
+
+        let changes = resolve_conflicts(&mut app_state.sanitize_conflicts, &choices);
+        let num_changes = changes.len();
 
         app_state.add_changes(changes).await?;
+        Ok(num_changes)
+    }
+
+    /// Loads every work item and reports any inconsistencies in the
+    /// hierarchy.
+    pub async fn check_integrity(&self) -> Result<Vec<IntegrityIssue>> {
+        self.load_all_work_items(false).await?;
+
+        let app_state = self.lock().await;
+        Ok(app_state
+            .work_items
+            .as_ref()
+            .map(|work_items| work_items.check_integrity())
+            .unwrap_or_default())
+    }
+
+    /// Applies a fix suggested by `check_integrity`.  Reloads are started in
+    /// the background; other fixes are staged as pending changes.
+    pub async fn apply_integrity_fix(&self, fix: IntegrityFix) -> Result<()> {
+        let mut app_state = self.lock().await;
+
+        if let IntegrityFix::Reload { work_item_ids } = &fix {
+            let items: Vec<_> = work_item_ids
+                .iter()
+                .map(|id| ItemToUpdate {
+                    work_item_id: id.clone(),
+                    force: true,
+                })
+                .collect();
+            let project_item_ids = app_state.get_project_ids_to_update(&items);
+            drop(app_state);
+
+            self.request_update_items(project_item_ids);
+            return Ok(());
+        }
+
+        if let Some(work_items) = app_state.work_items.as_ref() {
+            let changes = fix.changes(work_items);
+            app_state.add_changes(changes).await?;
+        }
         Ok(())
     }
 
@@ -793,50 +1476,97 @@ impl DataState {
 
 const FIELDS_FILENAME: &str = "fields";
 
+const FIELDS_FORMAT: CacheFormat = CacheFormat {
+    name: "fields",
+    migrations: &[appdata::from_unversioned],
+};
+
 fn load_fields_from_appdata() -> anyhow::Result<Fields> {
     let path = get_appdata_path(FIELDS_FILENAME);
     info!("Attempting to load fields cache from {path:?}");
 
-    let reader = fs::File::open(path)?;
-    Ok(serde_json::from_reader(BufReader::new(reader))?)
+    appdata::load(&path, &FIELDS_FORMAT)?.ok_or_else(|| anyhow::anyhow!("no fields cache"))
 }
 
 fn save_fields_to_appdata(fields: &Fields) -> anyhow::Result<()> {
     let path = get_appdata_path(FIELDS_FILENAME);
     info!("Attempting to save fields cache to {path:?}");
 
-    let writer = fs::File::create(path)?;
-    Ok(serde_json::to_writer_pretty(
-        BufWriter::new(writer),
-        fields,
-    )?)
+    appdata::save(&path, &FIELDS_FORMAT, fields)
 }
 
+/// Where work items were cached before the cache moved to a database; see
+/// [`cache::WorkItemCache::open`].
 const WORK_ITEMS_FILENAME: &str = "work_items";
 
-fn load_workitems_from_appdata() -> anyhow::Result<WorkItems> {
-    let path = get_appdata_path(WORK_ITEMS_FILENAME);
-    info!("Attempting to load work item cache from {path:?}");
+fn open_work_item_cache() -> anyhow::Result<WorkItemCache> {
+    let path = cache::get_cache_path();
+    info!("Opening work item cache {path:?}");
+    WorkItemCache::open(&path, &get_appdata_path(WORK_ITEMS_FILENAME))
+}
 
-    let reader = fs::File::open(path)?;
-    Ok(serde_json::from_reader(BufReader::new(reader))?)
+fn load_workitems_from_appdata() -> anyhow::Result<WorkItems> {
+    open_work_item_cache()?
+        .load()?
+        .ok_or_else(|| anyhow::anyhow!("the work item cache is empty"))
 }
 
 fn save_workitems_to_appdata(work_items: &WorkItems) -> anyhow::Result<()> {
-    let path = get_appdata_path(WORK_ITEMS_FILENAME);
-    info!("Attempting to save work item cache to {path:?}");
-
-    let writer = fs::File::create(path)?;
-    Ok(serde_json::to_writer_pretty(
-        BufWriter::new(writer),
-        work_items,
-    )?)
+    open_work_item_cache()?.replace_all(work_items)
+}
+
+fn upsert_workitems_to_appdata(items: &[WorkItem]) -> anyhow::Result<()> {
+    open_work_item_cache()?.upsert(items)
+}
+
+fn remove_workitems_from_appdata(ids: &[WorkItemId]) -> anyhow::Result<()> {
+    open_work_item_cache()?.remove(ids)
+}
+
+const ISSUE_TYPES_FILENAME: &str = "issue_types";
+
+/// Issue types rarely change, but when they do we want to pick them up without
+/// requiring a full refresh.
+const ISSUE_TYPES_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+const ISSUE_TYPES_FORMAT: CacheFormat = CacheFormat {
+    name: "issue types",
+    migrations: &[appdata::from_unversioned],
+};
+
+fn load_issue_types_from_appdata() -> anyhow::Result<IssueTypeRegistry> {
+    let path = get_appdata_path(ISSUE_TYPES_FILENAME);
+    info!("Attempting to load issue types cache from {path:?}");
+
+    let mut issue_types: IssueTypeRegistry = appdata::load(&path, &ISSUE_TYPES_FORMAT)?
+        .ok_or_else(|| anyhow::anyhow!("no issue types cache"))?;
+    issue_types.remove_expired(ISSUE_TYPES_MAX_AGE);
+    Ok(issue_types)
+}
+
+fn save_issue_types_to_appdata(issue_types: &IssueTypeRegistry) -> anyhow::Result<()> {
+    let path = get_appdata_path(ISSUE_TYPES_FILENAME);
+    info!("Attempting to save issue types cache to {path:?}");
+
+    appdata::save(&path, &ISSUE_TYPES_FORMAT, issue_types)
 }
 
 const WORK_ITEMS_EXTRA_DATA: &str = "work_items_extra_data";
 
+/// The extra data is JSON written by the frontend, which the cache stores as
+/// is.
+const WORK_ITEMS_EXTRA_DATA_FORMAT: CacheFormat = CacheFormat {
+    name: "work items extra data",
+    migrations: &[appdata::from_unversioned],
+};
+
 const VIEW_CONFIG_FILENAME: &str = "view_config";
 
+const VIEW_CONFIG_FORMAT: CacheFormat = CacheFormat {
+    name: "view config",
+    migrations: &[appdata::from_unversioned],
+};
+
 /// Snapshot written to disk by [`AppState::capture_view`].  Includes the
 /// active filter settings, the pivot configuration, and the rendered node tree
 /// so that a developer can fully reproduce the view from the file alone.
@@ -859,13 +1589,11 @@ struct ViewConfigCache {
 }
 
 fn load_view_config_from_file(path: &Path) -> anyhow::Result<ViewConfigCache> {
-    let reader = fs::File::open(path)?;
-    Ok(serde_json::from_reader(BufReader::new(reader))?)
+    appdata::load(path, &VIEW_CONFIG_FORMAT)?.ok_or_else(|| anyhow::anyhow!("no view config cache"))
 }
 
 fn save_view_config_to_file(path: &Path, cache: &ViewConfigCache) -> anyhow::Result<()> {
-    let writer = fs::File::create(path)?;
-    Ok(serde_json::to_writer_pretty(BufWriter::new(writer), cache)?)
+    appdata::save(path, &VIEW_CONFIG_FORMAT, cache)
 }
 
 fn load_view_config_from_appdata() -> anyhow::Result<ViewConfigCache> {
@@ -880,30 +1608,86 @@ fn save_view_config_to_appdata(cache: &ViewConfigCache) -> anyhow::Result<()> {
     save_view_config_to_file(&path, cache)
 }
 
+const SAVE_HISTORY_FILENAME: &str = "save_history";
+
+const SAVE_HISTORY_FORMAT: CacheFormat = CacheFormat {
+    name: "save history",
+    migrations: &[appdata::from_unversioned],
+};
+
+/// Loads the history of saves, which is shared by the app and `ghui-util` so
+/// that a save made by either can be reverted by either.
+pub fn load_save_history() -> anyhow::Result<SaveHistory> {
+    let path = get_appdata_path(SAVE_HISTORY_FILENAME);
+    info!("Attempting to load save history from {path:?}");
+
+    Ok(appdata::load(&path, &SAVE_HISTORY_FORMAT)?.unwrap_or_default())
+}
+
+/// Adds the changes committed by `report` to the save history.  Returns the
+/// updated history and the number of the new record, if anything was
+/// committed.  `work_items` must be the state from *before* the save.  The
+/// history is reloaded first so that saves recorded by other processes are
+/// kept.
+pub fn record_save_history(
+    report: &SaveReport,
+    work_items: &WorkItems,
+) -> anyhow::Result<(SaveHistory, Option<usize>)> {
+    let mut history = load_save_history()?;
+    let number = history.record(report, work_items);
+    if let Some(number) = number {
+        let path = get_appdata_path(SAVE_HISTORY_FILENAME);
+        info!("Recording save #{number} in {path:?}");
+        appdata::save(&path, &SAVE_HISTORY_FORMAT, &history)?;
+    }
+    Ok((history, number))
+}
+
 pub fn save_work_items_extra_data(data: &str) -> anyhow::Result<()> {
     let path = get_appdata_path(WORK_ITEMS_EXTRA_DATA);
     info!("Saving work items extra data to {path:?}");
 
-    let mut writer = fs::File::create(path)?;
-    writer.write_all(data.as_bytes())?;
-    Ok(())
+    let data: serde_json::Value = serde_json::from_str(data)?;
+    appdata::save(&path, &WORK_ITEMS_EXTRA_DATA_FORMAT, &data)
 }
 
 pub fn load_work_items_extra_data() -> anyhow::Result<String> {
     let path = get_appdata_path(WORK_ITEMS_EXTRA_DATA);
     info!("Loading work items extra data from {path:?}");
 
-    let mut reader = fs::File::open(path)?;
+    let data: serde_json::Value = appdata::load(&path, &WORK_ITEMS_EXTRA_DATA_FORMAT)?
+        .ok_or_else(|| anyhow::anyhow!("no work items extra data"))?;
+    Ok(serde_json::to_string_pretty(&data)?)
+}
+
+const SANITIZE_CONFIG_FILENAME: &str = "sanitize";
+
+/// Returns the path to the sanitize config file (`~/sanitize.ghui.json`),
+/// which enables/disables rules and overrides their severity.
+pub fn get_sanitize_config_path() -> PathBuf {
+    get_appdata_path(SANITIZE_CONFIG_FILENAME)
+}
+
+/// Loads the sanitize config from `path`.  A missing file means every rule
+/// runs with its default settings.
+pub fn load_sanitize_config(path: &Path) -> Result<SanitizeConfig> {
+    let reader = match fs::File::open(path) {
+        Ok(reader) => reader,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(SanitizeConfig::default());
+        }
+        Err(e) => return Err(e.into()),
+    };
 
-    let mut buf = String::new();
-    reader.read_to_string(&mut buf)?;
-    Ok(buf)
+    info!("Loading sanitize config from {path:?}");
+    serde_json::from_reader(BufReader::new(reader))
+        .map_err(|e| anyhow::anyhow!("invalid sanitize config {path:?}: {e}"))
 }
 
-fn get_appdata_path(name: &str) -> PathBuf {
-    let mut path = home_dir().unwrap();
-    path.push(format!("{name}.ghui.json"));
-    path
+/// Returns the sanitize rules and their settings from the config file.
+pub fn get_sanitize_rules() -> Result<Vec<SanitizeRuleInfo>> {
+    let config = load_sanitize_config(&get_sanitize_config_path())?;
+    Ok(SanitizeRules::default().describe(&config))
 }
 
 fn summarize_refresh_changes(
@@ -935,7 +1719,9 @@ fn summarize_refresh_changes(
 mod tests {
     use super::*;
     use github_graphql::data::test_helpers::TestData;
-    use github_graphql::data::{Issue, IssueState, PullRequest, PullRequestState, WorkItemData};
+    use github_graphql::data::{
+        ChangeData, Issue, IssueState, PullRequest, PullRequestState, WorkItemData,
+    };
     use github_graphql::pivot::{Axis, MultiValueStrategy, PivotField};
     use std::sync::{Arc, Mutex};
     use tempfile::NamedTempFile;
@@ -1005,6 +1791,27 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn test_failed_text_load_is_not_retried_until_reload() {
+        let mut data = TestData::default();
+        let id = data.build().add();
+
+        let mut state = AppState::new();
+        state.work_items = Some(data.work_items);
+
+        let ids = state.start_text_load().unwrap();
+        assert_eq!(ids, vec![id]);
+        assert_eq!(state.start_text_load(), None);
+
+        state.finish_text_load(
+            &ids,
+            Err(github_graphql::Error::Connectivity("offline".to_owned())),
+        );
+        assert_eq!(state.text_load, TextLoad::Failed);
+        assert_eq!(state.start_text_load(), None);
+        assert!(state.search("anything", 10).is_empty());
+    }
+
     #[test]
     fn test_summarize_refresh_changes_counts_new_and_updated_items() {
         let mut previous_data = TestData::default();
@@ -1079,12 +1886,13 @@ mod tests {
             },
             pivot_config: PivotConfig {
                 recipe: vec![
-                    Axis::Pivot(PivotField::Status),
-                    Axis::Group(PivotField::Epic),
-                    Axis::Hierarchy,
+                    Axis::Pivot(PivotField::Status.into()),
+                    Axis::Group(PivotField::Epic.into()),
+                    Axis::Hierarchy(None),
                 ],
                 multi_value_strategy: MultiValueStrategy::Explode,
                 show_ghost_ancestors: false,
+                aggregates: Vec::new(),
             },
         };
 
@@ -1121,7 +1929,87 @@ mod tests {
 
         save_view_config_to_file(file.path(), &loaded).unwrap();
         let persisted = fs::read_to_string(file.path()).unwrap();
-        assert!(persisted.contains("\"pivotConfig\""));
+        let persisted: serde_json::Value = serde_json::from_str(&persisted).unwrap();
+        assert_eq!(persisted["version"], 1);
+        assert!(persisted["data"].get("pivotConfig").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_set_filter_query() {
+        let data = TestData::default();
+        let mut state = AppState::new();
+        state.fields = Some(data.fields);
+        state.work_items = Some(data.work_items);
+
+        state.set_filter_query("is:open epic:X").await.unwrap();
+        assert_eq!(
+            state.view_config_cache().filters.query,
+            Some(parse_query("is:open AND epic:X").unwrap())
+        );
+
+        assert!(state.set_filter_query("bogus:1").await.is_err());
+        assert!(state.filters.query.is_some());
+
+        state.set_filter_query("  ").await.unwrap();
+        assert_eq!(state.filters.query, None);
+    }
+
+    #[tokio::test]
+    async fn test_apply_view() {
+        let data = TestData::default();
+        let mut state = AppState::new();
+        state.fields = Some(data.fields);
+        state.work_items = Some(data.work_items);
+
+        let triage = views::SavedView {
+            name: "Triage".to_owned(),
+            filters: Filters {
+                hide_closed: true,
+                ..Default::default()
+            },
+            pivot_config: PivotConfig {
+                recipe: vec![Axis::Pivot(PivotField::Status.into())],
+                multi_value_strategy: MultiValueStrategy::Explode,
+                show_ghost_ancestors: false,
+                aggregates: Vec::new(),
+            },
+        };
+        state.views.save(triage.clone()).unwrap();
+
+        state.apply_view("Triage").await.unwrap();
+        let cache = state.view_config_cache();
+        assert_eq!(cache.pivot_config, triage.pivot_config);
+        assert!(cache.filters.hide_closed);
+
+        assert!(state.apply_view("Missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_open_deep_link() {
+        let data = TestData::default();
+        let mut state = AppState::new();
+        state.fields = Some(data.fields);
+        state.work_items = Some(data.work_items);
+        state.filters.hide_closed = true;
+
+        let url = "ghui://open?project=llvm/4&recipe=Pivot(Status)&query=is:open&item=I_1";
+        state
+            .open_deep_link(deep_link::parse(url).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            state.pivot_config.recipe,
+            vec![Axis::Pivot(PivotField::Status.into())]
+        );
+        assert!(state.filters.hide_closed);
+        assert_eq!(state.filters.query, Some(parse_query("is:open").unwrap()));
+        assert_eq!(state.take_focus_item(), Some(WorkItemId("I_1".to_owned())));
+        assert_eq!(state.take_focus_item(), None);
+
+        let link = deep_link::parse(&state.deep_link(None)).unwrap();
+        assert_eq!(link.recipe, Some(state.pivot_config.recipe.clone()));
+        assert_eq!(link.item, None);
     }
 
     #[tokio::test]
@@ -1132,9 +2020,10 @@ mod tests {
         state.work_items = Some(data.work_items);
 
         let non_default_pivot = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Status)],
+            recipe: vec![Axis::Pivot(PivotField::Status.into())],
             multi_value_strategy: MultiValueStrategy::Explode,
             show_ghost_ancestors: false,
+            aggregates: Vec::new(),
         };
         let new_filters = Filters {
             status: vec![Some(FieldOptionId("status-a".to_string()))],
@@ -1153,6 +2042,67 @@ mod tests {
         assert_eq!(cache.filters.status, new_filters.status);
     }
 
+    #[tokio::test]
+    async fn test_refresh_nodes_sends_node_edits() {
+        let mut data = TestData::default();
+        let moved = data.build().status("Active").add();
+        data.build().status("Active").add();
+        data.build().status("Closed").add();
+
+        let mut state = AppState::new();
+        state.fields = Some(data.fields.clone());
+        state.work_items = Some(data.work_items.clone());
+        state.filters = Filters::default();
+        state.pivot_config = PivotConfig {
+            recipe: vec![
+                Axis::Pivot(PivotField::Status.into()),
+                Axis::Hierarchy(None),
+            ],
+            ..PivotConfig::default()
+        };
+        state.refresh(false).await.unwrap();
+
+        let patches = Arc::new(Mutex::new(Vec::new()));
+        let patches_clone = Arc::clone(&patches);
+        state.watcher = Arc::new(Box::new(move |update| {
+            if let DataUpdate::Patch(patch) = update {
+                patches_clone.lock().unwrap().push(patch.node_edits);
+            }
+        }));
+
+        let mut item = data.work_items.get(&moved).unwrap().clone();
+        item.project_item.status = data.fields.status.option_id(Some("Closed")).cloned();
+        state.work_items.as_mut().unwrap().update(item.clone());
+        state
+            .refresh_nodes(vec![item], &HashSet::from([moved.clone()]))
+            .await
+            .unwrap();
+
+        let patches = patches.lock().unwrap();
+        assert_eq!(patches.len(), 1);
+        let removed = format!("path/status=id(Active)/{}", moved.0);
+        let inserted = format!("path/status=id(Closed)/{}", moved.0);
+        assert!(
+            patches[0]
+                .iter()
+                .any(|edit| matches!(edit, NodeEdit::Remove { count: 1, .. }))
+        );
+        assert!(patches[0].iter().any(|edit| matches!(
+            edit,
+            NodeEdit::Insert { nodes, .. } if nodes.iter().any(|node| node.id == inserted)
+        )));
+
+        let ids: Vec<&str> = state
+            .nodes
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|node| node.id.as_str())
+            .collect();
+        assert!(!ids.contains(&removed.as_str()));
+        assert!(ids.contains(&inserted.as_str()));
+    }
+
     #[tokio::test]
     async fn test_set_pivot_config_triggers_data_update() {
         let data = TestData::default();
@@ -1172,9 +2122,10 @@ mod tests {
         }));
 
         let next = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Status)],
+            recipe: vec![Axis::Pivot(PivotField::Status.into())],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: true,
+            aggregates: Vec::new(),
         };
 
         state.set_pivot_config(next.clone()).await.unwrap();
@@ -1379,6 +2330,7 @@ mod tests {
         data.work_items.get_mut(id).unwrap().data = WorkItemData::PullRequest(PullRequest {
             state: state.into(),
             assignees: Vec::new(),
+            ..Default::default()
         });
     }
 }
@@ -2,10 +2,14 @@ use github_graphql::data::{FieldOptionId, WorkItemId};
 use serde::Serialize;
 use ts_rs::TS;
 
+mod cross_tab;
+mod diff;
 pub(crate) mod recipe_builder;
+pub use cross_tab::{CrossTab, CrossTabHeader};
+pub use diff::{NodeEdit, diff_nodes};
 pub(crate) use recipe_builder::RecipeNodeBuilder;
 
-#[derive(Serialize, TS, Debug)]
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Node {
     pub level: u32,
@@ -14,9 +18,25 @@ pub struct Node {
     pub has_children: bool,
     pub is_modified: bool,
     pub is_ghost: bool,
+    /// Rollups over the work items under this node, for groups and parents
+    /// when `PivotConfig::aggregates` isn't empty.
+    pub aggregates: Option<Aggregates>,
 }
 
-#[derive(Serialize, TS, Debug)]
+/// Only the aggregates selected in `PivotConfig::aggregates` are set.
+#[derive(Serialize, TS, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Aggregates {
+    pub count: Option<usize>,
+    pub open: Option<usize>,
+    pub closed: Option<usize>,
+    /// Sum of the items' Estimate values, in days.
+    pub estimate: Option<f64>,
+    /// Percentage of items whose Status is Done or Closed, rounded down.
+    pub percent_done: Option<usize>,
+}
+
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum NodeData {
     #[serde(rename_all = "camelCase")]
@@ -0,0 +1,116 @@
+use github_graphql::{
+    data::{FieldOptionId, WorkItemId, WorkItems},
+    pivot::CrossTabValue,
+};
+use serde::Serialize;
+use ts_rs::TS;
+
+/// A two dimensional pivot of the work items that pass the active filters.
+/// `cells[row][column]` lists the items with both values.  With
+/// `MultiValueStrategy::Explode` an item can appear in more than one cell.
+#[derive(Serialize, TS, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CrossTab {
+    pub rows: Vec<CrossTabHeader>,
+    pub columns: Vec<CrossTabHeader>,
+    pub cells: Vec<Vec<Vec<WorkItemId>>>,
+}
+
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CrossTabHeader {
+    pub key: String,
+    pub label: String,
+    pub field_option_id: Option<FieldOptionId>,
+}
+
+impl CrossTab {
+    /// Formats the cross-tab as CSV, with the column labels as the header
+    /// row and the row labels as the first column.
+    pub fn to_csv(&self, value: CrossTabValue, work_items: &WorkItems) -> String {
+        let mut csv = String::new();
+
+        let header = std::iter::once(String::new())
+            .chain(self.columns.iter().map(|column| column.label.clone()))
+            .collect::<Vec<_>>();
+        push_csv_record(&mut csv, &header);
+
+        for (row, cells) in self.rows.iter().zip(&self.cells) {
+            let record = std::iter::once(row.label.clone())
+                .chain(cells.iter().map(|items| {
+                    match value {
+                        CrossTabValue::Count => items.len().to_string(),
+                        CrossTabValue::Items => items
+                            .iter()
+                            .filter_map(|id| work_items.get(id))
+                            .map(|item| item.title.as_str())
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    }
+                }))
+                .collect::<Vec<_>>();
+            push_csv_record(&mut csv, &record);
+        }
+
+        csv
+    }
+}
+
+fn push_csv_record(csv: &mut String, fields: &[String]) {
+    let fields = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    csv.push_str(&fields.join(","));
+    csv.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use github_graphql::data::test_helpers::TestData;
+
+    use super::*;
+
+    fn header(label: &str) -> CrossTabHeader {
+        CrossTabHeader {
+            key: label.to_owned(),
+            label: label.to_owned(),
+            field_option_id: None,
+        }
+    }
+
+    #[test]
+    fn test_cross_tab_to_csv() {
+        let mut data = TestData::default();
+        let first = data.build().add();
+        let second = data.build().add();
+        data.work_items.get_mut(&first).unwrap().title = "Fix \"quotes\", commas".to_owned();
+        data.work_items.get_mut(&second).unwrap().title = "Second".to_owned();
+
+        let cross_tab = CrossTab {
+            rows: vec![header("WS1"), header("(none)")],
+            columns: vec![header("S1"), header("S2, late")],
+            cells: vec![
+                vec![vec![first.clone(), second.clone()], vec![]],
+                vec![vec![], vec![second.clone()]],
+            ],
+        };
+
+        assert_eq!(
+            cross_tab.to_csv(CrossTabValue::Count, &data.work_items),
+            ",S1,\"S2, late\"\r\nWS1,2,0\r\n(none),0,1\r\n"
+        );
+        assert_eq!(
+            cross_tab.to_csv(CrossTabValue::Items, &data.work_items),
+            ",S1,\"S2, late\"\r\nWS1,\"Fix \"\"quotes\"\", commas\nSecond\",\r\n(none),,Second\r\n"
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use ts_rs::TS;
+
+use super::Node;
+
+/// One step in turning the nodes the frontend has into the current ones.
+/// Edits are applied in order, and each index refers to the nodes as they
+/// are after the previous edits.
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "type")]
+#[ts(export)]
+pub enum NodeEdit {
+    /// Removes `count` nodes, starting at `index`.
+    Remove { index: usize, count: usize },
+    /// Inserts `nodes` before `index`.
+    Insert { index: usize, nodes: Vec<Node> },
+    /// Moves the node at `from` so that it ends up at `to`.
+    Move { from: usize, to: usize },
+    /// Replaces the node at `index` with `node`, which has the same id.
+    Update { index: usize, node: Node },
+}
+
+/// Returns the edits that turn `old` into `new`.  Nodes are matched by id,
+/// which is unique within a tree; should an id repeat, the extra nodes are
+/// inserted or removed rather than moved.
+pub fn diff_nodes(old: &[Node], new: &[Node]) -> Vec<NodeEdit> {
+    let new_ids: HashSet<&str> = new.iter().map(|node| node.id.as_str()).collect();
+    let mut edits = Vec::new();
+
+    // Remove the nodes that are gone first, so that every node left in
+    // `kept` has a place in `new`.
+    let mut kept: Vec<&Node> = Vec::with_capacity(old.len());
+    let mut index = 0;
+    while index < old.len() {
+        if new_ids.contains(old[index].id.as_str()) {
+            kept.push(&old[index]);
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < old.len() && !new_ids.contains(old[index].id.as_str()) {
+            index += 1;
+        }
+        edits.push(NodeEdit::Remove {
+            index: kept.len(),
+            count: index - start,
+        });
+    }
+
+    let mut slots: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (slot, node) in kept.iter().enumerate() {
+        slots.entry(node.id.as_str()).or_default().push_back(slot);
+    }
+
+    // Once `index` nodes have been placed they match the start of `new`, and
+    // the kept nodes that haven't been placed follow them in their original
+    // order.
+    let mut unplaced = Unplaced::new(kept.len());
+    for (index, node) in new.iter().enumerate() {
+        let Some(slot) = slots
+            .get_mut(node.id.as_str())
+            .and_then(VecDeque::pop_front)
+        else {
+            match edits.last_mut() {
+                Some(NodeEdit::Insert {
+                    index: start,
+                    nodes,
+                }) if *start + nodes.len() == index => nodes.push(node.clone()),
+                _ => edits.push(NodeEdit::Insert {
+                    index,
+                    nodes: vec![node.clone()],
+                }),
+            }
+            continue;
+        };
+
+        let from = index + unplaced.before(slot);
+        if from != index {
+            edits.push(NodeEdit::Move { from, to: index });
+        }
+        unplaced.place(slot);
+
+        if kept[slot] != node {
+            edits.push(NodeEdit::Update {
+                index,
+                node: node.clone(),
+            });
+        }
+    }
+
+    if unplaced.len > 0 {
+        edits.push(NodeEdit::Remove {
+            index: new.len(),
+            count: unplaced.len,
+        });
+    }
+
+    edits
+}
+
+/// The kept nodes that haven't been placed yet, as a Fenwick tree over their
+/// slots so that finding a node's current index doesn't mean walking the
+/// ones before it.
+struct Unplaced {
+    tree: Vec<usize>,
+    len: usize,
+}
+
+impl Unplaced {
+    fn new(len: usize) -> Self {
+        // With every slot counting one, each entry covers as many slots as
+        // its lowest set bit.
+        Unplaced {
+            tree: (0..=len).map(|i| i & i.wrapping_neg()).collect(),
+            len,
+        }
+    }
+
+    /// The number of unplaced slots before `slot`.
+    fn before(&self, slot: usize) -> usize {
+        let mut count = 0;
+        let mut i = slot;
+        while i > 0 {
+            count += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        count
+    }
+
+    fn place(&mut self, slot: usize) {
+        let mut i = slot + 1;
+        while i < self.tree.len() {
+            self.tree[i] -= 1;
+            i += i & i.wrapping_neg();
+        }
+        self.len -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::NodeData;
+    use github_graphql::data::WorkItemId;
+
+    fn node(id: &str, level: u32) -> Node {
+        Node {
+            level,
+            id: id.to_owned(),
+            data: NodeData::WorkItem {
+                work_item_id: WorkItemId(id.to_owned()),
+            },
+            has_children: false,
+            is_modified: false,
+            is_ghost: false,
+            aggregates: None,
+        }
+    }
+
+    fn apply(old: &[Node], edits: &[NodeEdit]) -> Vec<Node> {
+        let mut nodes = old.to_vec();
+        for edit in edits {
+            match edit {
+                NodeEdit::Remove { index, count } => {
+                    nodes.drain(*index..index + count);
+                }
+                NodeEdit::Insert { index, nodes: new } => {
+                    nodes.splice(*index..*index, new.iter().cloned());
+                }
+                NodeEdit::Move { from, to } => {
+                    let node = nodes.remove(*from);
+                    nodes.insert(*to, node);
+                }
+                NodeEdit::Update { index, node } => nodes[*index] = node.clone(),
+            }
+        }
+        nodes
+    }
+
+    #[test]
+    fn test_diff_nodes() {
+        let old = vec![
+            node("a", 0),
+            node("b", 1),
+            node("c", 1),
+            node("d", 0),
+            node("e", 1),
+        ];
+        let mut changed = node("e", 1);
+        changed.has_children = true;
+        let new = vec![
+            node("d", 0),
+            node("x", 1),
+            node("y", 1),
+            changed,
+            node("a", 0),
+            node("c", 1),
+        ];
+
+        let edits = diff_nodes(&old, &new);
+        assert_eq!(apply(&old, &edits), new);
+        assert_eq!(
+            edits,
+            vec![
+                NodeEdit::Remove { index: 1, count: 1 },
+                NodeEdit::Move { from: 2, to: 0 },
+                NodeEdit::Insert {
+                    index: 1,
+                    nodes: vec![node("x", 1), node("y", 1)],
+                },
+                NodeEdit::Move { from: 5, to: 3 },
+                NodeEdit::Update {
+                    index: 3,
+                    node: new[3].clone(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_identical_nodes_is_empty() {
+        let nodes = vec![node("a", 0), node("b", 1)];
+        assert!(diff_nodes(&nodes, &nodes).is_empty());
+        assert_eq!(apply(&[], &diff_nodes(&[], &nodes)), nodes);
+        assert_eq!(apply(&nodes, &diff_nodes(&nodes, &[])), vec![]);
+    }
+
+    #[test]
+    fn test_diff_nodes_with_repeated_ids() {
+        let old = vec![node("a", 0), node("b", 0), node("a", 0)];
+        let new = vec![node("a", 0), node("c", 0), node("a", 0), node("a", 0)];
+        assert_eq!(apply(&old, &diff_nodes(&old, &new)), new);
+        assert_eq!(apply(&new, &diff_nodes(&new, &old)), old);
+
+        let old = vec![node("a", 0), node("b", 0)];
+        let new = vec![node("b", 0), node("a", 0), node("b", 0)];
+        assert_eq!(apply(&old, &diff_nodes(&old, &new)), new);
+        assert_eq!(apply(&new, &diff_nodes(&new, &old)), old);
+    }
+
+    #[test]
+    fn test_diff_reversed_nodes() {
+        let old: Vec<Node> = (0..100).map(|i| node(&i.to_string(), 0)).collect();
+        let new: Vec<Node> = old.iter().rev().cloned().collect();
+        let edits = diff_nodes(&old, &new);
+        assert_eq!(apply(&old, &edits), new);
+        assert_eq!(edits.len(), 99);
+    }
+}
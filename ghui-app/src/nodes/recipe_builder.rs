@@ -1,16 +1,23 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
 
 use github_graphql::{
     data::{
-        Field, FieldOptionId, Fields, Issue, IssueState, PullRequest, PullRequestState, WorkItem,
-        WorkItemData, WorkItemId, WorkItems,
+        DelayLoad, Field, FieldOptionId, Fields, WorkItem, WorkItemData, WorkItemId, WorkItems,
+    },
+    pivot::{
+        Aggregate, Axis, Bucketing, ItemAttribute, ItemFieldValue, MultiValueStrategy, PivotConfig,
+        PivotField, SortField, SortKey, item_field_values,
     },
-    pivot::{Axis, MultiValueStrategy, PivotConfig, PivotField},
+    query::{Query, QueryContext},
 };
+use time::{Date, Duration, Month, OffsetDateTime};
 
 use crate::Filters;
 
-use super::{Node, NodeData};
+use super::{Aggregates, CrossTab, CrossTabHeader, Node, NodeData};
 
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 enum SortValue {
@@ -26,6 +33,20 @@ struct FieldValue {
     field_option_id: Option<FieldOptionId>,
 }
 
+impl From<ItemFieldValue<'_>> for FieldValue {
+    fn from(value: ItemFieldValue<'_>) -> Self {
+        FieldValue {
+            key: value.key.to_owned(),
+            label: value.name.to_owned(),
+            sort_value: match value.index {
+                Some(index) => SortValue::Index(index),
+                None => SortValue::Text(value.name.to_owned()),
+            },
+            field_option_id: value.option_id.cloned(),
+        }
+    }
+}
+
 struct Bucket {
     key: String,
     label: String,
@@ -40,6 +61,12 @@ pub(crate) struct RecipeNodeBuilder<'a> {
     filters: &'a Filters,
     original_work_items: &'a HashMap<WorkItemId, WorkItem>,
     pivot_config: &'a PivotConfig,
+    viewer: Option<&'a str>,
+    today: Date,
+    sort_keys: Vec<SortKey>,
+    /// How many more levels of the tree the enclosing `Hierarchy` axis
+    /// shows, counting the one being rendered, if it limits them.
+    depth_left: Option<u32>,
     nodes: Vec<Node>,
 }
 
@@ -57,10 +84,30 @@ impl<'a> RecipeNodeBuilder<'a> {
             filters,
             original_work_items,
             pivot_config,
+            viewer: None,
+            today: OffsetDateTime::now_utc().date(),
+            sort_keys: Vec::new(),
+            depth_left: None,
             nodes: Vec::new(),
         }
     }
 
+    pub fn work_items(&self) -> &'a WorkItems {
+        self.work_items
+    }
+
+    /// Sets the login that `@me` in the filter query refers to.
+    pub fn with_viewer(mut self, viewer: Option<&'a str>) -> Self {
+        self.viewer = viewer;
+        self
+    }
+
+    /// Sets the date that `UpdatedAge` measures from.
+    pub fn with_today(mut self, today: Date) -> Self {
+        self.today = today;
+        self
+    }
+
     pub fn build(&mut self) -> Vec<Node> {
         let items = self
             .work_items
@@ -69,9 +116,227 @@ impl<'a> RecipeNodeBuilder<'a> {
             .cloned()
             .collect();
         self.render_scope(items, &self.pivot_config.recipe, 0, "");
+        self.compute_aggregates(0);
+        std::mem::take(&mut self.nodes)
+    }
+
+    /// Builds the same nodes as `build`, given the nodes built before the
+    /// items in `affected` changed.  When the recipe buckets the items at the
+    /// top level, only the buckets that hold, or held, an affected item are
+    /// rendered again; the others are copied from `previous`.  `previous`
+    /// must have been built with the same configuration.
+    pub fn rebuild(&mut self, previous: &[Node], affected: &HashSet<WorkItemId>) -> Vec<Node> {
+        let mut items: Vec<WorkItemId> = self
+            .work_items
+            .iter()
+            .filter(|id| self.should_include(id))
+            .cloned()
+            .collect();
+
+        // Changing an item can move any of its descendants between buckets
+        // of these fields, and those aren't in `affected`.
+        if self.pivot_config.recipe.iter().any(axis_reads_ancestors) {
+            return self.build();
+        }
+
+        // Filter and Sort axes apply to every bucket, so they can be applied
+        // up front.
+        let mut recipe: &'a [Axis] = &self.pivot_config.recipe;
+        let bucketing = loop {
+            match recipe.first() {
+                Some(Axis::Pivot(bucketing) | Axis::Group(bucketing)) => break bucketing,
+                Some(Axis::Filter(query)) => items = self.filter_items(items, query),
+                Some(Axis::Sort(keys)) => self.sort_keys = keys.clone(),
+                Some(Axis::Hierarchy(_)) | None => {
+                    self.sort_keys = Vec::new();
+                    return self.build();
+                }
+            }
+            recipe = &recipe[1..];
+        };
+
+        let matches: Vec<WorkItemId> = items
+            .into_iter()
+            .filter(|id| self.is_genuine_match(id))
+            .collect();
+        let buckets = self.bucket_items(matches, bucketing);
+        let group_ids: HashSet<String> = buckets
+            .iter()
+            .map(|bucket| self.group_node_id("", &bucketing.field, &bucket.key))
+            .collect();
+
+        // A collapsed group keeps the id of the group beneath it, which
+        // extends its own, so each previous group goes to the longest bucket
+        // id that its top-level node's id starts with.
+        let mut previous_groups: HashMap<&str, &[Node]> = HashMap::new();
+        let mut start = 0;
+        while start < previous.len() {
+            let end = start
+                + 1
+                + previous[start + 1..]
+                    .iter()
+                    .take_while(|node| node.level > 0)
+                    .count();
+            let id = previous[start].id.as_str();
+            let group_id = id
+                .match_indices('/')
+                .map(|(index, _)| &id[..index])
+                .chain([id])
+                .filter(|prefix| group_ids.contains(*prefix))
+                .last();
+            if let Some(group_id) = group_id {
+                previous_groups.insert(group_id, &previous[start..end]);
+            }
+            start = end;
+        }
+
+        for bucket in buckets {
+            let group_id = self.group_node_id("", &bucketing.field, &bucket.key);
+            let unchanged = previous_groups.get(group_id.as_str()).filter(|nodes| {
+                !bucket.items.iter().any(|id| affected.contains(id))
+                    && !nodes.iter().any(|node| match &node.data {
+                        NodeData::WorkItem { work_item_id } => affected.contains(work_item_id),
+                        NodeData::Group { .. } => false,
+                    })
+            });
+
+            match unchanged {
+                Some(nodes) => self.nodes.extend_from_slice(nodes),
+                None => {
+                    let start = self.nodes.len();
+                    self.render_bucket(bucket, bucketing, &recipe[1..], 0, "");
+                    self.compute_aggregates(start);
+                }
+            }
+        }
+
+        self.sort_keys = Vec::new();
         std::mem::take(&mut self.nodes)
     }
 
+    /// Pivots the items that pass the filters by `rows` and `columns`.  Rows
+    /// and columns are ordered the same way as the buckets of a `Pivot` axis,
+    /// and only values that some item has get a row or column.
+    pub fn build_cross_tab(&self, rows: &PivotField, columns: &PivotField) -> CrossTab {
+        let items: Vec<WorkItemId> = self
+            .work_items
+            .iter()
+            .filter(|id| self.is_genuine_match(id))
+            .cloned()
+            .collect();
+
+        let column_buckets = self.bucket_by_field(items.clone(), columns);
+        let column_indexes: HashMap<&str, usize> = column_buckets
+            .iter()
+            .enumerate()
+            .map(|(index, bucket)| (bucket.key.as_str(), index))
+            .collect();
+
+        let mut cross_tab = CrossTab {
+            rows: Vec::new(),
+            columns: column_buckets.iter().map(Self::cross_tab_header).collect(),
+            cells: Vec::new(),
+        };
+
+        for row in self.bucket_by_field(items, rows) {
+            let mut cells = vec![Vec::new(); column_buckets.len()];
+            for column in self.bucket_by_field(row.items.clone(), columns) {
+                let mut items = column.items;
+                self.order_items(&mut items);
+                cells[column_indexes[column.key.as_str()]] = items;
+            }
+            cross_tab.rows.push(Self::cross_tab_header(&row));
+            cross_tab.cells.push(cells);
+        }
+
+        cross_tab
+    }
+
+    fn cross_tab_header(bucket: &Bucket) -> CrossTabHeader {
+        CrossTabHeader {
+            key: bucket.key.clone(),
+            label: bucket.label.clone(),
+            field_option_id: bucket.field_option_id.clone(),
+        }
+    }
+
+    /// Sets the aggregates of every group and parent node, from `start` on,
+    /// from the work items rendered beneath it.  Ghost ancestors are only
+    /// there for context, so they aren't counted.
+    fn compute_aggregates(&mut self, start: usize) {
+        if self.pivot_config.aggregates.is_empty() {
+            return;
+        }
+
+        for index in start..self.nodes.len() {
+            if !self.nodes[index].has_children {
+                continue;
+            }
+
+            let level = self.nodes[index].level;
+            let items: HashSet<&WorkItemId> = self.nodes[index + 1..]
+                .iter()
+                .take_while(|node| node.level > level)
+                .filter(|node| !node.is_ghost)
+                .filter_map(|node| match &node.data {
+                    NodeData::WorkItem { work_item_id } => Some(work_item_id),
+                    NodeData::Group { .. } => None,
+                })
+                .collect();
+
+            let aggregates = self.aggregate(&items);
+            self.nodes[index].aggregates = Some(aggregates);
+        }
+    }
+
+    fn aggregate(&self, ids: &HashSet<&WorkItemId>) -> Aggregates {
+        let items: Vec<&WorkItem> = ids.iter().filter_map(|id| self.item(id)).collect();
+        let count_closed = |closed: bool| {
+            items
+                .iter()
+                .filter(|item| item.is_closed() == DelayLoad::Loaded(closed))
+                .count()
+        };
+
+        let mut aggregates = Aggregates::default();
+        for aggregate in &self.pivot_config.aggregates {
+            match aggregate {
+                Aggregate::Count => aggregates.count = Some(items.len()),
+                Aggregate::Open => aggregates.open = Some(count_closed(false)),
+                Aggregate::Closed => aggregates.closed = Some(count_closed(true)),
+                Aggregate::Estimate => {
+                    aggregates.estimate = Some(
+                        items
+                            .iter()
+                            .filter_map(|item| {
+                                self.fields
+                                    .estimate
+                                    .option_name(item.project_item.estimate.as_ref())
+                                    .and_then(estimate_days)
+                            })
+                            .sum(),
+                    )
+                }
+                Aggregate::PercentDone => {
+                    let done = items
+                        .iter()
+                        .filter(|item| {
+                            self.fields
+                                .status
+                                .option_name(item.project_item.status.as_ref())
+                                .is_some_and(|status| {
+                                    status.eq_ignore_ascii_case("done")
+                                        || status.eq_ignore_ascii_case("closed")
+                                })
+                        })
+                        .count();
+                    aggregates.percent_done = (!items.is_empty()).then(|| done * 100 / items.len());
+                }
+            }
+        }
+        aggregates
+    }
+
     fn render_scope(&mut self, items: Vec<WorkItemId>, recipe: &[Axis], level: u32, path: &str) {
         if items.is_empty() {
             return;
@@ -79,7 +344,7 @@ impl<'a> RecipeNodeBuilder<'a> {
 
         if recipe.is_empty() {
             let mut items = items;
-            items.sort_by(|a, b| self.item_title(a).cmp(self.item_title(b)));
+            self.order_items(&mut items);
             for id in items {
                 self.push_item(&id, level, false, false, path);
             }
@@ -87,7 +352,7 @@ impl<'a> RecipeNodeBuilder<'a> {
         }
 
         match &recipe[0] {
-            Axis::Pivot(field) | Axis::Group(field) => {
+            Axis::Pivot(bucketing) | Axis::Group(bucketing) => {
                 // Only bucket items that genuinely match the filters. An item
                 // that is in scope merely because a descendant matched (an
                 // "ancestor pull-in") must not be filed under its own field
@@ -101,32 +366,60 @@ impl<'a> RecipeNodeBuilder<'a> {
                     .into_iter()
                     .filter(|id| self.is_genuine_match(id))
                     .collect();
-                for bucket in self.bucket_by_field(matches, field) {
-                    let group_id = self.group_node_id(path, field, &bucket.key);
-                    self.nodes.push(Node {
-                        level,
-                        id: group_id.clone(),
-                        data: NodeData::Group {
-                            name: bucket.label,
-                            field_option_id: bucket.field_option_id,
-                        },
-                        has_children: !bucket.items.is_empty(),
-                        is_modified: false,
-                        is_ghost: false,
-                    });
-                    self.render_scope(bucket.items, &recipe[1..], level + 1, &group_id);
+                for bucket in self.bucket_items(matches, bucketing) {
+                    self.render_bucket(bucket, bucketing, &recipe[1..], level, path);
                 }
             }
-            Axis::Sort(field) => {
-                self.render_scope(self.sort_items(items, field), &recipe[1..], level, path);
+            Axis::Sort(keys) => {
+                let outer_keys = std::mem::replace(&mut self.sort_keys, keys.clone());
+                self.render_scope(items, &recipe[1..], level, path);
+                self.sort_keys = outer_keys;
+            }
+            Axis::Hierarchy(options) => {
+                let options = options.clone().unwrap_or_default();
+                let show_ghosts = options
+                    .ghosts
+                    .unwrap_or(self.pivot_config.show_ghost_ancestors);
+                let outer_depth = std::mem::replace(&mut self.depth_left, options.depth);
+                self.render_hierarchy(items, show_ghosts, &recipe[1..], level, path);
+                self.depth_left = outer_depth;
+            }
+            Axis::Filter(query) => {
+                self.render_scope(self.filter_items(items, query), &recipe[1..], level, path);
             }
-            Axis::Hierarchy => self.render_hierarchy(items, &recipe[1..], level, path),
         }
     }
 
+    fn render_bucket(
+        &mut self,
+        bucket: Bucket,
+        bucketing: &Bucketing,
+        recipe: &[Axis],
+        level: u32,
+        path: &str,
+    ) {
+        let group_id = self.group_node_id(path, &bucketing.field, &bucket.key);
+        self.nodes.push(Node {
+            level,
+            id: group_id.clone(),
+            data: NodeData::Group {
+                name: bucket.label,
+                field_option_id: bucket.field_option_id,
+            },
+            has_children: !bucket.items.is_empty(),
+            is_modified: false,
+            is_ghost: false,
+            aggregates: None,
+        });
+        let group_index = self.nodes.len() - 1;
+        self.render_scope(bucket.items, recipe, level + 1, &group_id);
+        self.finish_group(group_index, bucketing);
+    }
+
     fn render_hierarchy(
         &mut self,
         items: Vec<WorkItemId>,
+        show_ghosts: bool,
         child_recipe: &[Axis],
         level: u32,
         path: &str,
@@ -134,7 +427,7 @@ impl<'a> RecipeNodeBuilder<'a> {
         let in_scope = std::collections::HashSet::<WorkItemId>::from_iter(items.iter().cloned());
         let roots = self.roots_in_scope(&items, &in_scope);
 
-        if self.pivot_config.show_ghost_ancestors {
+        if show_ghosts {
             let mut ghost_ids = std::collections::HashSet::new();
             for root in &roots {
                 let mut parent = self.parent_id(root);
@@ -196,12 +489,12 @@ impl<'a> RecipeNodeBuilder<'a> {
         // tree, bucketing each node's children (and the roots themselves) by
         // the field. This differs from `Group` after `Hierarchy`, which only
         // buckets the first level of children.
-        if let [Axis::Pivot(field)] = child_recipe {
-            self.render_pivot_level(roots, scope_ids, ghost_ids, field, level, path);
+        if let [Axis::Pivot(bucketing)] = child_recipe {
+            self.render_pivot_level(roots, scope_ids, ghost_ids, bucketing, level, path);
             return;
         }
 
-        roots.sort_by(|a, b| self.item_title(a).cmp(self.item_title(b)));
+        self.order_items(&mut roots);
         for root in roots {
             self.render_tree_node(&root, scope_ids, ghost_ids, child_recipe, level, path);
         }
@@ -216,7 +509,7 @@ impl<'a> RecipeNodeBuilder<'a> {
         items: Vec<WorkItemId>,
         scope_ids: &std::collections::HashSet<WorkItemId>,
         ghost_ids: &std::collections::HashSet<WorkItemId>,
-        field: &PivotField,
+        bucketing: &Bucketing,
         level: u32,
         path: &str,
     ) {
@@ -224,8 +517,8 @@ impl<'a> RecipeNodeBuilder<'a> {
             return;
         }
 
-        for bucket in self.bucket_by_field(items, field) {
-            let group_id = self.group_node_id(path, field, &bucket.key);
+        for bucket in self.bucket_items(items, bucketing) {
+            let group_id = self.group_node_id(path, &bucketing.field, &bucket.key);
             self.nodes.push(Node {
                 level,
                 id: group_id.clone(),
@@ -236,12 +529,14 @@ impl<'a> RecipeNodeBuilder<'a> {
                 has_children: !bucket.items.is_empty(),
                 is_modified: false,
                 is_ghost: false,
+                aggregates: None,
             });
+            let group_index = self.nodes.len() - 1;
 
             let mut bucket_items = bucket.items;
-            bucket_items.sort_by(|a, b| self.item_title(a).cmp(self.item_title(b)));
+            self.order_items(&mut bucket_items);
             for id in bucket_items {
-                let children = self.children_in_scope(&id, scope_ids);
+                let children = self.tree_children(&id, scope_ids);
                 self.push_item(
                     &id,
                     level + 1,
@@ -251,16 +546,20 @@ impl<'a> RecipeNodeBuilder<'a> {
                 );
                 if !children.is_empty() {
                     let child_path = self.child_path(&group_id, &id);
+                    let depth_left = self.depth_left;
+                    self.depth_left = depth_left.map(|depth| depth - 1);
                     self.render_pivot_level(
                         children,
                         scope_ids,
                         ghost_ids,
-                        field,
+                        bucketing,
                         level + 2,
                         &child_path,
                     );
+                    self.depth_left = depth_left;
                 }
             }
+            self.finish_group(group_index, bucketing);
         }
     }
 
@@ -273,7 +572,7 @@ impl<'a> RecipeNodeBuilder<'a> {
         level: u32,
         path: &str,
     ) {
-        let children = self.children_in_scope(id, scope_ids);
+        let children = self.tree_children(id, scope_ids);
         self.push_item(
             id,
             level,
@@ -287,9 +586,11 @@ impl<'a> RecipeNodeBuilder<'a> {
         }
 
         let child_path = self.child_path(path, id);
+        let depth_left = self.depth_left;
+        self.depth_left = depth_left.map(|depth| depth - 1);
         if child_recipe.is_empty() {
             let mut children = children;
-            children.sort_by(|a, b| self.item_title(a).cmp(self.item_title(b)));
+            self.order_items(&mut children);
             for child in children {
                 self.render_tree_node(
                     &child,
@@ -300,17 +601,30 @@ impl<'a> RecipeNodeBuilder<'a> {
                     &child_path,
                 );
             }
-            return;
+        } else {
+            self.render_scope_in_tree(
+                children,
+                child_recipe,
+                level + 1,
+                scope_ids,
+                ghost_ids,
+                &child_path,
+            );
         }
+        self.depth_left = depth_left;
+    }
 
-        self.render_scope_in_tree(
-            children,
-            child_recipe,
-            level + 1,
-            scope_ids,
-            ghost_ids,
-            &child_path,
-        );
+    /// The children of `id` to render beneath it in a tree: none once the
+    /// enclosing `Hierarchy` axis's depth has been reached.
+    fn tree_children(
+        &self,
+        id: &WorkItemId,
+        scope_ids: &std::collections::HashSet<WorkItemId>,
+    ) -> Vec<WorkItemId> {
+        if self.depth_left.is_some_and(|depth| depth <= 1) {
+            return Vec::new();
+        }
+        self.children_in_scope(id, scope_ids)
     }
 
     fn render_scope_in_tree(
@@ -328,7 +642,7 @@ impl<'a> RecipeNodeBuilder<'a> {
 
         if recipe.is_empty() {
             let mut items = items;
-            items.sort_by(|a, b| self.item_title(a).cmp(self.item_title(b)));
+            self.order_items(&mut items);
             for id in items {
                 self.render_tree_node(&id, scope_ids, ghost_ids, recipe, level, path);
             }
@@ -336,9 +650,9 @@ impl<'a> RecipeNodeBuilder<'a> {
         }
 
         match &recipe[0] {
-            Axis::Pivot(field) | Axis::Group(field) => {
-                for bucket in self.bucket_by_field(items, field) {
-                    let group_id = self.group_node_id(path, field, &bucket.key);
+            Axis::Pivot(bucketing) | Axis::Group(bucketing) => {
+                for bucket in self.bucket_items(items, bucketing) {
+                    let group_id = self.group_node_id(path, &bucketing.field, &bucket.key);
                     self.nodes.push(Node {
                         level,
                         id: group_id.clone(),
@@ -349,7 +663,9 @@ impl<'a> RecipeNodeBuilder<'a> {
                         has_children: !bucket.items.is_empty(),
                         is_modified: false,
                         is_ghost: false,
+                        aggregates: None,
                     });
+                    let group_index = self.nodes.len() - 1;
                     self.render_scope_in_tree(
                         bucket.items,
                         &recipe[1..],
@@ -358,11 +674,20 @@ impl<'a> RecipeNodeBuilder<'a> {
                         ghost_ids,
                         &group_id,
                     );
+                    self.finish_group(group_index, bucketing);
                 }
             }
-            Axis::Sort(field) => {
+            Axis::Sort(keys) => {
+                let outer_keys = std::mem::replace(&mut self.sort_keys, keys.clone());
+                self.render_scope_in_tree(items, &recipe[1..], level, scope_ids, ghost_ids, path);
+                self.sort_keys = outer_keys;
+            }
+            Axis::Hierarchy(_) => {
+                self.render_scope_in_tree(items, &recipe[1..], level, scope_ids, ghost_ids, path);
+            }
+            Axis::Filter(query) => {
                 self.render_scope_in_tree(
-                    self.sort_items(items, field),
+                    self.filter_items(items, query),
                     &recipe[1..],
                     level,
                     scope_ids,
@@ -370,10 +695,101 @@ impl<'a> RecipeNodeBuilder<'a> {
                     path,
                 );
             }
-            Axis::Hierarchy => {
-                self.render_scope_in_tree(items, &recipe[1..], level, scope_ids, ghost_ids, path);
+        }
+    }
+
+    /// Applies the options of `bucketing` to the group node at `group_index`,
+    /// once everything under it has been rendered.
+    fn finish_group(&mut self, group_index: usize, bucketing: &Bucketing) {
+        if !bucketing.show_empty {
+            self.remove_empty_group(group_index);
+        }
+        if bucketing.collapse {
+            self.collapse_group(group_index);
+        }
+    }
+
+    /// Drops the group node at `group_index` if nothing was rendered under it,
+    /// which happens when a later `Filter` axis removes all of its items.
+    fn remove_empty_group(&mut self, group_index: usize) {
+        if self.nodes.len() == group_index + 1 {
+            self.nodes.pop();
+        }
+    }
+
+    /// Merges the group node at `group_index` into the node beneath it when
+    /// that is its only child and is a group too, so that a chain of single
+    /// buckets reads as one row, e.g. "Epic A › Active".  The merged row
+    /// keeps the inner group's id and option.
+    fn collapse_group(&mut self, group_index: usize) {
+        let Some(group) = self.nodes.get(group_index) else {
+            return;
+        };
+        let child_level = group.level + 1;
+        let children = &self.nodes[group_index + 1..];
+        let only_child_is_group = matches!(
+            children.first(),
+            Some(Node {
+                data: NodeData::Group { .. },
+                ..
+            })
+        ) && children
+            .iter()
+            .filter(|node| node.level == child_level)
+            .count()
+            == 1;
+        if !only_child_is_group {
+            return;
+        }
+
+        let outer = self.nodes.remove(group_index);
+        if let (
+            NodeData::Group {
+                name: outer_name, ..
+            },
+            NodeData::Group { name, .. },
+        ) = (&outer.data, &mut self.nodes[group_index].data)
+        {
+            *name = format!("{outer_name} › {name}");
+        }
+        for node in &mut self.nodes[group_index..] {
+            node.level -= 1;
+        }
+    }
+
+    fn filter_items(&self, items: Vec<WorkItemId>, query: &Query) -> Vec<WorkItemId> {
+        let context = self.query_context();
+        items
+            .into_iter()
+            .filter(|id| {
+                self.item(id)
+                    .is_some_and(|item| query.matches(item, &context))
+            })
+            .collect()
+    }
+
+    /// Buckets `items` by the field of `bucketing`, adding an empty bucket
+    /// for each of the field's options that no item has if it asks for them.
+    fn bucket_items(&self, items: Vec<WorkItemId>, bucketing: &Bucketing) -> Vec<Bucket> {
+        let mut buckets = self.bucket_by_field(items, &bucketing.field);
+        if !bucketing.show_empty {
+            return buckets;
+        }
+
+        let keys: HashSet<String> = buckets.iter().map(|bucket| bucket.key.clone()).collect();
+        for value in self.declared_field_values(&bucketing.field) {
+            if !keys.contains(&value.key) {
+                buckets.push(Bucket {
+                    key: value.key,
+                    label: value.label,
+                    sort_value: value.sort_value,
+                    field_option_id: value.field_option_id,
+                    items: Vec::new(),
+                });
             }
         }
+        sort_buckets(&mut buckets);
+        buckets
     }
 
     fn bucket_by_field(&self, items: Vec<WorkItemId>, field: &PivotField) -> Vec<Bucket> {
@@ -400,11 +816,7 @@ impl<'a> RecipeNodeBuilder<'a> {
             }
         }
 
-        buckets.sort_by(|a, b| {
-            a.sort_value
-                .cmp(&b.sort_value)
-                .then_with(|| a.label.cmp(&b.label))
-        });
+        sort_buckets(&mut buckets);
         buckets
     }
 
@@ -429,28 +841,58 @@ impl<'a> RecipeNodeBuilder<'a> {
         index
     }
 
-    fn sort_items(&self, mut items: Vec<WorkItemId>, field: &PivotField) -> Vec<WorkItemId> {
-        items.sort_by(|a, b| self.compare_optional_field_values(a, b, field));
-        items
+    /// Orders items by the keys of the enclosing `Sort` axis, if any, falling
+    /// back to title.  Items missing a key's value sort after those that have
+    /// one, whichever the direction.
+    fn order_items(&self, items: &mut [WorkItemId]) {
+        items.sort_by(|a, b| {
+            self.sort_keys
+                .iter()
+                .map(|key| self.compare_sort_values(a, b, key))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| self.item_title(a).cmp(self.item_title(b)))
+        });
     }
 
-    fn compare_optional_field_values(
+    fn compare_sort_values(
         &self,
         left: &WorkItemId,
         right: &WorkItemId,
-        field: &PivotField,
+        key: &SortKey,
     ) -> Ordering {
         match (
-            self.field_values(left, field).into_iter().next(),
-            self.field_values(right, field).into_iter().next(),
+            self.sort_value(left, &key.field),
+            self.sort_value(right, &key.field),
         ) {
-            (Some(left), Some(right)) => left.sort_value.cmp(&right.sort_value),
+            (Some(left), Some(right)) if key.descending => right.cmp(&left),
+            (Some(left), Some(right)) => left.cmp(&right),
             (Some(_), None) => Ordering::Less,
             (None, Some(_)) => Ordering::Greater,
             (None, None) => Ordering::Equal,
         }
     }
 
+    fn sort_value(&self, id: &WorkItemId, field: &SortField) -> Option<SortValue> {
+        match field {
+            SortField::Field(field) => self
+                .field_values(id, field)
+                .into_iter()
+                .next()
+                .map(|value| value.sort_value),
+            SortField::Attribute(attribute) => {
+                let item = self.item(id)?;
+                match attribute {
+                    ItemAttribute::Title => Some(SortValue::Text(item.title.clone())),
+                    ItemAttribute::UpdatedAt => (!item.updated_at.is_empty())
+                        .then(|| SortValue::Text(item.updated_at.clone())),
+                    ItemAttribute::Number => item
+                        .number()
+                        .map(|number| SortValue::Index(number as usize)),
+                }
+            }
+        }
+    }
+
     fn roots_in_scope(
         &self,
         items: &[WorkItemId],
@@ -490,76 +932,127 @@ impl<'a> RecipeNodeBuilder<'a> {
         };
 
         match field {
-            PivotField::Status => {
-                self.option_field_values(item.project_item.status.as_ref(), &self.fields.status)
-            }
-            PivotField::Blocked => self.option_field_values(
-                item.project_item.blocked.expect_loaded().as_ref(),
-                &self.fields.blocked,
-            ),
-            PivotField::Epic => {
-                self.option_field_values(item.project_item.epic.as_ref(), &self.fields.epic)
-            }
-            PivotField::Iteration => self.option_field_values(
-                item.project_item.iteration.expect_loaded().as_ref(),
-                &self.fields.iteration,
-            ),
-            PivotField::Kind => self.option_field_values(
-                item.project_item.kind.expect_loaded().as_ref(),
-                &self.fields.kind,
-            ),
-            PivotField::Workstream => self.option_field_values(
-                item.project_item.workstream.expect_loaded().as_ref(),
-                &self.fields.workstream,
-            ),
-            PivotField::Estimate => {
-                self.option_field_values(item.project_item.estimate.as_ref(), &self.fields.estimate)
-            }
-            PivotField::Priority => {
-                self.option_field_values(item.project_item.priority.as_ref(), &self.fields.priority)
-            }
             PivotField::Assignee => self.assignee_field_values(item),
-            PivotField::Repository => item
-                .repo_name_with_owner
-                .as_ref()
-                .map(|repo| vec![Self::text_field_value(repo.clone())])
+            PivotField::Status
+            | PivotField::Blocked
+            | PivotField::Epic
+            | PivotField::Iteration
+            | PivotField::Kind
+            | PivotField::Workstream
+            | PivotField::Estimate
+            | PivotField::Priority
+            | PivotField::Repository
+            | PivotField::IssueType
+            | PivotField::Type
+            | PivotField::State => item_field_values(item, self.fields, field)
+                .into_iter()
+                .map(FieldValue::from)
+                .collect(),
+            PivotField::UpdatedWeek => timestamp_date(&item.updated_at)
+                .map(|date| vec![Self::week_field_value(date)])
+                .unwrap_or_default(),
+            PivotField::UpdatedMonth => timestamp_date(&item.updated_at)
+                .map(|date| vec![Self::month_field_value(date)])
+                .unwrap_or_default(),
+            PivotField::CreatedMonth => timestamp_date(&item.created_at)
+                .map(|date| vec![Self::month_field_value(date)])
+                .unwrap_or_default(),
+            PivotField::UpdatedAge => timestamp_date(&item.updated_at)
+                .map(|date| vec![self.age_field_value(date)])
+                .unwrap_or_default(),
+            PivotField::IterationStart => self.iteration_start_field_values(item),
+            PivotField::Parent => self
+                .ancestors(item)
+                .first()
+                .map(|parent| vec![Self::item_field_value(parent)])
+                .unwrap_or_default(),
+            PivotField::Root => {
+                let ancestors = self.ancestors(item);
+                vec![Self::item_field_value(ancestors.last().unwrap_or(&item))]
+            }
+            PivotField::Ancestor(field) => self
+                .ancestors(item)
+                .into_iter()
+                .map(|ancestor| self.field_values(&ancestor.id, field))
+                .find(|values| !values.is_empty())
                 .unwrap_or_default(),
-            PivotField::IssueType => match &item.data {
-                WorkItemData::Issue(issue) => issue
-                    .issue_type
-                    .expect_loaded()
-                    .as_ref()
-                    .map(|issue_type| vec![Self::text_field_value(issue_type.clone())])
-                    .unwrap_or_default(),
-                WorkItemData::DraftIssue | WorkItemData::PullRequest(_) => Vec::new(),
-            },
-            PivotField::Type => vec![Self::type_field_value(&item.data)],
-            PivotField::State => Self::state_field_values(&item.data),
         }
     }
 
-    fn option_field_values<T>(
-        &self,
-        id: Option<&FieldOptionId>,
-        field: &Field<T>,
-    ) -> Vec<FieldValue> {
-        id.map(|id| {
-            vec![FieldValue {
-                key: id.0.clone(),
-                label: field.option_name(Some(id)).unwrap_or(&id.0).to_owned(),
-                sort_value: SortValue::Index(field.option_index(Some(id))),
-                field_option_id: Some(id.clone()),
-            }]
-        })
-        .unwrap_or_default()
+    /// Every value `field` can have, in the order they're declared: the
+    /// options of single select and iteration fields.  Other fields have no
+    /// declared values.
+    fn declared_field_values(&self, field: &PivotField) -> Vec<FieldValue> {
+        match field {
+            PivotField::Status => Self::option_values(&self.fields.status),
+            PivotField::Blocked => Self::option_values(&self.fields.blocked),
+            PivotField::Epic => Self::option_values(&self.fields.epic),
+            PivotField::Iteration => Self::option_values(&self.fields.iteration),
+            PivotField::Kind => Self::option_values(&self.fields.kind),
+            PivotField::Workstream => Self::option_values(&self.fields.workstream),
+            PivotField::Estimate => Self::option_values(&self.fields.estimate),
+            PivotField::Priority => Self::option_values(&self.fields.priority),
+            PivotField::Ancestor(field) => self.declared_field_values(field),
+            PivotField::Assignee
+            | PivotField::Repository
+            | PivotField::IssueType
+            | PivotField::Type
+            | PivotField::State
+            | PivotField::UpdatedWeek
+            | PivotField::UpdatedMonth
+            | PivotField::CreatedMonth
+            | PivotField::UpdatedAge
+            | PivotField::IterationStart
+            | PivotField::Parent
+            | PivotField::Root => Vec::new(),
+        }
+    }
+
+    fn option_values<T>(field: &Field<T>) -> Vec<FieldValue> {
+        field
+            .options
+            .iter()
+            .enumerate()
+            .map(|(index, option)| FieldValue {
+                key: option.id.0.clone(),
+                label: option.value.clone(),
+                sort_value: SortValue::Index(index),
+                field_option_id: Some(option.id.clone()),
+            })
+            .collect()
+    }
+
+    /// The loaded ancestors of `item`, found by following `get_parent`,
+    /// nearest first.
+    fn ancestors(&self, item: &WorkItem) -> Vec<&WorkItem> {
+        let mut ancestors: Vec<&WorkItem> = Vec::new();
+        let mut current = item;
+        while let Some(parent) = current.get_parent().and_then(|id| self.item(id)) {
+            // Guard against cycles in inconsistent data.
+            if parent.id == item.id || ancestors.iter().any(|a| a.id == parent.id) {
+                break;
+            }
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+    }
+
+    fn item_field_value(item: &WorkItem) -> FieldValue {
+        FieldValue {
+            key: item.id.0.clone(),
+            label: item.title.clone(),
+            sort_value: SortValue::Text(item.title.clone()),
+            field_option_id: None,
+        }
     }
 
     fn assignee_field_values(&self, item: &WorkItem) -> Vec<FieldValue> {
-        let assignees = match &item.data {
-            WorkItemData::Issue(Issue { assignees, .. }) => assignees.as_slice(),
-            WorkItemData::PullRequest(PullRequest { assignees, .. }) => assignees.as_slice(),
-            WorkItemData::DraftIssue => &[],
-        };
+        let mut assignees: Vec<&str> = item_field_values(item, self.fields, &PivotField::Assignee)
+            .into_iter()
+            .map(|value| value.name)
+            .collect();
+        assignees.sort();
 
         if assignees.is_empty() {
             return Vec::new();
@@ -567,8 +1060,6 @@ impl<'a> RecipeNodeBuilder<'a> {
 
         match self.pivot_config.multi_value_strategy {
             MultiValueStrategy::Combined => {
-                let mut assignees = assignees.to_vec();
-                assignees.sort();
                 let key = assignees.join("+");
                 let label = assignees
                     .iter()
@@ -582,66 +1073,69 @@ impl<'a> RecipeNodeBuilder<'a> {
                     field_option_id: None,
                 }]
             }
-            MultiValueStrategy::Explode => {
-                let mut assignees = assignees.to_vec();
-                assignees.sort();
-                assignees
-                    .into_iter()
-                    .map(|assignee| {
-                        let label = format!("@{assignee}");
-                        FieldValue {
-                            key: assignee,
-                            label: label.clone(),
-                            sort_value: SortValue::Text(label),
-                            field_option_id: None,
-                        }
-                    })
-                    .collect()
-            }
+            MultiValueStrategy::Explode => assignees
+                .into_iter()
+                .map(|assignee| {
+                    let label = format!("@{assignee}");
+                    FieldValue {
+                        key: assignee.to_owned(),
+                        label: label.clone(),
+                        sort_value: SortValue::Text(label),
+                        field_option_id: None,
+                    }
+                })
+                .collect(),
         }
     }
 
-    fn type_field_value(data: &WorkItemData) -> FieldValue {
-        let value = match data {
-            WorkItemData::Issue(_) => ("issue", 0),
-            WorkItemData::PullRequest(_) => ("pullRequest", 1),
-            WorkItemData::DraftIssue => ("draftIssue", 2),
-        };
+    fn week_field_value(date: Date) -> FieldValue {
+        let monday = date - Duration::days(date.weekday().number_days_from_monday().into());
         FieldValue {
-            key: value.0.to_owned(),
-            label: value.0.to_owned(),
-            sort_value: SortValue::Index(value.1),
+            key: monday.to_string(),
+            label: format!("Week of {monday}"),
+            sort_value: SortValue::Text(monday.to_string()),
             field_option_id: None,
         }
     }
 
-    fn state_field_values(data: &WorkItemData) -> Vec<FieldValue> {
-        let value = match data {
-            WorkItemData::Issue(Issue { state, .. }) => Some(match state.expect_loaded() {
-                IssueState::OPEN => ("OPEN".to_owned(), 0),
-                IssueState::CLOSED => ("CLOSED".to_owned(), 1),
-                IssueState::Other(value) => (value.clone(), 99),
-            }),
-            WorkItemData::PullRequest(PullRequest { state, .. }) => {
-                Some(match state.expect_loaded() {
-                    PullRequestState::OPEN => ("OPEN".to_owned(), 0),
-                    PullRequestState::CLOSED => ("CLOSED".to_owned(), 1),
-                    PullRequestState::MERGED => ("MERGED".to_owned(), 2),
-                    PullRequestState::Other(value) => (value.clone(), 99),
-                })
-            }
-            WorkItemData::DraftIssue => None,
-        };
+    fn month_field_value(date: Date) -> FieldValue {
+        let key = format!("{}-{:02}", date.year(), u8::from(date.month()));
+        FieldValue {
+            label: format!("{} {}", date.month(), date.year()),
+            sort_value: SortValue::Text(key.clone()),
+            key,
+            field_option_id: None,
+        }
+    }
 
-        value
-            .map(|(label, sort_index)| {
-                vec![FieldValue {
-                    key: label.clone(),
-                    label,
-                    sort_value: SortValue::Index(sort_index),
-                    field_option_id: None,
-                }]
-            })
+    fn age_field_value(&self, date: Date) -> FieldValue {
+        // Upper bounds, in days, of each age bucket.
+        const AGES: [(i64, &str); 4] =
+            [(7, "<1w"), (28, "1-4w"), (91, "1-3mo"), (i64::MAX, ">3mo")];
+
+        let days = (self.today - date).whole_days();
+        let index = AGES
+            .iter()
+            .position(|(limit, _)| days < *limit)
+            .unwrap_or(AGES.len() - 1);
+        FieldValue {
+            key: AGES[index].1.to_owned(),
+            label: AGES[index].1.to_owned(),
+            sort_value: SortValue::Index(index),
+            field_option_id: None,
+        }
+    }
+
+    fn iteration_start_field_values(&self, item: &WorkItem) -> Vec<FieldValue> {
+        let Some(id) = item.project_item.iteration.expect_loaded() else {
+            return Vec::new();
+        };
+        self.fields
+            .iteration
+            .options
+            .iter()
+            .find(|option| option.id == *id)
+            .map(|option| vec![Self::text_field_value(option.data.start_date.clone())])
             .unwrap_or_default()
     }
 
@@ -674,6 +1168,7 @@ impl<'a> RecipeNodeBuilder<'a> {
             has_children,
             is_modified: self.original_work_items.contains_key(id),
             is_ghost,
+            aggregates: None,
         });
     }
 
@@ -714,8 +1209,8 @@ impl<'a> RecipeNodeBuilder<'a> {
         }
     }
 
-    fn field_key(field: &PivotField) -> &'static str {
-        match field {
+    fn field_key(field: &PivotField) -> String {
+        let key = match field {
             PivotField::Status => "status",
             PivotField::Blocked => "blocked",
             PivotField::Epic => "epic",
@@ -729,7 +1224,16 @@ impl<'a> RecipeNodeBuilder<'a> {
             PivotField::IssueType => "issue_type",
             PivotField::Type => "type",
             PivotField::State => "state",
-        }
+            PivotField::UpdatedWeek => "updated_week",
+            PivotField::UpdatedMonth => "updated_month",
+            PivotField::CreatedMonth => "created_month",
+            PivotField::UpdatedAge => "updated_age",
+            PivotField::IterationStart => "iteration_start",
+            PivotField::Parent => "parent",
+            PivotField::Root => "root",
+            PivotField::Ancestor(field) => return format!("ancestor_{}", Self::field_key(field)),
+        };
+        key.to_owned()
     }
 
     fn should_include(&self, work_item_id: &WorkItemId) -> bool {
@@ -746,7 +1250,7 @@ impl<'a> RecipeNodeBuilder<'a> {
                     }
                 }
             }
-            self.filters.should_include(work_item)
+            self.passes_filters(work_item)
         } else {
             false
         }
@@ -760,24 +1264,87 @@ impl<'a> RecipeNodeBuilder<'a> {
     /// under its own (possibly excluded) field value.
     fn is_genuine_match(&self, work_item_id: &WorkItemId) -> bool {
         self.item(work_item_id)
-            .is_some_and(|work_item| self.filters.should_include(work_item))
+            .is_some_and(|work_item| self.passes_filters(work_item))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::collections::{HashMap, HashSet};
+    fn passes_filters(&self, work_item: &WorkItem) -> bool {
+        self.filters.should_include(work_item)
+            && self.filters.matches_query(work_item, &self.query_context())
+    }
 
-    use github_graphql::{
-        data::{
-            Issue, IssueState, ProjectItem, PullRequest, PullRequestState, UpdateType, WorkItem,
-            WorkItemData, WorkItemId, test_helpers::TestData,
-        },
-        pivot::{Axis, MultiValueStrategy, PivotConfig, PivotField, parse_recipe},
-    };
-    use serde_json::Value;
+    fn query_context(&self) -> QueryContext<'a> {
+        QueryContext {
+            fields: self.fields,
+            viewer: self.viewer,
+        }
+    }
+}
 
-    use crate::Filters;
+/// The date of an ISO 8601 timestamp such as `2025-03-01T12:00:00Z`, or
+/// `None` if it doesn't start with a valid `YYYY-MM-DD` date.
+fn timestamp_date(timestamp: &str) -> Option<Date> {
+    let mut parts = timestamp.get(..10)?.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month: u8 = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Date::from_calendar_date(year, Month::try_from(month).ok()?, day).ok()
+}
+
+fn sort_buckets(buckets: &mut [Bucket]) {
+    buckets.sort_by(|a, b| {
+        a.sort_value
+            .cmp(&b.sort_value)
+            .then_with(|| a.label.cmp(&b.label))
+    });
+}
+
+fn axis_reads_ancestors(axis: &Axis) -> bool {
+    match axis {
+        Axis::Pivot(bucketing) | Axis::Group(bucketing) => bucketing.field.reads_ancestors(),
+        Axis::Sort(keys) => keys
+            .iter()
+            .any(|key| matches!(&key.field, SortField::Field(field) if field.reads_ancestors())),
+        Axis::Hierarchy(_) | Axis::Filter(_) => false,
+    }
+}
+
+/// Maps an Estimate option to a number of days.  Options starting with a
+/// number (e.g. "3" or "0.5 days") use that number; the named sizes used by
+/// the project map to rough equivalents.
+fn estimate_days(name: &str) -> Option<f64> {
+    let name = name.trim();
+    let number: String = name
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    if let Ok(days) = number.parse() {
+        return Some(days);
+    }
+
+    match name.to_ascii_lowercase().as_str() {
+        "hours" => Some(0.5),
+        "days" => Some(2.0),
+        "week" => Some(5.0),
+        "sprint" => Some(10.0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use github_graphql::{
+        data::{
+            Issue, IssueState, ProjectItem, PullRequest, PullRequestState, UpdateType, WorkItem,
+            WorkItemData, WorkItemId, test_helpers::TestData,
+        },
+        pivot::{Axis, MultiValueStrategy, PivotConfig, PivotField, parse_recipe},
+        query::parse_query,
+    };
+    use serde_json::Value;
+
+    use crate::Filters;
 
     use super::*;
 
@@ -803,6 +1370,7 @@ mod tests {
                     recipe: parse_recipe(preset).unwrap(),
                     multi_value_strategy: MultiValueStrategy::Combined,
                     show_ghost_ancestors: true,
+                    aggregates: Vec::new(),
                 };
                 let nodes = build_recipe_nodes(&fixture.fields, &fixture.work_items, &config);
 
@@ -967,165 +1535,748 @@ total=14 unique_ids=14
 1 group path/state=(none)/epic=(none) (none)
 2 item 6 ghost=false children=false
 
-== Pivot(Status) ==
-total=11 unique_ids=11
-0 group path/status=id(Active) Active
+== Pivot(Status) ==
+total=11 unique_ids=11
+0 group path/status=id(Active) Active
+1 item 1 ghost=false children=false
+1 item 5 ghost=false children=false
+0 group path/status=id(Open) Open
+1 item 2 ghost=false children=false
+0 group path/status=id(Closed) Closed
+1 item 3 ghost=false children=false
+0 group path/status=id(Planning) Planning
+1 item 4 ghost=false children=false
+0 group path/status=(none) (none)
+1 item 6 ghost=false children=false
+
+== Pivot(Status) → Group(Workstream) ==
+total=17 unique_ids=17
+0 group path/status=id(Active) Active
+1 group path/status=id(Active)/workstream=id(WS1) WS1
+2 item 1 ghost=false children=false
+1 group path/status=id(Active)/workstream=id(WS2) WS2
+2 item 5 ghost=false children=false
+0 group path/status=id(Open) Open
+1 group path/status=id(Open)/workstream=id(WS1) WS1
+2 item 2 ghost=false children=false
+0 group path/status=id(Closed) Closed
+1 group path/status=id(Closed)/workstream=id(WS2) WS2
+2 item 3 ghost=false children=false
+0 group path/status=id(Planning) Planning
+1 group path/status=id(Planning)/workstream=(none) (none)
+2 item 4 ghost=false children=false
+0 group path/status=(none) (none)
+1 group path/status=(none)/workstream=(none) (none)
+2 item 6 ghost=false children=false
+
+== Pivot(Type) → Group(Status) ==
+total=15 unique_ids=15
+0 group path/type=issue issue
+1 group path/type=issue/status=id(Active) Active
+2 item 1 ghost=false children=false
+1 group path/type=issue/status=id(Open) Open
+2 item 2 ghost=false children=false
+1 group path/type=issue/status=id(Closed) Closed
+2 item 3 ghost=false children=false
+1 group path/type=issue/status=id(Planning) Planning
+2 item 4 ghost=false children=false
+0 group path/type=pullRequest pullRequest
+1 group path/type=pullRequest/status=id(Active) Active
+2 item 5 ghost=false children=false
+0 group path/type=draftIssue draftIssue
+1 group path/type=draftIssue/status=(none) (none)
+2 item 6 ghost=false children=false
+
+== Sort(Epic) ==
+total=6 unique_ids=6
+0 item 1 ghost=false children=false
+0 item 2 ghost=false children=false
+0 item 3 ghost=false children=false
+0 item 5 ghost=false children=false
+0 item 4 ghost=false children=false
+0 item 6 ghost=false children=false
+
+== Sort(Priority) → Hierarchy ==
+total=6 unique_ids=6
+0 item 2 ghost=false children=true
+1 item 1 ghost=false children=false
+0 item 5 ghost=false children=false
+0 item 4 ghost=false children=true
+1 item 3 ghost=false children=false
+0 item 6 ghost=false children=false"#
+        );
+    }
+
+    #[test]
+    fn test_recipe_builder_ghost_rows_for_mixed_epic_children() {
+        let mut data = TestData::default();
+        let child_a = data.build().epic("EpicA").add();
+        let child_b = data.build().epic("EpicB").add();
+        let parent = data
+            .build()
+            .epic("EpicA")
+            .sub_issues(&[&child_a, &child_b])
+            .add();
+        set_title(&mut data, &child_a, "Alpha child");
+        set_title(&mut data, &child_b, "Beta child");
+        set_title(&mut data, &parent, "Parent");
+
+        let config = PivotConfig {
+            recipe: vec![Axis::Pivot(PivotField::Epic.into()), Axis::Hierarchy(None)],
+            multi_value_strategy: MultiValueStrategy::Combined,
+            show_ghost_ancestors: true,
+            aggregates: Vec::new(),
+        };
+
+        assert_eq!(
+            render_recipe_nodes(&data.fields, &data.work_items, &config),
+            r#"0 group path/epic=id(EpicA) EpicA
+1 item 3 ghost=false children=true
+2 item 1 ghost=false children=false
+0 group path/epic=id(EpicB) EpicB
+1 item 3 ghost=true children=true
+2 item 2 ghost=false children=false"#
+        );
+    }
+
+    #[test]
+    fn test_recipe_builder_hierarchy_then_pivot_recurses_every_level() {
+        // Reproduces the issue example: pivot on Epic follows the parent ↔
+        // sub-issue tree, bucketing children by Epic at every level (and the
+        // roots themselves).
+        //
+        //   IssueA (EpicA)
+        //   ├─ IssueA-A (EpicA)
+        //   │  ├─ IssueA-A-A (EpicA)
+        //   │  └─ IssueA-A-B (EpicA)
+        //   ├─ IssueA-B (EpicB)
+        //   └─ IssueA-C (EpicA)
+        let mut data = TestData::default();
+        let issue_a_a_a = data.build().epic("EpicA").add();
+        let issue_a_a_b = data.build().epic("EpicA").add();
+        let issue_a_a = data
+            .build()
+            .epic("EpicA")
+            .sub_issues(&[&issue_a_a_a, &issue_a_a_b])
+            .add();
+        let issue_a_b = data.build().epic("EpicB").add();
+        let issue_a_c = data.build().epic("EpicA").add();
+        let issue_a = data
+            .build()
+            .epic("EpicA")
+            .sub_issues(&[&issue_a_a, &issue_a_b, &issue_a_c])
+            .add();
+
+        set_title(&mut data, &issue_a, "IssueA");
+        set_title(&mut data, &issue_a_a, "IssueA-A");
+        set_title(&mut data, &issue_a_b, "IssueA-B");
+        set_title(&mut data, &issue_a_c, "IssueA-C");
+        set_title(&mut data, &issue_a_a_a, "IssueA-A-A");
+        set_title(&mut data, &issue_a_a_b, "IssueA-A-B");
+
+        let config = PivotConfig {
+            recipe: parse_recipe("Hierarchy → Pivot(Epic)").unwrap(),
+            multi_value_strategy: MultiValueStrategy::Combined,
+            show_ghost_ancestors: true,
+            aggregates: Vec::new(),
+        };
+
+        assert_eq!(
+            render_recipe_nodes(&data.fields, &data.work_items, &config),
+            r#"0 group path/epic=id(EpicA) EpicA
+1 item 6 ghost=false children=true
+2 group path/epic=id(EpicA)/6/epic=id(EpicA) EpicA
+3 item 3 ghost=false children=true
+4 group path/epic=id(EpicA)/6/epic=id(EpicA)/3/epic=id(EpicA) EpicA
+5 item 1 ghost=false children=false
+5 item 2 ghost=false children=false
+3 item 5 ghost=false children=false
+2 group path/epic=id(EpicA)/6/epic=id(EpicB) EpicB
+3 item 4 ghost=false children=false"#
+        );
+    }
+
+    #[test]
+    fn test_recipe_builder_filter_axis() {
+        let mut data = TestData::default();
+        let active = data.build().epic("EpicA").status("Active").add();
+        let closed = data.build().epic("EpicA").status("Closed").add();
+        let closed_b = data.build().epic("EpicB").status("Closed").add();
+        let parent = data
+            .build()
+            .epic("EpicA")
+            .status("Active")
+            .sub_issues(&[&active, &closed])
+            .add();
+
+        set_title(&mut data, &active, "Active");
+        set_title(&mut data, &closed, "Closed");
+        set_title(&mut data, &closed_b, "ClosedB");
+        set_title(&mut data, &parent, "Parent");
+
+        let config = PivotConfig {
+            recipe: parse_recipe("Pivot(Epic) → Filter(Status != Closed) → Hierarchy").unwrap(),
+            multi_value_strategy: MultiValueStrategy::Combined,
+            show_ghost_ancestors: true,
+            aggregates: Vec::new(),
+        };
+
+        assert_eq!(
+            render_recipe_nodes(&data.fields, &data.work_items, &config),
+            r#"0 group path/epic=id(EpicA) EpicA
+1 item 4 ghost=false children=true
+2 item 1 ghost=false children=false"#
+        );
+    }
+
+    #[test]
+    fn test_recipe_builder_show_empty_buckets() {
+        let mut data = TestData::default();
+        let planned = data.build().iteration("S2").status("Active").add();
+        let unplanned = data.build().status("Closed").add();
+
+        let config = PivotConfig {
+            recipe: parse_recipe("Pivot(Iteration, empty) → Filter(Status != Closed)").unwrap(),
+            multi_value_strategy: MultiValueStrategy::Combined,
+            show_ghost_ancestors: true,
+            aggregates: Vec::new(),
+        };
+
+        // Every iteration gets a bucket, in the order they're declared, and
+        // buckets left empty by a later filter stay.
+        assert_eq!(
+            render_recipe_nodes(&data.fields, &data.work_items, &config),
+            format!(
+                r#"0 group path/iteration=id(S1) S1
+0 group path/iteration=id(S2) S2
+1 item {} ghost=false children=false
+0 group path/iteration=(none) (none)"#,
+                planned.0
+            )
+        );
+
+        let nodes = build_recipe_nodes(&data.fields, &data.work_items, &config);
+        assert!(!nodes[0].has_children);
+        assert!(!nodes.iter().any(|node| node.data
+            == NodeData::WorkItem {
+                work_item_id: unplanned.clone()
+            }));
+    }
+
+    #[test]
+    fn test_recipe_builder_collapse_single_bucket_chains() {
+        let mut data = TestData::default();
+        let a = data.build().epic("EpicA").status("Active").add();
+        let b_active = data.build().epic("EpicB").status("Active").add();
+        let b_closed = data.build().epic("EpicB").status("Closed").add();
+
+        let config = PivotConfig {
+            recipe: parse_recipe("Pivot(Epic, collapse) → Group(Status)").unwrap(),
+            multi_value_strategy: MultiValueStrategy::Combined,
+            show_ghost_ancestors: true,
+            aggregates: Vec::new(),
+        };
+
+        assert_eq!(
+            render_recipe_nodes(&data.fields, &data.work_items, &config),
+            format!(
+                r#"0 group path/epic=id(EpicA)/status=id(Active) EpicA › Active
+1 item {} ghost=false children=false
+0 group path/epic=id(EpicB) EpicB
+1 group path/epic=id(EpicB)/status=id(Active) Active
+2 item {} ghost=false children=false
+1 group path/epic=id(EpicB)/status=id(Closed) Closed
+2 item {} ghost=false children=false"#,
+                a.0, b_active.0, b_closed.0
+            )
+        );
+    }
+
+    #[test]
+    fn test_recipe_builder_hierarchy_options() {
+        let mut data = TestData::default();
+        let child = data.build().issue().status("Active").add();
+        let parent = data.build().sub_issues(&[&child]).add();
+        let grandparent = data.build().sub_issues(&[&parent]).add();
+
+        let render = |recipe: &str, show_ghost_ancestors: bool| {
+            let config = PivotConfig {
+                recipe: parse_recipe(recipe).unwrap(),
+                multi_value_strategy: MultiValueStrategy::Combined,
+                show_ghost_ancestors,
+                aggregates: Vec::new(),
+            };
+            render_recipe_nodes(&data.fields, &data.work_items, &config)
+        };
+
+        assert_eq!(
+            render("Hierarchy(depth=2)", true),
+            format!(
+                r#"0 item {} ghost=false children=true
+1 item {} ghost=false children=false"#,
+                grandparent.0, parent.0
+            )
+        );
+
+        let ghosts = format!(
+            r#"0 item {} ghost=true children=true
+1 item {} ghost=true children=true
+2 item {} ghost=false children=false"#,
+            grandparent.0, parent.0, child.0
+        );
+        let no_ghosts = format!("0 item {} ghost=false children=false", child.0);
+        assert_eq!(render("Filter(status:Active) → Hierarchy", true), ghosts);
+        assert_eq!(
+            render("Filter(status:Active) → Hierarchy(noghosts)", true),
+            no_ghosts
+        );
+        assert_eq!(
+            render("Filter(status:Active) → Hierarchy(ghosts)", false),
+            ghosts
+        );
+    }
+
+    #[test]
+    fn test_recipe_builder_date_fields() {
+        let mut data = TestData::default();
+        let recent = data.build().iteration("S1").add();
+        let weeks = data.build().iteration("S2").add();
+        let old = data.build().add();
+        let undated = data.build().add();
+
+        set_title(&mut data, &recent, "Recent");
+        set_title(&mut data, &weeks, "Weeks");
+        set_title(&mut data, &old, "Old");
+        set_title(&mut data, &undated, "Undated");
+        set_updated_at(&mut data, &recent, "2025-03-18T09:00:00Z");
+        set_updated_at(&mut data, &weeks, "2025-03-03T17:30:00Z");
+        set_updated_at(&mut data, &old, "2024-11-01T00:00:00Z");
+        set_created_at(&mut data, &recent, "2025-01-15T00:00:00Z");
+        set_created_at(&mut data, &weeks, "2025-01-02T00:00:00Z");
+        set_created_at(&mut data, &old, "2024-11-01T00:00:00Z");
+
+        let render = |recipe: &str| {
+            let config = PivotConfig {
+                recipe: parse_recipe(recipe).unwrap(),
+                multi_value_strategy: MultiValueStrategy::Combined,
+                show_ghost_ancestors: true,
+                aggregates: Vec::new(),
+            };
+            let filters = Filters::default();
+            let original_work_items = HashMap::new();
+            let nodes = RecipeNodeBuilder::new(
+                &data.fields,
+                &data.work_items,
+                &filters,
+                &original_work_items,
+                &config,
+            )
+            .with_today(Date::from_calendar_date(2025, Month::March, 20).unwrap())
+            .build();
+            format_nodes_string(&nodes)
+        };
+
+        assert_eq!(
+            render("Pivot(UpdatedAge)"),
+            r#"0 group path/updated_age=<1w <1w
+1 item 1 ghost=false children=false
+0 group path/updated_age=1-4w 1-4w
+1 item 2 ghost=false children=false
+0 group path/updated_age=>3mo >3mo
+1 item 3 ghost=false children=false
+0 group path/updated_age=(none) (none)
+1 item 4 ghost=false children=false"#
+        );
+
+        assert_eq!(
+            render("Pivot(UpdatedWeek)"),
+            r#"0 group path/updated_week=(none) (none)
+1 item 4 ghost=false children=false
+0 group path/updated_week=2024-10-28 Week of 2024-10-28
+1 item 3 ghost=false children=false
+0 group path/updated_week=2025-03-03 Week of 2025-03-03
+1 item 2 ghost=false children=false
+0 group path/updated_week=2025-03-17 Week of 2025-03-17
+1 item 1 ghost=false children=false"#
+        );
+
+        assert_eq!(
+            render("Pivot(CreatedMonth)"),
+            r#"0 group path/created_month=(none) (none)
+1 item 4 ghost=false children=false
+0 group path/created_month=2024-11 November 2024
+1 item 3 ghost=false children=false
+0 group path/created_month=2025-01 January 2025
+1 item 1 ghost=false children=false
+1 item 2 ghost=false children=false"#
+        );
+
+        // Both test iterations start on the same day.
+        assert_eq!(
+            render("Pivot(IterationStart)"),
+            r#"0 group path/iteration_start=(none) (none)
+1 item 3 ghost=false children=false
+1 item 4 ghost=false children=false
+0 group path/iteration_start=2025-01-01 2025-01-01
+1 item 1 ghost=false children=false
+1 item 2 ghost=false children=false"#
+        );
+    }
+
+    #[test]
+    fn test_recipe_builder_ancestor_fields() {
+        let mut data = TestData::default();
+        let task = data.build().issue().add();
+        let feature = data.build().sub_issues(&[&task]).add();
+        let epic = data.build().workstream("WS1").sub_issues(&[&feature]).add();
+        let lone = data.build().workstream("WS2").issue().add();
+
+        set_title(&mut data, &task, "Task");
+        set_title(&mut data, &feature, "Feature");
+        set_title(&mut data, &epic, "Epic");
+        set_title(&mut data, &lone, "Lone");
+
+        let render = |recipe: &str| {
+            let config = PivotConfig {
+                recipe: parse_recipe(recipe).unwrap(),
+                multi_value_strategy: MultiValueStrategy::Combined,
+                show_ghost_ancestors: true,
+                aggregates: Vec::new(),
+            };
+            render_recipe_nodes(&data.fields, &data.work_items, &config)
+        };
+
+        assert_eq!(
+            render("Pivot(Parent)"),
+            r#"0 group path/parent=(none) (none)
+1 item 3 ghost=false children=false
+1 item 4 ghost=false children=false
+0 group path/parent=3 Epic
+1 item 2 ghost=false children=false
+0 group path/parent=2 Feature
+1 item 1 ghost=false children=false"#
+        );
+
+        // Items without a parent are their own root.
+        assert_eq!(
+            render("Pivot(Root)"),
+            r#"0 group path/root=3 Epic
+1 item 3 ghost=false children=false
+1 item 2 ghost=false children=false
 1 item 1 ghost=false children=false
-1 item 5 ghost=false children=false
-0 group path/status=id(Open) Open
+0 group path/root=4 Lone
+1 item 4 ghost=false children=false"#
+        );
+
+        // The nearest ancestor with a value wins; the item's own value
+        // doesn't count.
+        assert_eq!(
+            render("Group(Ancestor(Workstream))"),
+            r#"0 group path/ancestor_workstream=id(WS1) WS1
 1 item 2 ghost=false children=false
-0 group path/status=id(Closed) Closed
+1 item 1 ghost=false children=false
+0 group path/ancestor_workstream=(none) (none)
 1 item 3 ghost=false children=false
-0 group path/status=id(Planning) Planning
-1 item 4 ghost=false children=false
-0 group path/status=(none) (none)
-1 item 6 ghost=false children=false
-
-== Pivot(Status) → Group(Workstream) ==
-total=17 unique_ids=17
-0 group path/status=id(Active) Active
-1 group path/status=id(Active)/workstream=id(WS1) WS1
-2 item 1 ghost=false children=false
-1 group path/status=id(Active)/workstream=id(WS2) WS2
-2 item 5 ghost=false children=false
-0 group path/status=id(Open) Open
-1 group path/status=id(Open)/workstream=id(WS1) WS1
-2 item 2 ghost=false children=false
-0 group path/status=id(Closed) Closed
-1 group path/status=id(Closed)/workstream=id(WS2) WS2
-2 item 3 ghost=false children=false
-0 group path/status=id(Planning) Planning
-1 group path/status=id(Planning)/workstream=(none) (none)
-2 item 4 ghost=false children=false
-0 group path/status=(none) (none)
-1 group path/status=(none)/workstream=(none) (none)
-2 item 6 ghost=false children=false
+1 item 4 ghost=false children=false"#
+        );
+    }
 
-== Pivot(Type) → Group(Status) ==
-total=15 unique_ids=15
-0 group path/type=issue issue
-1 group path/type=issue/status=id(Active) Active
-2 item 1 ghost=false children=false
-1 group path/type=issue/status=id(Open) Open
-2 item 2 ghost=false children=false
-1 group path/type=issue/status=id(Closed) Closed
-2 item 3 ghost=false children=false
-1 group path/type=issue/status=id(Planning) Planning
-2 item 4 ghost=false children=false
-0 group path/type=pullRequest pullRequest
-1 group path/type=pullRequest/status=id(Active) Active
-2 item 5 ghost=false children=false
-0 group path/type=draftIssue draftIssue
-1 group path/type=draftIssue/status=(none) (none)
-2 item 6 ghost=false children=false
+    #[test]
+    fn test_recipe_builder_multi_key_sort() {
+        let mut data = TestData::default();
+        let low_old = data.build().add();
+        let high_new = data.build().add();
+        let high_old = data.build().add();
+        let unprioritized = data.build().add();
+        let low_new = data.build().add();
+
+        set_priority(&mut data, &low_old, Some("Low"));
+        set_priority(&mut data, &high_new, Some("High"));
+        set_priority(&mut data, &high_old, Some("High"));
+        set_priority(&mut data, &low_new, Some("Low"));
+
+        set_updated_at(&mut data, &low_old, "2025-01-01T00:00:00Z");
+        set_updated_at(&mut data, &high_new, "2025-03-01T00:00:00Z");
+        set_updated_at(&mut data, &high_old, "2025-02-01T00:00:00Z");
+        set_updated_at(&mut data, &unprioritized, "2025-04-01T00:00:00Z");
+        set_updated_at(&mut data, &low_new, "2025-05-01T00:00:00Z");
+
+        let render = |recipe: &str| {
+            let config = PivotConfig {
+                recipe: parse_recipe(recipe).unwrap(),
+                multi_value_strategy: MultiValueStrategy::Combined,
+                show_ghost_ancestors: true,
+                aggregates: Vec::new(),
+            };
+            render_recipe_nodes(&data.fields, &data.work_items, &config)
+        };
 
-== Sort(Epic) ==
-total=6 unique_ids=6
-0 item 1 ghost=false children=false
-0 item 2 ghost=false children=false
+        assert_eq!(
+            render("Sort(Priority desc, UpdatedAt desc)"),
+            r#"0 item 2 ghost=false children=false
 0 item 3 ghost=false children=false
-0 item 4 ghost=false children=false
 0 item 5 ghost=false children=false
-0 item 6 ghost=false children=false
-
-== Sort(Priority) → Hierarchy ==
-total=6 unique_ids=6
-0 item 2 ghost=false children=true
-1 item 1 ghost=false children=false
-0 item 4 ghost=false children=true
-1 item 3 ghost=false children=false
+0 item 1 ghost=false children=false
+0 item 4 ghost=false children=false"#
+        );
+        assert_eq!(
+            render("Sort(Priority, UpdatedAt)"),
+            r#"0 item 1 ghost=false children=false
 0 item 5 ghost=false children=false
-0 item 6 ghost=false children=false"#
+0 item 3 ghost=false children=false
+0 item 2 ghost=false children=false
+0 item 4 ghost=false children=false"#
         );
     }
 
     #[test]
-    fn test_recipe_builder_ghost_rows_for_mixed_epic_children() {
+    fn test_recipe_builder_cross_tab() {
         let mut data = TestData::default();
-        let child_a = data.build().epic("EpicA").add();
-        let child_b = data.build().epic("EpicB").add();
+        let ws1_s1 = data.build().assignees(&["alice"]).add();
+        let ws1_s2 = data.build().assignees(&["alice", "bob"]).add();
+        let ws2_s1 = data.build().issue().add();
+        let _unset = data.build().issue().add();
+
+        set_workstream(&mut data, &ws1_s1, Some("WS1"));
+        set_iteration(&mut data, &ws1_s1, Some("S1"));
+        set_workstream(&mut data, &ws1_s2, Some("WS1"));
+        set_iteration(&mut data, &ws1_s2, Some("S2"));
+        set_workstream(&mut data, &ws2_s1, Some("WS2"));
+        set_iteration(&mut data, &ws2_s1, Some("S1"));
+
+        let render = |multi_value_strategy, rows, columns| {
+            let config = PivotConfig {
+                recipe: Vec::new(),
+                multi_value_strategy,
+                show_ghost_ancestors: true,
+                aggregates: Vec::new(),
+            };
+            let filters = Filters::default();
+            let original_work_items = HashMap::new();
+            let cross_tab = RecipeNodeBuilder::new(
+                &data.fields,
+                &data.work_items,
+                &filters,
+                &original_work_items,
+                &config,
+            )
+            .build_cross_tab(&rows, &columns);
+            format_cross_tab(&cross_tab)
+        };
+
+        assert_eq!(
+            render(
+                MultiValueStrategy::Combined,
+                PivotField::Workstream,
+                PivotField::Iteration
+            ),
+            r#"| S1 | S2 | (none)
+WS1 | 1 | 2 | -
+WS2 | 3 | - | -
+(none) | - | - | 4"#
+        );
+
+        assert_eq!(
+            render(
+                MultiValueStrategy::Explode,
+                PivotField::Assignee,
+                PivotField::Iteration
+            ),
+            r#"| S1 | S2 | (none)
+(none) | 3 | - | 4
+@alice | 1 | 2 | -
+@bob | - | 2 | -"#
+        );
+    }
+
+    #[test]
+    fn test_recipe_builder_aggregates() {
+        let mut data = TestData::default();
+        let open = data.build().epic("EpicA").status("Active").add();
+        let closed = data
+            .build()
+            .epic("EpicA")
+            .status("Closed")
+            .issue_state(IssueState::CLOSED)
+            .add();
         let parent = data
             .build()
             .epic("EpicA")
-            .sub_issues(&[&child_a, &child_b])
+            .status("Active")
+            .sub_issues(&[&open, &closed])
             .add();
-        set_title(&mut data, &child_a, "Alpha child");
-        set_title(&mut data, &child_b, "Beta child");
-        set_title(&mut data, &parent, "Parent");
+        let other = data.build().epic("EpicB").status("Closed").add();
+
+        set_estimate(&mut data, &open, Some("Days"));
+        set_estimate(&mut data, &closed, Some("Hours"));
+        set_estimate(&mut data, &other, Some("Week"));
 
         let config = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Epic), Axis::Hierarchy],
+            recipe: parse_recipe("Pivot(Epic) → Hierarchy").unwrap(),
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: true,
+            aggregates: vec![
+                Aggregate::Count,
+                Aggregate::Open,
+                Aggregate::Closed,
+                Aggregate::Estimate,
+                Aggregate::PercentDone,
+            ],
+        };
+        let nodes = build_recipe_nodes(&data.fields, &data.work_items, &config);
+        let aggregates = |id: &str| {
+            nodes
+                .iter()
+                .find(|node| node.id == id)
+                .unwrap()
+                .aggregates
+                .clone()
         };
 
         assert_eq!(
-            render_recipe_nodes(&data.fields, &data.work_items, &config),
-            r#"0 group path/epic=id(EpicA) EpicA
-1 item 3 ghost=false children=true
-2 item 1 ghost=false children=false
-0 group path/epic=id(EpicB) EpicB
-1 item 3 ghost=true children=true
-2 item 2 ghost=false children=false"#
+            aggregates("path/epic=id(EpicA)"),
+            Some(Aggregates {
+                count: Some(3),
+                open: Some(2),
+                closed: Some(1),
+                estimate: Some(2.5),
+                percent_done: Some(33),
+            })
+        );
+        assert_eq!(
+            aggregates(&format!("path/epic=id(EpicA)/{}", parent.0)),
+            Some(Aggregates {
+                count: Some(2),
+                open: Some(1),
+                closed: Some(1),
+                estimate: Some(2.5),
+                percent_done: Some(50),
+            })
+        );
+        assert_eq!(
+            aggregates(&format!("path/epic=id(EpicA)/{}/{}", parent.0, open.0)),
+            None
+        );
+        assert_eq!(
+            aggregates("path/epic=id(EpicB)"),
+            Some(Aggregates {
+                count: Some(1),
+                open: Some(1),
+                closed: Some(0),
+                estimate: Some(5.0),
+                percent_done: Some(100),
+            })
         );
     }
 
     #[test]
-    fn test_recipe_builder_hierarchy_then_pivot_recurses_every_level() {
-        // Reproduces the issue example: pivot on Epic follows the parent ↔
-        // sub-issue tree, bucketing children by Epic at every level (and the
-        // roots themselves).
-        //
-        //   IssueA (EpicA)
-        //   ├─ IssueA-A (EpicA)
-        //   │  ├─ IssueA-A-A (EpicA)
-        //   │  └─ IssueA-A-B (EpicA)
-        //   ├─ IssueA-B (EpicB)
-        //   └─ IssueA-C (EpicA)
+    fn test_recipe_builder_rebuild() {
         let mut data = TestData::default();
-        let issue_a_a_a = data.build().epic("EpicA").add();
-        let issue_a_a_b = data.build().epic("EpicA").add();
-        let issue_a_a = data
-            .build()
-            .epic("EpicA")
-            .sub_issues(&[&issue_a_a_a, &issue_a_a_b])
-            .add();
-        let issue_a_b = data.build().epic("EpicB").add();
-        let issue_a_c = data.build().epic("EpicA").add();
-        let issue_a = data
+        let child1 = data.build().workstream("WS1").add();
+        let child2 = data.build().workstream("WS2").add();
+        let parent = data
             .build()
-            .epic("EpicA")
-            .sub_issues(&[&issue_a_a, &issue_a_b, &issue_a_c])
+            .workstream("WS1")
+            .sub_issues(&[&child1, &child2])
             .add();
-
-        set_title(&mut data, &issue_a, "IssueA");
-        set_title(&mut data, &issue_a_a, "IssueA-A");
-        set_title(&mut data, &issue_a_b, "IssueA-B");
-        set_title(&mut data, &issue_a_c, "IssueA-C");
-        set_title(&mut data, &issue_a_a_a, "IssueA-A-A");
-        set_title(&mut data, &issue_a_a_b, "IssueA-A-B");
+        let other = data.build().workstream("WS2").add();
+        let loose1 = data.build().add();
+        let loose2 = data.build().add();
+        set_title(&mut data, &other, "other");
+        set_title(&mut data, &loose1, "a");
+        set_title(&mut data, &loose2, "b");
 
         let config = PivotConfig {
-            recipe: parse_recipe("Hierarchy → Pivot(Epic)").unwrap(),
-            multi_value_strategy: MultiValueStrategy::Combined,
-            show_ghost_ancestors: true,
+            recipe: parse_recipe("Sort(Title) → Pivot(Workstream) → Hierarchy").unwrap(),
+            aggregates: vec![Aggregate::Count],
+            ..PivotConfig::default()
         };
+        let previous = build_recipe_nodes(&data.fields, &data.work_items, &config);
 
+        set_workstream(&mut data, &child1, Some("WS2"));
+        // Not reported as affected, so its bucket is copied as it was rather
+        // than re-sorted.
+        set_title(&mut data, &loose1, "c");
+
+        let affected = HashSet::from([child1.clone(), parent.clone()]);
+        let rebuilt = rebuild_recipe_nodes(&data, &config, &previous, &affected);
+        let built = build_recipe_nodes(&data.fields, &data.work_items, &config);
+
+        let without_loose = |nodes: &[Node]| {
+            nodes
+                .iter()
+                .filter(|node| !node.id.starts_with("path/workstream=(none)"))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(without_loose(&rebuilt), without_loose(&built));
         assert_eq!(
-            render_recipe_nodes(&data.fields, &data.work_items, &config),
-            r#"0 group path/epic=id(EpicA) EpicA
-1 item 6 ghost=false children=true
-2 group path/epic=id(EpicA)/6/epic=id(EpicA) EpicA
-3 item 3 ghost=false children=true
-4 group path/epic=id(EpicA)/6/epic=id(EpicA)/3/epic=id(EpicA) EpicA
-5 item 1 ghost=false children=false
-5 item 2 ghost=false children=false
-3 item 5 ghost=false children=false
-2 group path/epic=id(EpicA)/6/epic=id(EpicB) EpicB
-3 item 4 ghost=false children=false"#
+            rebuilt
+                .iter()
+                .filter(|node| node.id.starts_with("path/workstream=(none)/"))
+                .map(|node| node.id.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                format!("path/workstream=(none)/{}", loose1.0),
+                format!("path/workstream=(none)/{}", loose2.0),
+            ]
+        );
+
+        // Without top-level buckets everything is rebuilt.
+        let config = PivotConfig {
+            recipe: parse_recipe("Hierarchy").unwrap(),
+            ..PivotConfig::default()
+        };
+        let previous = build_recipe_nodes(&data.fields, &data.work_items, &config);
+        set_title(&mut data, &loose1, "a");
+        assert_eq!(
+            rebuild_recipe_nodes(&data, &config, &previous, &HashSet::new()),
+            build_recipe_nodes(&data.fields, &data.work_items, &config)
         );
     }
 
+    #[test]
+    fn test_recipe_builder_rebuild_with_collapse() {
+        let mut data = TestData::default();
+        let a1 = data.build().epic("EpicA").status("Active").add();
+        let a2 = data.build().epic("EpicA").status("Active").add();
+        data.build().epic("EpicB").status("Active").add();
+        let b_closed = data.build().epic("EpicB").status("Closed").add();
+        set_title(&mut data, &a1, "a");
+        set_title(&mut data, &a2, "b");
+
+        let config = PivotConfig {
+            recipe: parse_recipe("Sort(Title) → Pivot(Epic, collapse) → Group(Status)").unwrap(),
+            ..PivotConfig::default()
+        };
+        let previous = build_recipe_nodes(&data.fields, &data.work_items, &config);
+
+        data.work_items
+            .get_mut(&b_closed)
+            .unwrap()
+            .project_item
+            .status = data.fields.status.option_id(Some("Active")).cloned();
+        // Not reported as affected, so the collapsed "EpicA › Active" group is
+        // copied as it was rather than re-sorted.
+        set_title(&mut data, &a1, "c");
+
+        let affected = HashSet::from([b_closed.clone()]);
+        let rebuilt = rebuild_recipe_nodes(&data, &config, &previous, &affected);
+        let built = build_recipe_nodes(&data.fields, &data.work_items, &config);
+
+        let epic = |nodes: &[Node], epic: &str| {
+            let prefix = format!("path/epic=id({epic})");
+            nodes
+                .iter()
+                .filter(|node| node.id.starts_with(&prefix))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(epic(&rebuilt, "EpicB"), epic(&built, "EpicB"));
+        assert_eq!(epic(&rebuilt, "EpicA"), epic(&previous, "EpicA"));
+        assert_ne!(epic(&built, "EpicA"), epic(&previous, "EpicA"));
+    }
+
     #[test]
     fn test_recipe_builder_multi_value_combined_vs_explode() {
         let mut data = TestData::default();
@@ -1133,14 +2284,16 @@ total=6 unique_ids=6
         set_title(&mut data, &item, "Shared");
 
         let combined = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Assignee)],
+            recipe: vec![Axis::Pivot(PivotField::Assignee.into())],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: true,
+            aggregates: Vec::new(),
         };
         let exploded = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Assignee)],
+            recipe: vec![Axis::Pivot(PivotField::Assignee.into())],
             multi_value_strategy: MultiValueStrategy::Explode,
             show_ghost_ancestors: true,
+            aggregates: Vec::new(),
         };
 
         assert_eq!(
@@ -1172,9 +2325,10 @@ total=6 unique_ids=6
         set_title(&mut data, &parent, "Parent");
 
         let config = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Epic), Axis::Hierarchy],
+            recipe: vec![Axis::Pivot(PivotField::Epic.into()), Axis::Hierarchy(None)],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: false,
+            aggregates: Vec::new(),
         };
 
         assert_eq!(
@@ -1223,9 +2377,10 @@ total=6 unique_ids=6
         set_title(&mut data, &child_b, "Beta");
 
         let config = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Epic), Axis::Hierarchy],
+            recipe: vec![Axis::Pivot(PivotField::Epic.into()), Axis::Hierarchy(None)],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: true,
+            aggregates: Vec::new(),
         };
 
         // Both children must appear; the missing parent must NOT appear
@@ -1267,9 +2422,13 @@ total=6 unique_ids=6
         let original_work_items = HashMap::new();
 
         let config = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Iteration), Axis::Hierarchy],
+            recipe: vec![
+                Axis::Pivot(PivotField::Iteration.into()),
+                Axis::Hierarchy(None),
+            ],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: true,
+            aggregates: Vec::new(),
         };
 
         let nodes = RecipeNodeBuilder::new(
@@ -1309,9 +2468,13 @@ total=6 unique_ids=6
         let original_work_items = HashMap::new();
 
         let config = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Iteration), Axis::Hierarchy],
+            recipe: vec![
+                Axis::Pivot(PivotField::Iteration.into()),
+                Axis::Hierarchy(None),
+            ],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: false,
+            aggregates: Vec::new(),
         };
 
         let nodes = RecipeNodeBuilder::new(
@@ -1350,9 +2513,13 @@ total=6 unique_ids=6
         let original_work_items = HashMap::new();
 
         let config = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Iteration), Axis::Hierarchy],
+            recipe: vec![
+                Axis::Pivot(PivotField::Iteration.into()),
+                Axis::Hierarchy(None),
+            ],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: true,
+            aggregates: Vec::new(),
         };
 
         let nodes = RecipeNodeBuilder::new(
@@ -1391,9 +2558,10 @@ total=6 unique_ids=6
         set_title(&mut data, &parent, "Parent in EpicA");
 
         let config = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Epic), Axis::Hierarchy],
+            recipe: vec![Axis::Pivot(PivotField::Epic.into()), Axis::Hierarchy(None)],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: true,
+            aggregates: Vec::new(),
         };
 
         let filters = Filters::default();
@@ -1479,6 +2647,7 @@ total=6 unique_ids=6
                         recipe: parse_recipe(preset_name).unwrap(),
                         multi_value_strategy: strategy.clone(),
                         show_ghost_ancestors,
+                        aggregates: Vec::new(),
                     };
                     let nodes = build_recipe_nodes(&fixture.fields, &fixture.work_items, &config);
 
@@ -1583,6 +2752,24 @@ total=6 unique_ids=6
         builder.build()
     }
 
+    fn rebuild_recipe_nodes(
+        data: &TestData,
+        pivot_config: &PivotConfig,
+        previous: &[Node],
+        affected: &HashSet<WorkItemId>,
+    ) -> Vec<Node> {
+        let filters = Filters::default();
+        let original_work_items = HashMap::new();
+        let mut builder = RecipeNodeBuilder::new(
+            &data.fields,
+            &data.work_items,
+            &filters,
+            &original_work_items,
+            pivot_config,
+        );
+        builder.rebuild(previous, affected)
+    }
+
     fn format_nodes_string(nodes: &[Node]) -> String {
         nodes
             .iter()
@@ -1608,6 +2795,38 @@ total=6 unique_ids=6
         format_nodes_string(&nodes)
     }
 
+    fn format_cross_tab(cross_tab: &CrossTab) -> String {
+        let header = cross_tab
+            .columns
+            .iter()
+            .map(|column| format!(" | {}", column.label))
+            .collect::<String>();
+        std::iter::once(header.trim_start().to_owned())
+            .chain(
+                cross_tab
+                    .rows
+                    .iter()
+                    .zip(&cross_tab.cells)
+                    .map(|(row, cells)| {
+                        let cells = cells
+                            .iter()
+                            .map(|items| {
+                                if items.is_empty() {
+                                    " | -".to_owned()
+                                } else {
+                                    let ids =
+                                        items.iter().map(|id| id.0.as_str()).collect::<Vec<_>>();
+                                    format!(" | {}", ids.join(","))
+                                }
+                            })
+                            .collect::<String>();
+                        format!("{}{cells}", row.label)
+                    }),
+            )
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Find duplicate `Node.id` values along with the count of occurrences.
     /// Returned in sorted order so failure messages are stable across runs.
     fn duplicate_node_ids(nodes: &[Node]) -> Vec<(String, usize)> {
@@ -1713,6 +2932,14 @@ total=6 unique_ids=6
         data.work_items.get_mut(id).unwrap().title = title.to_owned();
     }
 
+    fn set_updated_at(data: &mut TestData, id: &WorkItemId, updated_at: &str) {
+        data.work_items.get_mut(id).unwrap().updated_at = updated_at.to_owned();
+    }
+
+    fn set_created_at(data: &mut TestData, id: &WorkItemId, created_at: &str) {
+        data.work_items.get_mut(id).unwrap().created_at = created_at.to_owned();
+    }
+
     fn set_repository(data: &mut TestData, id: &WorkItemId, repo: Option<&str>) {
         data.work_items.get_mut(id).unwrap().repo_name_with_owner = repo.map(str::to_owned);
     }
@@ -1741,6 +2968,7 @@ total=6 unique_ids=6
                 .iter()
                 .map(|assignee| (*assignee).to_owned())
                 .collect(),
+            ..Default::default()
         });
     }
 
@@ -1753,6 +2981,11 @@ total=6 unique_ids=6
         data.work_items.get_mut(id).unwrap().project_item.iteration = option.into();
     }
 
+    fn set_estimate(data: &mut TestData, id: &WorkItemId, value: Option<&str>) {
+        let option = data.fields.estimate.option_id(value).cloned();
+        data.work_items.get_mut(id).unwrap().project_item.estimate = option;
+    }
+
     fn set_priority(data: &mut TestData, id: &WorkItemId, value: Option<&str>) {
         let option = data.fields.priority.option_id(value).cloned();
         data.work_items.get_mut(id).unwrap().project_item.priority = option;
@@ -1793,9 +3026,10 @@ total=6 unique_ids=6
         };
         let original_work_items = HashMap::new();
         let config = PivotConfig {
-            recipe: vec![Axis::Hierarchy],
+            recipe: vec![Axis::Hierarchy(None)],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: false,
+            aggregates: Vec::new(),
         };
         let mut builder = RecipeNodeBuilder::new(
             &data.fields,
@@ -1822,6 +3056,53 @@ total=6 unique_ids=6
         assert!(has_work_item(&parent2_id));
     }
 
+    #[test]
+    fn test_recipe_node_builder_filters_query() {
+        let mut data = TestData::default();
+
+        let mine_id = data.build().status("Active").assignees(&["me"]).add();
+        let closed_id = data.build().status("Closed").assignees(&["me"]).add();
+        let parent_id = data
+            .build()
+            .sub_issues(&[&mine_id, &closed_id])
+            .status("Active")
+            .add();
+        let other_id = data.build().status("Active").assignees(&["other"]).add();
+
+        let filters = Filters {
+            query: Some(parse_query("assignee:@me AND status!=Closed").unwrap()),
+            ..Filters::default()
+        };
+        let original_work_items = HashMap::new();
+        let config = PivotConfig {
+            recipe: vec![Axis::Hierarchy(None)],
+            multi_value_strategy: MultiValueStrategy::Combined,
+            show_ghost_ancestors: false,
+            aggregates: Vec::new(),
+        };
+        let nodes = RecipeNodeBuilder::new(
+            &data.fields,
+            &data.work_items,
+            &filters,
+            &original_work_items,
+            &config,
+        )
+        .with_viewer(Some("me"))
+        .build();
+
+        let has_work_item = |id: &WorkItemId| {
+            nodes.iter().any(|n| match &n.data {
+                NodeData::WorkItem { work_item_id } => work_item_id == id,
+                _ => false,
+            })
+        };
+
+        assert!(has_work_item(&mine_id));
+        assert!(has_work_item(&parent_id));
+        assert!(!has_work_item(&closed_id));
+        assert!(!has_work_item(&other_id));
+    }
+
     #[test]
     fn test_recipe_node_builder_new_item_after_update_appears() {
         let mut data = TestData::default();
@@ -1843,9 +3124,10 @@ total=6 unique_ids=6
         let filters = Filters::default();
         let original_work_items = HashMap::new();
         let config = PivotConfig {
-            recipe: vec![Axis::Hierarchy],
+            recipe: vec![Axis::Hierarchy(None)],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: false,
+            aggregates: Vec::new(),
         };
         let mut builder = RecipeNodeBuilder::new(
             &data.fields,
@@ -1890,9 +3172,13 @@ total=6 unique_ids=6
         set_title(&mut data, &item3, "Gamma");
 
         let config = PivotConfig {
-            recipe: vec![Axis::Pivot(PivotField::Workstream), Axis::Hierarchy],
+            recipe: vec![
+                Axis::Pivot(PivotField::Workstream.into()),
+                Axis::Hierarchy(None),
+            ],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: false,
+            aggregates: Vec::new(),
         };
 
         let filters = Filters::default();
@@ -0,0 +1,323 @@
+//! Full-text search over the titles, bodies and comments of work items.
+//!
+//! Each word of the search text must match a word of the item, either
+//! exactly, as a prefix ("sub" matches "subissue") or, for longer words,
+//! with a typo or two ("iteraton" matches "iteration").  Exact matches rank
+//! above prefix matches, which rank above fuzzy ones, and matches in the
+//! title rank above those in the body, which rank above those in comments.
+
+use crate::nodes::{Node, NodeData};
+use github_graphql::data::{DelayLoad, WorkItem, WorkItemId, WorkItems};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
+use ts_rs::TS;
+
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SearchResult {
+    pub work_item_id: WorkItemId,
+    pub score: u32,
+    /// For each node that shows the item in the current view, the ids of the
+    /// nodes from the top of the tree down to it.  Empty if the view doesn't
+    /// show the item.
+    pub paths: Vec<Vec<String>>,
+}
+
+/// Where in a work item a word was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Source {
+    Comment,
+    Body,
+    Title,
+}
+
+impl Source {
+    fn weight(self) -> u32 {
+        match self {
+            Source::Comment => 1,
+            Source::Body => 2,
+            Source::Title => 4,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SearchIndex {
+    /// Each word, and the items it's in along with the best place it was
+    /// found in each.
+    words: BTreeMap<String, HashMap<WorkItemId, Source>>,
+    /// The words indexed for each item, so that they can be removed.
+    items: HashMap<WorkItemId, Vec<String>>,
+}
+
+impl SearchIndex {
+    pub fn new(work_items: &WorkItems) -> Self {
+        let mut index = Self::default();
+        for id in work_items.iter() {
+            if let Some(item) = work_items.get(id) {
+                index.update(item);
+            }
+        }
+        index
+    }
+
+    /// Indexes `item`, replacing whatever was indexed for it before.
+    pub fn update(&mut self, item: &WorkItem) {
+        self.remove(&item.id);
+
+        let mut sources: HashMap<String, Source> = HashMap::new();
+        let mut add = |text: &str, source: Source| {
+            for word in words(text) {
+                let best = sources.entry(word).or_insert(source);
+                *best = (*best).max(source);
+            }
+        };
+        add(&item.title, Source::Title);
+        if let DelayLoad::Loaded(body) = &item.body {
+            add(body, Source::Body);
+        }
+        if let DelayLoad::Loaded(comments) = &item.comments {
+            for comment in comments {
+                add(comment, Source::Comment);
+            }
+        }
+
+        for (word, source) in &sources {
+            self.words
+                .entry(word.clone())
+                .or_default()
+                .insert(item.id.clone(), *source);
+        }
+        self.items
+            .insert(item.id.clone(), sources.into_keys().collect());
+    }
+
+    pub fn remove(&mut self, id: &WorkItemId) {
+        for word in self.items.remove(id).unwrap_or_default() {
+            if let Some(items) = self.words.get_mut(&word) {
+                items.remove(id);
+                if items.is_empty() {
+                    self.words.remove(&word);
+                }
+            }
+        }
+    }
+
+    /// Returns up to `limit` items that match every word of `text`, best
+    /// matches first.
+    pub fn search(&self, text: &str, limit: usize) -> Vec<(WorkItemId, u32)> {
+        let mut totals: Option<HashMap<&WorkItemId, u32>> = None;
+        for term in words(text) {
+            let scores = self.term_scores(&term);
+            totals = Some(match totals {
+                None => scores,
+                Some(totals) => totals
+                    .into_iter()
+                    .filter_map(|(id, total)| scores.get(id).map(|score| (id, total + score)))
+                    .collect(),
+            });
+        }
+
+        let mut results: Vec<(WorkItemId, u32)> = totals
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(id, score)| (id.clone(), score))
+            .collect();
+        results.sort_by(|(a_id, a_score), (b_id, b_score)| {
+            b_score.cmp(a_score).then_with(|| a_id.0.cmp(&b_id.0))
+        });
+        results.truncate(limit);
+        results
+    }
+
+    /// The best score of each item with a word that matches `term`.
+    fn term_scores(&self, term: &str) -> HashMap<&WorkItemId, u32> {
+        let mut scores = HashMap::new();
+
+        let prefixed = self
+            .words
+            .range::<str, _>((Bound::Included(term), Bound::Unbounded))
+            .take_while(|(word, _)| word.starts_with(term));
+        for (word, items) in prefixed {
+            merge_scores(&mut scores, items, if word == term { 3 } else { 2 });
+        }
+
+        let max_edits = max_edits(term);
+        if max_edits > 0 {
+            for (word, items) in &self.words {
+                if !word.starts_with(term)
+                    && word.chars().count().abs_diff(term.chars().count()) <= max_edits
+                    && edit_distance(word, term) <= max_edits
+                {
+                    merge_scores(&mut scores, items, 1);
+                }
+            }
+        }
+
+        scores
+    }
+}
+
+fn merge_scores<'a>(
+    scores: &mut HashMap<&'a WorkItemId, u32>,
+    items: &'a HashMap<WorkItemId, Source>,
+    factor: u32,
+) {
+    for (id, source) in items {
+        let score = scores.entry(id).or_insert(0);
+        *score = (*score).max(source.weight() * factor);
+    }
+}
+
+/// The lowercased words in `text`, without duplicates.
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if !word.is_empty() && !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// How many typos a search word may have: none for short words, where
+/// almost anything would match.
+fn max_edits(term: &str) -> usize {
+    match term.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// For each node that shows one of `ids`, other than ghosts, the ids of the
+/// nodes from the top of the tree down to it.
+pub fn node_paths(
+    nodes: &[Node],
+    ids: &HashSet<&WorkItemId>,
+) -> HashMap<WorkItemId, Vec<Vec<String>>> {
+    let mut paths: HashMap<WorkItemId, Vec<Vec<String>>> = HashMap::new();
+    let mut ancestors: Vec<&Node> = Vec::new();
+
+    for node in nodes {
+        while ancestors
+            .last()
+            .is_some_and(|ancestor| ancestor.level >= node.level)
+        {
+            ancestors.pop();
+        }
+
+        if let NodeData::WorkItem { work_item_id } = &node.data
+            && !node.is_ghost
+            && ids.contains(work_item_id)
+        {
+            paths.entry(work_item_id.clone()).or_default().push(
+                ancestors
+                    .iter()
+                    .map(|ancestor| ancestor.id.clone())
+                    .chain([node.id.clone()])
+                    .collect(),
+            );
+        }
+        ancestors.push(node);
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use github_graphql::data::test_helpers::TestData;
+
+    fn ids(results: &[(WorkItemId, u32)]) -> Vec<&WorkItemId> {
+        results.iter().map(|(id, _)| id).collect()
+    }
+
+    fn item<'a>(data: &'a mut TestData, id: &WorkItemId) -> &'a mut WorkItem {
+        data.work_items.get_mut(id).unwrap()
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        let mut data = TestData::default();
+        let in_title = data.build().add();
+        let in_body = data.build().add();
+        let in_comment = data.build().add();
+        let prefix = data.build().add();
+        let typo = data.build().add();
+
+        item(&mut data, &in_title).title = "Shader compiler crash".to_owned();
+        item(&mut data, &in_body).title = "Unrelated".to_owned();
+        item(&mut data, &in_body).body = DelayLoad::Loaded("The shader crashes".to_owned());
+        item(&mut data, &in_comment).title = "Also unrelated".to_owned();
+        item(&mut data, &in_comment).comments =
+            DelayLoad::Loaded(vec!["Seen with this shader too".to_owned()]);
+        item(&mut data, &prefix).title = "Shaders everywhere".to_owned();
+        item(&mut data, &typo).title = "Shadr typo".to_owned();
+
+        let index = SearchIndex::new(&data.work_items);
+
+        let results = index.search("shader", 10);
+        assert_eq!(
+            ids(&results),
+            vec![&in_title, &prefix, &in_body, &typo, &in_comment]
+        );
+
+        // Every word has to match.
+        assert_eq!(
+            ids(&index.search("SHADER crash", 10)),
+            vec![&in_title, &in_body]
+        );
+        assert_eq!(ids(&index.search("shader", 2)), vec![&in_title, &prefix]);
+        assert!(index.search("", 10).is_empty());
+        assert!(index.search("missing", 10).is_empty());
+    }
+
+    #[test]
+    fn test_search_update_and_remove() {
+        let mut data = TestData::default();
+        let id = data.build().add();
+        data.work_items.get_mut(&id).unwrap().title = "Old title".to_owned();
+
+        let mut index = SearchIndex::new(&data.work_items);
+        assert_eq!(ids(&index.search("old", 10)), vec![&id]);
+
+        item(&mut data, &id).title = "New title".to_owned();
+        index.update(item(&mut data, &id));
+        assert!(index.search("old", 10).is_empty());
+        assert_eq!(ids(&index.search("new", 10)), vec![&id]);
+
+        index.remove(&id);
+        assert!(index.search("title", 10).is_empty());
+        assert!(index.words.is_empty());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("shader", "shader"), 0);
+        assert_eq!(edit_distance("shadr", "shader"), 1);
+        assert_eq!(edit_distance("iteraton", "iteration"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+}
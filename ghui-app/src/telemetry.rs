@@ -53,6 +53,13 @@ pub enum TelemetryEvent {
         conflicts_count: usize,
     },
     ConvertTracked,
+    ConvertAllTracked {
+        changes_count: usize,
+        conflicts_count: usize,
+    },
+    RevertSave {
+        changes_count: usize,
+    },
 
     AddItemFromUrl {
         has_parent: bool,
@@ -188,7 +195,7 @@ fn generate_session_id() -> String {
     format!("{:016x}", mixed)[..8].to_string()
 }
 
-fn format_iso_timestamp() -> String {
+pub fn format_iso_timestamp() -> String {
     let now = OffsetDateTime::now_utc();
     format!(
         "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
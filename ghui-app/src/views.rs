@@ -0,0 +1,253 @@
+//! Named views: saved filter and pivot configurations that can be switched
+//! between, and shared with teammates as JSON files.
+
+use crate::Filters;
+use crate::appdata::{self, CacheFormat};
+use anyhow::{Result, bail};
+use github_graphql::pivot::PivotConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use ts_rs::TS;
+
+#[derive(Serialize, Deserialize, TS, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SavedView {
+    pub name: String,
+    #[serde(default)]
+    pub filters: Filters,
+    #[serde(default)]
+    pub pivot_config: PivotConfig,
+}
+
+#[derive(Default, Serialize, Deserialize, TS, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ViewRegistry {
+    pub views: Vec<SavedView>,
+    /// The view applied when ghui starts.
+    pub default_view: Option<String>,
+}
+
+impl ViewRegistry {
+    pub fn get(&self, name: &str) -> Option<&SavedView> {
+        self.views.iter().find(|view| view.name == name)
+    }
+
+    /// Adds `view`, replacing any existing view with the same name.
+    pub fn save(&mut self, view: SavedView) -> Result<()> {
+        if view.name.trim().is_empty() {
+            bail!("A view needs a name");
+        }
+
+        match self.views.iter_mut().find(|v| v.name == view.name) {
+            Some(existing) => *existing = view,
+            None => self.views.push(view),
+        }
+        Ok(())
+    }
+
+    pub fn rename(&mut self, name: &str, new_name: &str) -> Result<()> {
+        if new_name.trim().is_empty() {
+            bail!("A view needs a name");
+        }
+        if name != new_name && self.get(new_name).is_some() {
+            bail!("A view named {new_name:?} already exists");
+        }
+        let Some(view) = self.views.iter_mut().find(|view| view.name == name) else {
+            bail!("No view named {name:?}");
+        };
+
+        view.name = new_name.to_owned();
+        if self.default_view.as_deref() == Some(name) {
+            self.default_view = Some(new_name.to_owned());
+        }
+        Ok(())
+    }
+
+    pub fn delete(&mut self, name: &str) -> Result<()> {
+        let count = self.views.len();
+        self.views.retain(|view| view.name != name);
+        if self.views.len() == count {
+            bail!("No view named {name:?}");
+        }
+
+        if self.default_view.as_deref() == Some(name) {
+            self.default_view = None;
+        }
+        Ok(())
+    }
+
+    /// Sets the view applied at startup; `None` keeps whatever was last used.
+    pub fn set_default(&mut self, name: Option<&str>) -> Result<()> {
+        if let Some(name) = name
+            && self.get(name).is_none()
+        {
+            bail!("No view named {name:?}");
+        }
+
+        self.default_view = name.map(str::to_owned);
+        Ok(())
+    }
+
+    pub fn default_view(&self) -> Option<&SavedView> {
+        self.default_view.as_deref().and_then(|name| self.get(name))
+    }
+
+    /// Adds a view shared by someone else.  If the name is taken the view is
+    /// renamed, e.g. "Triage (2)", rather than replacing the local one.
+    /// Returns the name the view was added under.
+    pub fn import(&mut self, mut view: SavedView) -> Result<String> {
+        let base = view.name.clone();
+        let mut suffix = 2;
+        while self.get(&view.name).is_some() {
+            view.name = format!("{base} ({suffix})");
+            suffix += 1;
+        }
+
+        let name = view.name.clone();
+        self.save(view)?;
+        Ok(name)
+    }
+}
+
+const VIEWS_FILENAME: &str = "views";
+
+const VIEWS_FORMAT: CacheFormat = CacheFormat {
+    name: "views",
+    migrations: &[appdata::from_unversioned],
+};
+
+pub fn get_views_file_path() -> PathBuf {
+    appdata::get_appdata_path(VIEWS_FILENAME)
+}
+
+/// Loads the registry from `path`.  A missing file means no views have been
+/// saved yet.
+pub fn load(path: &Path) -> Result<ViewRegistry> {
+    Ok(appdata::load(path, &VIEWS_FORMAT)?.unwrap_or_default())
+}
+
+pub fn save(path: &Path, registry: &ViewRegistry) -> Result<()> {
+    appdata::save(path, &VIEWS_FORMAT, registry)
+}
+
+/// Writes `view` to `dir` so that it can be shared, returning the file's path.
+pub fn export(dir: &Path, view: &SavedView) -> Result<PathBuf> {
+    let stem = view
+        .name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    let path = dir.join(format!("{stem}.ghui-view.json"));
+
+    let writer = fs::File::create(&path)?;
+    serde_json::to_writer_pretty(BufWriter::new(writer), view)?;
+    Ok(path)
+}
+
+/// Parses a view exported by [`export`].
+pub fn parse(json: &str) -> Result<SavedView> {
+    serde_json::from_str(json).map_err(|e| anyhow::anyhow!("not a ghui view: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use github_graphql::pivot::{Axis, PivotField};
+
+    fn view(name: &str) -> SavedView {
+        SavedView {
+            name: name.to_owned(),
+            filters: Filters::default(),
+            pivot_config: PivotConfig {
+                recipe: vec![Axis::Pivot(PivotField::Epic.into())],
+                ..PivotConfig::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_rename_and_delete_update_default() {
+        let mut registry = ViewRegistry::default();
+        registry.save(view("Triage")).unwrap();
+        registry.save(view("My items")).unwrap();
+        registry.set_default(Some("Triage")).unwrap();
+
+        assert!(registry.rename("Triage", "My items").is_err());
+        registry.rename("Triage", "Inbox").unwrap();
+        assert_eq!(registry.default_view.as_deref(), Some("Inbox"));
+
+        registry.delete("Inbox").unwrap();
+        assert_eq!(registry.default_view, None);
+        assert!(registry.delete("Inbox").is_err());
+        assert!(registry.set_default(Some("Inbox")).is_err());
+    }
+
+    #[test]
+    fn test_import_keeps_existing_view() {
+        let mut registry = ViewRegistry::default();
+        registry.save(view("Triage")).unwrap();
+
+        assert_eq!(registry.import(view("Triage")).unwrap(), "Triage (2)");
+        assert_eq!(registry.import(view("Triage")).unwrap(), "Triage (3)");
+        assert_eq!(registry.import(view("Review")).unwrap(), "Review");
+        assert_eq!(registry.views.len(), 4);
+    }
+
+    #[test]
+    fn test_export_and_parse_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let exported = view("Epic review / Q4");
+
+        let path = export(dir.path(), &exported).unwrap();
+        assert_eq!(
+            path.file_name().unwrap().to_str().unwrap(),
+            "Epic_review___Q4.ghui-view.json"
+        );
+
+        let parsed = parse(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(parsed.name, exported.name);
+        assert_eq!(parsed.pivot_config, exported.pivot_config);
+        assert!(parse("{}").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("views.ghui.json");
+        assert!(load(&path).unwrap().views.is_empty());
+
+        let mut registry = ViewRegistry::default();
+        registry.save(view("Triage")).unwrap();
+        registry.set_default(Some("Triage")).unwrap();
+        save(&path, &registry).unwrap();
+
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.views.len(), 1);
+        assert_eq!(loaded.default_view.as_deref(), Some("Triage"));
+    }
+
+    #[test]
+    fn test_load_unversioned_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("views.ghui.json");
+
+        // Written before the file had a version.
+        fs::write(
+            &path,
+            r#"{ "views": [{ "name": "Triage" }], "defaultView": "Triage" }"#,
+        )
+        .unwrap();
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.views[0].name, "Triage");
+        assert_eq!(loaded.default_view.as_deref(), Some("Triage"));
+
+        save(&path, &loaded).unwrap();
+        let written: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(written["version"], 1);
+        assert_eq!(load(&path).unwrap().views.len(), 1);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::Result;
+use github_graphql::data;
+
+#[derive(Debug, clap::Args)]
+pub struct Options {
+    /// Check the items in all_items.json rather than fetching them
+    #[arg(long)]
+    test_data: bool,
+}
+
+pub async fn run(options: Options) -> Result {
+    let items = if options.test_data {
+        let mut file = std::fs::File::open("all_items.json")?;
+        let work_items: Vec<data::WorkItem> = serde_json::from_reader(&mut file)?;
+        data::WorkItems::from_iter(work_items)
+    } else {
+        crate::hygiene::get_items(&crate::client()).await?
+    };
+
+    let describe =
+        |id: &data::WorkItemId| items.get(id).map(|i| i.describe()).unwrap_or(id.0.clone());
+
+    let issues = items.check_integrity();
+    for issue in &issues {
+        let fix = issue
+            .fix
+            .as_ref()
+            .map(|fix| format!(" (fix: {})", fix.describe()))
+            .unwrap_or_default();
+        println!(
+            "{} - {}{}",
+            describe(&issue.work_item_id),
+            issue.problem.describe(),
+            fix
+        );
+    }
+
+    println!("{} integrity problem(s)", issues.len());
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::Result;
+use github_graphql::{
+    client::graphql::custom_fields_query::get_fields,
+    data::{Change, IssueTypeRegistry, SaveMode, WorkItemId},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Options {
+    #[arg(value_enum, default_value_t = Mode::DryRun)]
+    mode: Mode,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mode {
+    DryRun,
+    Commit,
+}
+
+pub async fn run(options: Options) -> Result {
+    let client = crate::client();
+
+    let items = crate::hygiene::get_items(&client).await?;
+    let fields = get_fields(&client).await?;
+
+    let describe = |id: &WorkItemId| items.get(id).map(|i| i.describe()).unwrap_or(id.0.clone());
+
+    let conversion = items.convert_all_tracked_to_sub_issues();
+
+    for conflict in &conversion.conflicts {
+        println!(
+            "CONFLICT {} - tracked by {} but already parented by {}",
+            describe(&conflict.work_item_id),
+            describe(&conflict.tracker_id),
+            describe(&conflict.parent_id)
+        );
+    }
+
+    println!(
+        "{} issue(s) to convert, {} conflict(s)",
+        conversion.changes.len(),
+        conversion.conflicts.len()
+    );
+
+    let report_progress = |change: &Change, _, _| {
+        println!(
+            "{} - {}",
+            describe(&change.work_item_id),
+            change.describe(&fields, &items)
+        );
+    };
+
+    let save_mode = match options.mode {
+        Mode::DryRun => SaveMode::DryRun,
+        Mode::Commit => SaveMode::Commit,
+    };
+
+    let mut changes = conversion.changes;
+    let report = changes
+        .save(
+            &client,
+            &fields,
+            &items,
+            &mut IssueTypeRegistry::default(),
+            save_mode,
+            &report_progress,
+        )
+        .await?;
+
+    crate::hygiene::print_save_report(&report, &items);
+    crate::hygiene::record_journal(&client, &report, &fields, &items).await;
+    crate::hygiene::record_save_history(&report, &items);
+
+    Ok(())
+}
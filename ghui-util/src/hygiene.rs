@@ -1,17 +1,46 @@
 use github_graphql::{
     client::{
-        graphql::{custom_fields_query::get_fields, get_all_items},
+        graphql::{custom_fields_query::get_fields, get_all_items, get_viewer_info},
         transport::GhCliClient,
     },
-    data::{self, Change, SaveMode},
+    data::{self, Change, Fields, IssueTypeRegistry, SanitizeConfig, SaveMode, SaveReport},
 };
 
-use crate::Result;
+use crate::{hygiene_report::HygieneReport, Result};
+use anyhow::bail;
+use std::path::PathBuf;
+use tokio::sync::OnceCell;
 
+/// For unattended runs (e.g. on a schedule or in CI), combine `commit` with
+/// `--safe-only`, one of the report options and `--max-conflicts`.
 #[derive(Debug, clap::Args)]
 pub struct Options {
     #[arg(value_enum, default_value_t = RunHygieneMode::DryRun)]
     mode: RunHygieneMode,
+
+    /// Only run this sanitize rule (may be repeated)
+    #[arg(short, long = "rule")]
+    rules: Vec<String>,
+
+    /// Sanitize config file (defaults to the one used by the app)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Only save changes from rules marked safe
+    #[arg(long)]
+    safe_only: bool,
+
+    /// Write a JSON report of the run to this file
+    #[arg(long)]
+    json_report: Option<PathBuf>,
+
+    /// Write a Markdown report of the run to this file
+    #[arg(long)]
+    markdown_report: Option<PathBuf>,
+
+    /// Exit with an error if more than this many conflicts are found
+    #[arg(long)]
+    max_conflicts: Option<usize>,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -24,10 +53,16 @@ pub enum RunHygieneMode {
 pub async fn run(options: Options) -> Result {
     let client = crate::client();
 
-    run_hygiene(&client, options.mode).await
+    let config_path = options
+        .config
+        .clone()
+        .unwrap_or_else(ghui_app::get_sanitize_config_path);
+    let config = ghui_app::load_sanitize_config(&config_path)?;
+
+    run_hygiene(&client, &options, &config).await
 }
 
-async fn get_items(client: &GhCliClient) -> Result<data::WorkItems> {
+pub async fn get_items(client: &GhCliClient) -> Result<data::WorkItems> {
     let report_progress = |c, t| println!("Retrieved {c} of {t} items");
     let report_inconsistency = |info: github_graphql::client::graphql::TotalCountInconsistency| {
         eprintln!(
@@ -40,7 +75,14 @@ async fn get_items(client: &GhCliClient) -> Result<data::WorkItems> {
     ))
 }
 
-pub async fn run_hygiene(client: &GhCliClient, mode: RunHygieneMode) -> Result {
+pub async fn run_hygiene(
+    client: &GhCliClient,
+    options: &Options,
+    config: &SanitizeConfig,
+) -> Result {
+    let mode = options.mode;
+    let rules = &options.rules;
+
     let items = match mode {
         RunHygieneMode::TestData => {
             let mut file = std::fs::File::open("all_items.json")?;
@@ -54,8 +96,69 @@ pub async fn run_hygiene(client: &GhCliClient, mode: RunHygieneMode) -> Result {
 
     let fields = get_fields(client).await?;
 
-    let report = items.sanitize(&fields);
-    let mut changes = report.changes;
+    let mut report = items.sanitize_with(&fields, config);
+    if !rules.is_empty() {
+        report.retain_rules(|rule| rules.iter().any(|r| r == rule));
+    }
+
+    for entry in &report.entries {
+        println!(
+            "[{}/{:?}] {} - {}",
+            entry.rule,
+            entry.severity,
+            items
+                .get(&entry.change.work_item_id)
+                .map(|i| i.describe())
+                .unwrap_or(entry.change.work_item_id.0.clone()),
+            entry.change.describe(&fields, &items)
+        );
+    }
+    for conflict in &report.conflicts {
+        let value_name = |data: &data::ChangeData| {
+            Change {
+                work_item_id: conflict.work_item_id.clone(),
+                data: data.clone(),
+            }
+            .new_value_name(&fields, &items)
+            .unwrap_or("<>".to_owned())
+        };
+        println!(
+            "[{}] CONFLICT {} - keeping {} {} ({}, proposed {})",
+            conflict.rule,
+            items
+                .get(&conflict.work_item_id)
+                .map(|i| i.describe())
+                .unwrap_or(conflict.work_item_id.0.clone()),
+            conflict.field,
+            value_name(&conflict.current),
+            conflict.reason,
+            value_name(&conflict.proposed),
+        );
+    }
+
+    for problem in &report.problems {
+        println!(
+            "[{}/{:?}] PROBLEM {} - {}",
+            problem.rule,
+            problem.severity,
+            items
+                .get(&problem.work_item_id)
+                .map(|i| i.describe())
+                .unwrap_or(problem.work_item_id.0.clone()),
+            problem.description
+        );
+    }
+
+    let mut changes = if options.safe_only {
+        let safe_changes = report.safe_changes();
+        let skipped = report.changes.len() - safe_changes.len();
+        if skipped > 0 {
+            println!("{skipped} change(s) from rules not marked safe will not be saved");
+        }
+        safe_changes
+    } else {
+        report.changes.clone()
+    };
 
     let report_progress = |change: &Change, _, _| {
         println!(
@@ -68,14 +171,144 @@ pub async fn run_hygiene(client: &GhCliClient, mode: RunHygieneMode) -> Result {
         );
     };
 
+    let mut issue_types = IssueTypeRegistry::default();
+
+    // Invalid changes are skipped by save; list them up front so dry-runs show
+    // what would be blocked.
+    let diagnostics = changes
+        .validate_with_load(client, &fields, &items, &mut issue_types)
+        .await;
+    for diagnostic in &diagnostics {
+        println!(
+            "INVALID {} - {}: {}",
+            items
+                .get(&diagnostic.change.work_item_id)
+                .map(|i| i.describe())
+                .unwrap_or(diagnostic.change.work_item_id.0.clone()),
+            diagnostic.change.field_name(),
+            diagnostic.problem.describe()
+        );
+    }
+    if !diagnostics.is_empty() {
+        println!("{} invalid change(s) will not be saved", diagnostics.len());
+    }
+
+    // Checked before saving so that an unattended commit saves nothing when
+    // there are too many conflicts to trust it.
+    let too_many_conflicts = options
+        .max_conflicts
+        .filter(|max_conflicts| report.conflicts.len() > *max_conflicts);
+
     let save_mode = match mode {
         RunHygieneMode::DryRun | RunHygieneMode::TestData => SaveMode::DryRun,
+        RunHygieneMode::Commit if too_many_conflicts.is_some() => {
+            println!("Too many conflicts; nothing will be saved");
+            SaveMode::DryRun
+        }
         RunHygieneMode::Commit => SaveMode::Commit,
     };
 
-    changes
-        .save(client, &fields, &items, save_mode, &report_progress)
+    let committed = matches!(save_mode, SaveMode::Commit);
+    let save_report = changes
+        .save(
+            client,
+            &fields,
+            &items,
+            &mut issue_types,
+            save_mode,
+            &report_progress,
+        )
         .await?;
 
+    print_save_report(&save_report, &items);
+    record_journal(client, &save_report, &fields, &items).await;
+    record_save_history(&save_report, &items);
+
+    let hygiene_report = HygieneReport::new(&report, &save_report, committed, &fields, &items);
+    if let Some(path) = &options.json_report {
+        let writer = std::fs::File::create(path)?;
+        serde_json::to_writer_pretty(writer, &hygiene_report)?;
+    }
+    if let Some(path) = &options.markdown_report {
+        std::fs::write(path, hygiene_report.to_markdown())?;
+    }
+
+    if let Some(max_conflicts) = too_many_conflicts {
+        bail!(
+            "{} conflict(s) found, more than the allowed {max_conflicts}",
+            report.conflicts.len()
+        );
+    }
+
     Ok(())
 }
+
+/// Journals the changes that `report` committed.  The login they're recorded
+/// against is fetched the first time there's something to journal.
+pub async fn record_journal(
+    client: &GhCliClient,
+    report: &SaveReport,
+    fields: &Fields,
+    items: &data::WorkItems,
+) {
+    static LOGIN: OnceCell<String> = OnceCell::const_new();
+
+    if report.succeeded_count() == 0 {
+        return;
+    }
+
+    let login = LOGIN
+        .get_or_init(|| async {
+            match get_viewer_info(client).await {
+                Ok(viewer) => viewer.login,
+                Err(error) => {
+                    eprintln!("WARNING: failed to get login for journal: {error}");
+                    "unknown".to_owned()
+                }
+            }
+        })
+        .await;
+    ghui_app::journal::record_save(report, login, fields, items);
+}
+
+/// Records the changes that `report` committed so that they can be reverted
+/// with `ghui-util revert`.
+pub fn record_save_history(report: &SaveReport, items: &data::WorkItems) {
+    match ghui_app::record_save_history(report, items) {
+        Ok((_, Some(number))) => {
+            println!("Recorded as save #{number}; undo it with `ghui-util revert {number}`")
+        }
+        Ok((_, None)) => {}
+        Err(error) => eprintln!("WARNING: failed to record save history: {error}"),
+    }
+}
+
+pub fn print_save_report(report: &SaveReport, items: &data::WorkItems) {
+    if report.results.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{:<8} {:<14} {:<50} OUTCOME", "TIME", "FIELD", "ITEM");
+    for result in &report.results {
+        let item = items
+            .get(&result.change.work_item_id)
+            .map(|i| i.describe())
+            .unwrap_or(result.change.work_item_id.0.clone());
+        println!(
+            "{:<8} {:<14} {:<50} {}",
+            format!("{}ms", result.duration_ms),
+            result.change.field_name(),
+            item,
+            result.outcome.describe()
+        );
+    }
+    println!();
+    println!(
+        "{} succeeded, {} failed, {} skipped in {}ms",
+        report.succeeded_count(),
+        report.failed_count(),
+        report.skipped_count(),
+        report.duration_ms
+    );
+}
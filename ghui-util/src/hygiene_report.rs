@@ -0,0 +1,349 @@
+use github_graphql::data::{
+    Change, ChangeOutcome, Fields, RuleSeverity, SanitizeReport, SaveReport, WorkItemId, WorkItems,
+};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// The result of a hygiene run, written out for CI and scheduled runs.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HygieneReport {
+    /// ISO 8601 UTC timestamp of the run.
+    pub ts: String,
+    /// Whether changes were committed to GitHub.
+    pub committed: bool,
+    pub item_count: usize,
+    pub changes: Vec<ReportChange>,
+    pub conflicts: Vec<ReportConflict>,
+    pub problems: Vec<ReportProblem>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportChange {
+    pub rule: String,
+    pub severity: RuleSeverity,
+    pub safe: bool,
+    pub item: String,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    /// `None` if the change wasn't saved, e.g. because its rule isn't safe.
+    pub outcome: Option<ChangeOutcome>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportConflict {
+    pub rule: String,
+    pub item: String,
+    pub field: String,
+    pub current: Option<String>,
+    pub proposed: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportProblem {
+    pub rule: String,
+    pub severity: RuleSeverity,
+    pub item: String,
+    pub description: String,
+}
+
+impl HygieneReport {
+    pub fn new(
+        sanitize_report: &SanitizeReport,
+        save_report: &SaveReport,
+        committed: bool,
+        fields: &Fields,
+        items: &WorkItems,
+    ) -> Self {
+        let describe =
+            |id: &WorkItemId| items.get(id).map(|i| i.describe()).unwrap_or(id.0.clone());
+
+        let changes = sanitize_report
+            .entries
+            .iter()
+            .map(|entry| ReportChange {
+                rule: entry.rule.clone(),
+                severity: entry.severity,
+                safe: entry.safe,
+                item: describe(&entry.change.work_item_id),
+                field: entry.change.field_name().to_owned(),
+                old_value: entry.change.old_value_name(fields, items),
+                new_value: entry.change.new_value_name(fields, items),
+                outcome: save_report
+                    .results
+                    .iter()
+                    .find(|result| result.change == entry.change)
+                    .map(|result| result.outcome.clone()),
+            })
+            .collect();
+
+        let value_name = |work_item_id: &WorkItemId, data| {
+            Change {
+                work_item_id: work_item_id.clone(),
+                data,
+            }
+            .new_value_name(fields, items)
+        };
+
+        let conflicts = sanitize_report
+            .conflicts
+            .iter()
+            .map(|conflict| ReportConflict {
+                rule: conflict.rule.clone(),
+                item: describe(&conflict.work_item_id),
+                field: conflict.field.clone(),
+                current: value_name(&conflict.work_item_id, conflict.current.clone()),
+                proposed: value_name(&conflict.work_item_id, conflict.proposed.clone()),
+                reason: conflict.reason.clone(),
+            })
+            .collect();
+
+        let problems = sanitize_report
+            .problems
+            .iter()
+            .map(|problem| ReportProblem {
+                rule: problem.rule.clone(),
+                severity: problem.severity,
+                item: describe(&problem.work_item_id),
+                description: problem.description.clone(),
+            })
+            .collect();
+
+        HygieneReport {
+            ts: ghui_app::telemetry::format_iso_timestamp(),
+            committed,
+            item_count: items.work_items.len(),
+            changes,
+            conflicts,
+            problems,
+        }
+    }
+
+    pub fn applied_count(&self) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| matches!(change.outcome, Some(ChangeOutcome::Succeeded)))
+            .count()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        let or_blank = |value: &Option<String>| value.as_deref().map(cell).unwrap_or_default();
+
+        let _ = writeln!(md, "# Hygiene report\n");
+        let _ = writeln!(
+            md,
+            "{} ({}): {} items, {} proposed change(s), {} applied, {} conflict(s), {} problem(s)\n",
+            self.ts,
+            if self.committed { "commit" } else { "dry run" },
+            self.item_count,
+            self.changes.len(),
+            self.applied_count(),
+            self.conflicts.len(),
+            self.problems.len()
+        );
+
+        if !self.changes.is_empty() {
+            let _ = writeln!(md, "## Changes\n");
+            let _ = writeln!(md, "| Rule | Item | Field | From | To | Outcome |");
+            let _ = writeln!(md, "|---|---|---|---|---|---|");
+            for change in &self.changes {
+                let outcome = match &change.outcome {
+                    Some(outcome) => outcome.describe(),
+                    None if !change.safe => "not applied (rule not safe)".to_owned(),
+                    None => "not applied".to_owned(),
+                };
+                let _ = writeln!(
+                    md,
+                    "| {} | {} | {} | {} | {} | {} |",
+                    cell(&change.rule),
+                    cell(&change.item),
+                    cell(&change.field),
+                    or_blank(&change.old_value),
+                    or_blank(&change.new_value),
+                    cell(&outcome)
+                );
+            }
+            let _ = writeln!(md);
+        }
+
+        if !self.conflicts.is_empty() {
+            let _ = writeln!(md, "## Conflicts\n");
+            let _ = writeln!(md, "| Rule | Item | Field | Current | Proposed | Reason |");
+            let _ = writeln!(md, "|---|---|---|---|---|---|");
+            for conflict in &self.conflicts {
+                let _ = writeln!(
+                    md,
+                    "| {} | {} | {} | {} | {} | {} |",
+                    cell(&conflict.rule),
+                    cell(&conflict.item),
+                    cell(&conflict.field),
+                    or_blank(&conflict.current),
+                    or_blank(&conflict.proposed),
+                    cell(&conflict.reason)
+                );
+            }
+            let _ = writeln!(md);
+        }
+
+        if !self.problems.is_empty() {
+            let _ = writeln!(md, "## Problems\n");
+            let _ = writeln!(md, "| Rule | Severity | Item | Problem |");
+            let _ = writeln!(md, "|---|---|---|---|");
+            for problem in &self.problems {
+                let _ = writeln!(
+                    md,
+                    "| {} | {:?} | {} | {} |",
+                    cell(&problem.rule),
+                    problem.severity,
+                    cell(&problem.item),
+                    cell(&problem.description)
+                );
+            }
+        }
+
+        md
+    }
+}
+
+/// Escapes `text` for use in a Markdown table cell, where a `|` would end the
+/// cell and a line break would end the row.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use github_graphql::data::{
+        test_helpers::TestData, ChangeData, ChangeProblem, ChangeResult, SanitizeEntry,
+        SanitizeProblem,
+    };
+
+    fn entry(change: &Change, safe: bool) -> SanitizeEntry {
+        SanitizeEntry {
+            rule: "test-rule".to_owned(),
+            severity: RuleSeverity::Warning,
+            safe,
+            change: change.clone(),
+        }
+    }
+
+    fn result(change: &Change, outcome: ChangeOutcome) -> ChangeResult {
+        ChangeResult {
+            change: change.clone(),
+            outcome,
+            duration_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_report_joins_save_outcomes_to_entries() {
+        let mut data = TestData::default();
+        let closed = data.fields.status.option_id(Some("Closed")).cloned();
+        let mut change = || Change {
+            work_item_id: data.build().status("Active").add(),
+            data: ChangeData::Status(closed.clone()),
+        };
+        let (saved, not_safe, skipped, invalid) = (change(), change(), change(), change());
+
+        let sanitize_report = SanitizeReport {
+            entries: vec![
+                entry(&saved, true),
+                entry(&not_safe, false),
+                entry(&skipped, true),
+                entry(&invalid, true),
+            ],
+            ..Default::default()
+        };
+        let save_report = SaveReport {
+            results: vec![
+                result(
+                    &invalid,
+                    ChangeOutcome::Invalid {
+                        problem: ChangeProblem::WorkItemNotFound,
+                    },
+                ),
+                result(&skipped, ChangeOutcome::SkippedConnectivity),
+                result(&saved, ChangeOutcome::Succeeded),
+            ],
+            ..Default::default()
+        };
+
+        let report = HygieneReport::new(
+            &sanitize_report,
+            &save_report,
+            true,
+            &data.fields,
+            &data.work_items,
+        );
+
+        let outcomes: Vec<_> = report.changes.iter().map(|c| c.outcome.clone()).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                Some(ChangeOutcome::Succeeded),
+                None,
+                Some(ChangeOutcome::SkippedConnectivity),
+                Some(ChangeOutcome::Invalid {
+                    problem: ChangeProblem::WorkItemNotFound
+                }),
+            ]
+        );
+        assert_eq!(report.changes[0].old_value.as_deref(), Some("Active"));
+        assert_eq!(report.changes[0].new_value.as_deref(), Some("Closed"));
+        assert_eq!(report.applied_count(), 1);
+
+        let md = report.to_markdown();
+        assert!(md.contains("| succeeded |"), "{md}");
+        assert!(md.contains("| not applied (rule not safe) |"), "{md}");
+        assert!(md.contains("| skipped (network unavailable) |"), "{md}");
+        assert!(
+            md.contains("| invalid: work item is not in the project |"),
+            "{md}"
+        );
+        assert!(md.contains("4 proposed change(s), 1 applied"), "{md}");
+    }
+
+    #[test]
+    fn test_markdown_escapes_table_cells() {
+        let mut data = TestData::default();
+        let id = data.build().add();
+
+        let sanitize_report = SanitizeReport {
+            problems: vec![SanitizeProblem {
+                work_item_id: id,
+                rule: "test-rule".to_owned(),
+                severity: RuleSeverity::Error,
+                description: "missing Kind | Priority\nand more".to_owned(),
+            }],
+            ..Default::default()
+        };
+
+        let report = HygieneReport::new(
+            &sanitize_report,
+            &SaveReport::default(),
+            false,
+            &data.fields,
+            &data.work_items,
+        );
+        let md = report.to_markdown();
+
+        assert!(
+            md.contains("| missing Kind \\| Priority and more |"),
+            "{md}"
+        );
+        let row_cells = md
+            .lines()
+            .find(|line| line.contains("missing Kind"))
+            .unwrap()
+            .split(" | ")
+            .count();
+        assert_eq!(row_cells, 4, "{md}");
+    }
+}
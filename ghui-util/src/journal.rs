@@ -0,0 +1,53 @@
+use crate::Result;
+use ghui_app::journal::{self, JournalQuery};
+
+#[derive(Debug, clap::Args)]
+pub struct Options {
+    /// Only show changes made on or after this date (e.g. 2026-03-01)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Only show changes made on or before this date
+    #[arg(long)]
+    until: Option<String>,
+
+    /// Only show changes to items whose URL or title contains this text
+    #[arg(short, long)]
+    item: Option<String>,
+
+    /// Only show changes to this field (e.g. epic, status)
+    #[arg(short, long)]
+    field: Option<String>,
+}
+
+pub fn run(options: Options) -> Result {
+    let query = JournalQuery {
+        since: options.since,
+        until: options.until,
+        item: options.item,
+        field: options.field,
+    };
+
+    let path = journal::get_journal_file_path();
+    let entries: Vec<_> = journal::read(&path)?
+        .into_iter()
+        .filter(|entry| query.matches(entry))
+        .collect();
+
+    for entry in &entries {
+        println!(
+            "{} {} {} {} ({}): {} -> {}",
+            entry.ts,
+            entry.login,
+            entry.field,
+            entry.item,
+            entry.title,
+            entry.old_value.as_deref().unwrap_or("<none>"),
+            entry.new_value.as_deref().unwrap_or("<none>"),
+        );
+    }
+
+    println!("{} change(s) in {}", entries.len(), path.display());
+
+    Ok(())
+}
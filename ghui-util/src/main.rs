@@ -18,7 +18,12 @@ enum Commands {
     GetAllItems,
     Viewer,
     Hygiene(hygiene::Options),
+    Check(check::Options),
+    ConvertTracked(convert_tracked::Options),
     AddItems(add_items::Options),
+    Journal(journal::Options),
+    Query(query::Options),
+    Revert(revert::Options),
 }
 
 type Result<T = ()> = core::result::Result<T, anyhow::Error>;
@@ -33,7 +38,12 @@ async fn main() -> Result {
         Commands::GetAllItems => run_get_all_items().await,
         Commands::Viewer => run_get_viewer().await,
         Commands::Hygiene(options) => hygiene::run(options).await,
+        Commands::Check(options) => check::run(options).await,
+        Commands::ConvertTracked(options) => convert_tracked::run(options).await,
         Commands::AddItems(options) => add_items::run(options).await,
+        Commands::Journal(options) => journal::run(options),
+        Commands::Query(options) => query::run(options).await,
+        Commands::Revert(options) => revert::run(options).await,
     }
 }
 
@@ -68,7 +78,13 @@ async fn run_get_viewer() -> Result {
 }
 
 mod add_items;
+mod check;
+mod convert_tracked;
 mod hygiene;
+mod hygiene_report;
+mod journal;
+mod query;
+mod revert;
 
 pub fn client() -> GhCliClient {
     GhCliClient::default()
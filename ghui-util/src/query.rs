@@ -0,0 +1,61 @@
+use crate::Result;
+use github_graphql::{
+    client::graphql::{custom_fields_query::get_fields, get_viewer_info},
+    data,
+    query::{parse_query, QueryContext},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Options {
+    /// Filter expression, e.g. 'epic:"DML Demo" AND NOT status:Done'
+    query: String,
+
+    /// Query the items in all_items.json rather than fetching them
+    #[arg(long)]
+    test_data: bool,
+
+    /// Login that @me refers to (defaults to the signed in user)
+    #[arg(long)]
+    viewer: Option<String>,
+}
+
+pub async fn run(options: Options) -> Result {
+    let query = parse_query(&options.query)?;
+    let client = crate::client();
+
+    let items = if options.test_data {
+        let mut file = std::fs::File::open("all_items.json")?;
+        let work_items: Vec<data::WorkItem> = serde_json::from_reader(&mut file)?;
+        data::WorkItems::from_iter(work_items)
+    } else {
+        crate::hygiene::get_items(&client).await?
+    };
+
+    let fields = get_fields(&client).await?;
+
+    let viewer = match options.viewer {
+        Some(viewer) => Some(viewer),
+        None if query.uses_viewer() => Some(get_viewer_info(&client).await?.login),
+        None => None,
+    };
+
+    let context = QueryContext {
+        fields: &fields,
+        viewer: viewer.as_deref(),
+    };
+
+    let mut count = 0;
+    for item in items.iter().filter_map(|id| items.get(id)) {
+        if query.matches(item, &context) {
+            println!("{} - {}", item.describe(), item.title);
+            count += 1;
+        }
+    }
+
+    println!(
+        "{count} of {} item(s) match {query}",
+        items.work_items.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::Result;
+use anyhow::anyhow;
+use github_graphql::{
+    client::graphql::custom_fields_query::get_fields,
+    data::{Change, IssueTypeRegistry, SaveMode},
+};
+
+/// Reverts a save made by ghui-util or the app, by saving the changes that
+/// restore the values it overwrote.
+#[derive(Debug, clap::Args)]
+pub struct Options {
+    /// Number of the save to revert, as printed when it was saved
+    save: usize,
+
+    #[arg(value_enum, default_value_t = Mode::DryRun)]
+    mode: Mode,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mode {
+    DryRun,
+    Commit,
+}
+
+pub async fn run(options: Options) -> Result {
+    let history = ghui_app::load_save_history()?;
+    let number = options.save;
+    let record = history
+        .get(number)
+        .ok_or_else(|| anyhow!("Save #{number} not found"))?;
+
+    let skipped = record
+        .changes
+        .iter()
+        .filter(|c| c.inverse.is_none())
+        .count();
+    if skipped > 0 {
+        println!("{skipped} change(s) can't be reverted because their old values weren't known");
+    }
+
+    let client = crate::client();
+    let items = crate::hygiene::get_items(&client).await?;
+    let fields = get_fields(&client).await?;
+
+    let report_progress = |change: &Change, _, _| {
+        println!(
+            "{} - {}",
+            items
+                .get(&change.work_item_id)
+                .map(|i| i.describe())
+                .unwrap_or(change.work_item_id.0.clone()),
+            change.describe(&fields, &items)
+        );
+    };
+
+    let save_mode = match options.mode {
+        Mode::DryRun => SaveMode::DryRun,
+        Mode::Commit => SaveMode::Commit,
+    };
+
+    let mut changes = record.revert_changes();
+    let report = changes
+        .save(
+            &client,
+            &fields,
+            &items,
+            &mut IssueTypeRegistry::default(),
+            save_mode,
+            &report_progress,
+        )
+        .await?;
+
+    crate::hygiene::print_save_report(&report, &items);
+    crate::hygiene::record_journal(&client, &report, &fields, &items).await;
+    crate::hygiene::record_save_history(&report, &items);
+
+    Ok(())
+}
@@ -20,8 +20,8 @@ type BigInt = String;
 
 pub mod mutators;
 pub use mutators::{
-    add_sub_issue, add_to_project, clear_project_field_value, set_issue_type,
-    set_project_field_value,
+    add_sub_issue, add_to_project, clear_project_field_value, delete_project_item,
+    remove_sub_issue, set_issue_type, set_project_field_value,
 };
 
 pub mod custom_fields_query;
@@ -33,6 +33,7 @@ pub use check_scopes::{check_project_access, ProjectAccess};
 pub mod get_issue_types;
 
 pub mod get_all_items;
+pub mod get_item_text;
 pub mod get_items;
 pub use get_all_items::{get_all_items, TotalCountInconsistency};
 
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use get_issue_types::GetIssueTypesRepositoryIssueTypesNodes;
 use graphql_client::{GraphQLQuery, Response};
+use serde::{Deserialize, Serialize};
 
 use crate::{client::transport::Client, Error, Result};
 
@@ -33,10 +34,10 @@ pub async fn get_repo_issue_types(
         ))
 }
 
-#[derive(Default, Debug, Eq, PartialEq, Hash)]
+#[derive(Default, Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct IssueTypeId(pub String);
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct IssueTypes {
     pub id_to_name: HashMap<IssueTypeId, String>,
     pub name_to_id: HashMap<String, IssueTypeId>,
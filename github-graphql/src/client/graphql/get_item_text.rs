@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use super::get_items::MAX_NODE_IDS;
+use crate::client::transport::Client;
+use crate::data::WorkItemId;
+use crate::{Error, Result};
+use futures::future::try_join_all;
+use graphql_client::{GraphQLQuery, Response};
+
+gql!(GetItemText, "src/client/graphql/get_item_text.graphql");
+use get_item_text::*;
+
+/// The body and latest comments of a work item.  `get_items` leaves these
+/// out: only search needs them, and they'd make fetching every item slower.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WorkItemText {
+    pub body: String,
+    /// The most recent comments, oldest first.
+    pub comments: Vec<String>,
+}
+
+pub async fn get_work_item_text(
+    client: &impl Client,
+    ids: Vec<WorkItemId>,
+) -> Result<HashMap<WorkItemId, WorkItemText>> {
+    let batch_futures = ids.chunks(MAX_NODE_IDS).map(|chunk| {
+        let client = client.clone();
+        let chunk = chunk.to_vec();
+        async move { get_work_item_text_single_batch(&client, chunk).await }
+    });
+
+    let mut texts = HashMap::new();
+    for batch in try_join_all(batch_futures).await? {
+        texts.extend(batch);
+    }
+    Ok(texts)
+}
+
+async fn get_work_item_text_single_batch(
+    client: &impl Client,
+    ids: Vec<WorkItemId>,
+) -> Result<Vec<(WorkItemId, WorkItemText)>> {
+    let request_body = GetItemText::build_query(get_item_text::Variables {
+        ids: ids.into_iter().map(|id| id.0).collect(),
+    });
+
+    let response: Response<get_item_text::ResponseData> = client.request(&request_body).await?;
+
+    if let Some(errors) = response.errors {
+        Err(Error::GraphQlResponseErrors(errors))?;
+    }
+
+    let nodes = response
+        .data
+        .map(|d| d.nodes)
+        .into_iter()
+        .flatten()
+        .flatten();
+
+    Ok(nodes
+        .filter_map(|node| match node {
+            ItemText::DraftIssue(d) => Some((
+                WorkItemId(d.id),
+                WorkItemText {
+                    body: d.body,
+                    comments: Vec::new(),
+                },
+            )),
+            ItemText::Issue(d) => Some((
+                WorkItemId(d.id),
+                WorkItemText {
+                    body: d.body,
+                    comments: comment_bodies(d.comments),
+                },
+            )),
+            ItemText::PullRequest(d) => Some((
+                WorkItemId(d.id),
+                WorkItemText {
+                    body: d.body,
+                    comments: comment_bodies(d.comments),
+                },
+            )),
+            _ => None,
+        })
+        .collect())
+}
+
+fn comment_bodies(comments: Comments) -> Vec<String> {
+    comments
+        .nodes
+        .unwrap_or_else(Vec::new)
+        .into_iter()
+        .flat_map(|node| node.map(|node| node.body))
+        .collect()
+}
@@ -12,7 +12,7 @@ gql!(GetItems, "src/client/graphql/get_items.graphql");
 pub use get_items::*;
 
 /// GitHub's GraphQL API limits the number of node IDs per `nodes(ids:)` query.
-const MAX_NODE_IDS: usize = 100;
+pub(crate) const MAX_NODE_IDS: usize = 100;
 
 pub async fn get_items(
     client: &impl Client,
@@ -91,8 +91,11 @@ async fn work_item(
             id: WorkItemId(d.id),
             title: d.title,
             updated_at: d.updated_at,
+            created_at: d.created_at,
             resource_path: None,
             repo_name_with_owner: None,
+            body: DelayLoad::NotLoaded,
+            comments: DelayLoad::NotLoaded,
             data: WorkItemData::DraftIssue,
         },
         ItemOnProjectV2ItemContent::Issue(d) => WorkItem {
@@ -100,8 +103,11 @@ async fn work_item(
             id: WorkItemId(d.id.clone()),
             title: d.title,
             updated_at: d.updated_at,
+            created_at: d.created_at,
             resource_path: d.resource_path.into(),
             repo_name_with_owner: d.repository.name_with_owner.into(),
+            body: DelayLoad::NotLoaded,
+            comments: DelayLoad::NotLoaded,
             data: WorkItemData::Issue(Issue {
                 parent_id: d.parent.map(|parent| WorkItemId(parent.id)),
                 issue_type: d.issue_type.map(|t| t.name).into(),
@@ -135,8 +141,11 @@ async fn work_item(
             id: WorkItemId(d.id),
             title: d.title,
             updated_at: d.updated_at,
+            created_at: d.created_at,
             resource_path: d.resource_path.into(),
             repo_name_with_owner: d.repository.name_with_owner.into(),
+            body: DelayLoad::NotLoaded,
+            comments: DelayLoad::NotLoaded,
             data: WorkItemData::PullRequest(PullRequest {
                 state: d.pull_request_state.into(),
                 assignees: d
@@ -146,6 +155,14 @@ async fn work_item(
                     .into_iter()
                     .flat_map(|node| node.map(|node| node.login))
                     .collect(),
+                linked_issues: d
+                    .closing_issues_references
+                    .and_then(|references| references.nodes)
+                    .unwrap_or_else(Vec::new)
+                    .into_iter()
+                    .flat_map(|node| node.map(|node| WorkItemId(node.id)))
+                    .collect::<Vec<_>>()
+                    .into(),
             }),
         },
     })
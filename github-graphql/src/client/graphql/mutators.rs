@@ -28,6 +28,30 @@ pub async fn add_sub_issue(client: &impl Client, issue_id: &str, sub_issue_id: &
     Ok(())
 }
 
+gql!(
+    RemoveSubIssue,
+    "src/client/graphql/remove_sub_issue.graphql"
+);
+
+pub async fn remove_sub_issue(client: &impl Client, issue_id: &str, sub_issue_id: &str) -> Result {
+    use remove_sub_issue::*;
+
+    let variables = Variables {
+        issue_id: issue_id.to_owned(),
+        sub_issue_id: sub_issue_id.to_owned(),
+    };
+
+    let request_body = RemoveSubIssue::build_query(variables);
+
+    let response: Response<ResponseData> = client.request(&request_body).await?;
+
+    if let Some(errors) = response.errors {
+        Err(Error::GraphQlResponseErrors(errors))?;
+    }
+
+    Ok(())
+}
+
 gql!(AddToProject, "src/client/graphql/add_to_project.graphql");
 
 pub async fn add_to_project(
@@ -60,6 +84,34 @@ pub async fn add_to_project(
         ))
 }
 
+gql!(
+    DeleteProjectItem,
+    "src/client/graphql/delete_project_item.graphql"
+);
+
+pub async fn delete_project_item(
+    client: &impl Client,
+    project_id: &str,
+    item_id: &ProjectItemId,
+) -> Result {
+    use delete_project_item::*;
+
+    let variables = Variables {
+        project_id: project_id.to_owned(),
+        item_id: item_id.0.to_owned(),
+    };
+
+    let request_body = DeleteProjectItem::build_query(variables);
+
+    let response: Response<ResponseData> = client.request(&request_body).await?;
+
+    if let Some(errors) = response.errors {
+        Err(Error::GraphQlResponseErrors(errors))?;
+    }
+
+    Ok(())
+}
+
 gql!(
     ClearProjectFieldValue,
     "src/client/graphql/clear_project_field_value.graphql"
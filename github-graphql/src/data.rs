@@ -9,12 +9,33 @@ pub use work_item::*;
 mod work_items;
 pub use work_items::*;
 
+mod sanitize;
+pub use sanitize::*;
+
 mod changes;
 pub use changes::*;
 
 mod custom_fields;
 pub use custom_fields::*;
 
+mod issue_types;
+pub use issue_types::*;
+
+mod validation;
+pub use validation::*;
+
+mod save_report;
+pub use save_report::*;
+
+mod revert;
+pub use revert::*;
+
+mod integrity;
+pub use integrity::*;
+
+mod convert_tracked;
+pub use convert_tracked::*;
+
 pub mod test_helpers;
 
 #[cfg(test)]
@@ -1,22 +1,25 @@
 use super::{
-    Field, FieldOptionId, Fields, Issue, ProjectItemId, Result, WorkItem, WorkItemData, WorkItemId,
+    ChangeOutcome, ChangeProblem, ChangeResult, DelayLoad, Field, FieldOptionId, Fields, Issue,
+    IssueTypeRegistry, ProjectItemId, Result, SaveReport, WorkItem, WorkItemData, WorkItemId,
     WorkItems,
 };
 use crate::{
     client::{
         graphql::{
-            add_sub_issue, add_to_project, clear_project_field_value, get_issue_types,
-            mutators::SettableProjectFieldValue, set_issue_type, set_project_field_value,
+            add_sub_issue, add_to_project, clear_project_field_value, delete_project_item,
+            mutators::SettableProjectFieldValue, remove_sub_issue, set_issue_type,
+            set_project_field_value,
         },
         transport::Client,
     },
     Error,
 };
-use log::{debug, warn};
+use log::warn;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{hash_map, HashMap, HashSet},
     mem::{take, Discriminant},
+    time::Instant,
 };
 use ts_rs::TS;
 
@@ -214,34 +217,83 @@ impl Changes {
 
     /// Saves all pending changes via the GitHub API.
     ///
-    /// Returns a list of `ProjectItemId`s that need to be re-fetched from GitHub
-    /// to update the local state. This includes both items that were modified
-    /// in-place (looked up from `work_items`) and items that were newly added
-    /// to the project (captured directly from the `add_to_project` mutation).
+    /// Returns a [`SaveReport`] with the outcome of every change.  Changes that
+    /// were not saved successfully are left queued in `self`.
+    ///
+    /// The report's `project_item_ids` lists the items that need to be
+    /// re-fetched from GitHub to update the local state. This includes both
+    /// items that were modified in-place (looked up from `work_items`) and
+    /// items that were newly added to the project (captured directly from the
+    /// `add_to_project` mutation).
     ///
     /// `AddToProject` changes are always processed first so that field changes
     /// (e.g. Epic, Workstream) for the same newly-added item can look up the
-    /// freshly-created `ProjectItemId` and save correctly.
+    /// freshly-created `ProjectItemId` and save correctly.  If adding an item
+    /// fails, the other changes that depend on it are skipped.
+    ///
+    /// Issue types are looked up in `issue_types`, which is populated on demand
+    /// so each repository is queried at most once.
+    ///
+    /// Changes that fail [`Changes::validate_with_load`] are skipped and left
+    /// queued.
     pub async fn save(
         &mut self,
         client: &impl Client,
         fields: &Fields,
         work_items: &WorkItems,
+        issue_types: &mut IssueTypeRegistry,
         mode: SaveMode,
         report_progress: &impl Fn(&Change, usize, usize),
-    ) -> Result<Vec<ProjectItemId>> {
+    ) -> Result<SaveReport> {
+        let started = Instant::now();
+        let mut results = Vec::new();
+
+        // Changes that fail validation are never sent to GitHub; they stay
+        // queued so the user can fix or discard them.
+        let invalid: HashMap<ChangeKey, ChangeProblem> = self
+            .validate_with_load(client, fields, work_items, issue_types)
+            .await
+            .into_iter()
+            .map(|diagnostic| {
+                warn!(
+                    "not saving {:?}: {}",
+                    diagnostic.change,
+                    diagnostic.problem.describe()
+                );
+                (diagnostic.change.key(), diagnostic.problem)
+            })
+            .collect();
+
         let data = take(&mut self.data);
         let mut changed_work_items = HashSet::new();
         let mut project_item_ids = Vec::new();
-
-        let change_count = data.len();
+        let mut removed_work_item_ids = Vec::new();
+
+        let mut sorted = Vec::new();
+        for (key, change) in data {
+            if let Some(problem) = invalid.get(&key) {
+                results.push(ChangeResult {
+                    change: change.clone(),
+                    outcome: ChangeOutcome::Invalid {
+                        problem: problem.clone(),
+                    },
+                    duration_ms: 0,
+                });
+                self.data.insert(key, change);
+            } else {
+                sorted.push((key, change));
+            }
+        }
+        let change_count = sorted.len();
 
         // Sort so AddToProject changes always run first.  This ensures that any
         // field changes (Epic, Workstream, …) for a newly-added item can find
-        // the ProjectItemId that AddToProject just created.
-        let mut sorted: Vec<_> = data.into_iter().collect();
+        // the ProjectItemId that AddToProject just created.  RemoveFromProject
+        // runs last, since other changes to the item need it to still be in
+        // the project.
         sorted.sort_by_key(|(_, change)| match &change.data {
             ChangeData::AddToProject => 0,
+            ChangeData::RemoveFromProject => 2,
             _ => 1,
         });
 
@@ -249,14 +301,36 @@ impl Changes {
         // pass so that subsequent field saves for those items can find the id.
         let mut new_project_items: HashMap<WorkItemId, ProjectItemId> = HashMap::new();
 
+        // Items whose AddToProject failed in this pass; no other change for
+        // them (or parenting onto them) can succeed.
+        let mut failed_additions: HashSet<WorkItemId> = HashSet::new();
+
         let mut changes = sorted.into_iter().enumerate();
         while let Some((change_number, (key, change))) = changes.next() {
+            let change_started = Instant::now();
+
+            if let Some(dependency) = change.failed_dependency(&failed_additions) {
+                warn!(
+                    "skipping {:?}: adding {} to the project failed",
+                    change.key(),
+                    dependency.0
+                );
+                let outcome = ChangeOutcome::SkippedDependency {
+                    dependency: dependency.clone(),
+                };
+                report_progress(&change, change_number, change_count);
+                results.push(ChangeResult::new(change.clone(), outcome, change_started));
+                self.data.insert(key, change);
+                continue;
+            }
+
             let result = if let SaveMode::Commit = mode {
                 let result = change
                     .save(
                         client,
                         fields,
                         work_items,
+                        issue_types,
                         &new_project_items,
                         &mut project_item_ids,
                     )
@@ -280,6 +354,14 @@ impl Changes {
 
             if let Err(e) = result {
                 warn!("save for {:?} failed {e:?}", change.key());
+                if let ChangeData::AddToProject = change.data {
+                    failed_additions.insert(change.work_item_id.clone());
+                }
+                results.push(ChangeResult::new(
+                    change.clone(),
+                    ChangeOutcome::failed(&e),
+                    change_started,
+                ));
                 self.data.insert(key, change);
 
                 // If the network is down, every remaining change will hit the
@@ -294,15 +376,34 @@ impl Changes {
                         );
                     }
                     for (_, (key, change)) in changes {
+                        results.push(ChangeResult {
+                            change: change.clone(),
+                            outcome: ChangeOutcome::SkippedConnectivity,
+                            duration_ms: 0,
+                        });
                         self.data.insert(key, change);
                     }
                     break;
                 }
             } else {
-                changed_work_items.insert(change.work_item_id);
+                let outcome = match mode {
+                    SaveMode::Commit => ChangeOutcome::Succeeded,
+                    SaveMode::DryRun => ChangeOutcome::DryRun,
+                };
+                results.push(ChangeResult::new(change.clone(), outcome, change_started));
+                if let (SaveMode::Commit, ChangeData::RemoveFromProject) = (&mode, &change.data) {
+                    removed_work_item_ids.push(change.work_item_id);
+                } else {
+                    changed_work_items.insert(change.work_item_id);
+                }
             }
         }
 
+        // Items that are no longer in the project can't be re-fetched.
+        for work_item_id in &removed_work_item_ids {
+            changed_work_items.remove(work_item_id);
+        }
+
         // Convert changed WorkItemIds to ProjectItemIds for items already in
         // the project. Items from AddToProject mutations are already captured
         // directly in project_item_ids by Change::save().
@@ -315,7 +416,12 @@ impl Changes {
         // Deduplicate to avoid redundant fetches when multiple changes touch
         // the same item or an AddToProject item is also resolvable via work_items.
         let unique: HashSet<ProjectItemId> = project_item_ids.into_iter().collect();
-        Ok(unique.into_iter().collect())
+        Ok(SaveReport {
+            results,
+            duration_ms: started.elapsed().as_millis() as u64,
+            project_item_ids: unique.into_iter().collect(),
+            removed_work_item_ids,
+        })
     }
 }
 
@@ -331,6 +437,20 @@ fn is_connectivity_error(error: &Error) -> bool {
 }
 
 impl Change {
+    /// Returns the item whose failed `AddToProject` prevents this change from
+    /// being saved, if any.
+    fn failed_dependency<'a>(
+        &'a self,
+        failed_additions: &HashSet<WorkItemId>,
+    ) -> Option<&'a WorkItemId> {
+        match &self.data {
+            ChangeData::AddToProject => None,
+            _ if failed_additions.contains(&self.work_item_id) => Some(&self.work_item_id),
+            ChangeData::SetParent(parent) if failed_additions.contains(parent) => Some(parent),
+            _ => None,
+        }
+    }
+
     /// Saves this change via the GitHub API.
     ///
     /// Returns `(changed_work_item_ids, Option<(WorkItemId, ProjectItemId)>)`.
@@ -341,6 +461,7 @@ impl Change {
         client: &impl Client,
         fields: &Fields,
         work_items: &WorkItems,
+        issue_types: &mut IssueTypeRegistry,
         new_project_items: &HashMap<WorkItemId, ProjectItemId>,
         project_item_ids: &mut Vec<ProjectItemId>,
     ) -> Result<(Vec<WorkItemId>, Option<(WorkItemId, ProjectItemId)>)> {
@@ -349,7 +470,10 @@ impl Change {
         let mut new_item = None;
 
         match &self.data {
-            ChangeData::IssueType(value) => self.set_issue_type(client, work_items, value).await?,
+            ChangeData::IssueType(value) => {
+                self.set_issue_type(client, work_items, issue_types, value)
+                    .await?
+            }
             ChangeData::Status(value) => {
                 self.save_field(
                     client,
@@ -450,12 +574,32 @@ impl Change {
 
                 changed_items.push(new_parent.clone());
             }
+            ChangeData::RemoveParent(parent) => {
+                remove_sub_issue(client, &parent.0, &self.work_item_id.0).await?;
+                changed_items.push(parent.clone());
+            }
             ChangeData::AddToProject => {
                 let project_item_id =
                     add_to_project(client, &fields.project_id, &self.work_item_id.0).await?;
                 project_item_ids.push(project_item_id.clone());
                 new_item = Some((self.work_item_id.clone(), project_item_id));
             }
+            ChangeData::RemoveFromProject => {
+                let Some(work_item) = work_items.get(&self.work_item_id) else {
+                    return Err(Error::UnexpectedData(format!(
+                        "Work item {:?} is not in the project",
+                        self.work_item_id
+                    )));
+                };
+                delete_project_item(client, &fields.project_id, &work_item.project_item.id).await?;
+                changed_items.clear();
+
+                // The item's parent and sub-issues are unaffected on GitHub,
+                // but they may now appear differently in the project.
+                if let Some(parent_id) = work_item.get_parent() {
+                    changed_items.push(parent_id.clone());
+                }
+            }
         }
 
         Ok((changed_items, new_item))
@@ -503,17 +647,23 @@ impl Change {
         &self,
         client: &impl Client,
         work_items: &WorkItems,
+        issue_types: &mut IssueTypeRegistry,
         value: &Option<String>,
     ) -> Result<()> {
         if let Some(work_item) = work_items.get(&self.work_item_id) {
             if let Some((owner, name)) = work_item.get_repository_info() {
-                debug!("TODO: cache issue types somehow, don't request for each change!");
-                let issue_types =
-                    get_issue_types::get_repo_issue_types(client, &owner, &name).await?;
-
-                let issue_type_id = value
-                    .as_ref()
-                    .and_then(|issue_type| issue_types.name_to_id.get(issue_type));
+                let issue_types = issue_types.get_or_load(client, &owner, &name).await?;
+
+                let issue_type_id = match value {
+                    Some(issue_type) => {
+                        Some(issue_types.name_to_id.get(issue_type).ok_or_else(|| {
+                            Error::UnexpectedData(format!(
+                                "Issue type '{issue_type}' does not exist in {owner}/{name}"
+                            ))
+                        })?)
+                    }
+                    None => None,
+                };
 
                 set_issue_type(
                     client,
@@ -573,7 +723,10 @@ pub enum ChangeData {
     Estimate(Option<FieldOptionId>),
     Priority(Option<FieldOptionId>),
     SetParent(WorkItemId),
+    /// Removes the item from the given parent, leaving it without one.
+    RemoveParent(WorkItemId),
     AddToProject,
+    RemoveFromProject,
 }
 
 impl Change {
@@ -585,44 +738,9 @@ impl Change {
     }
 
     pub fn describe(&self, fields: &Fields, work_items: &WorkItems) -> String {
-        let work_item = work_items.get(&self.work_item_id).unwrap();
-
-        let old_value = match self.data {
-            ChangeData::IssueType(_) => match &work_item.data {
-                WorkItemData::Issue(issue) => issue.issue_type.expect_loaded().as_deref(),
-                _ => None,
-            },
-            ChangeData::Status(_) => fields
-                .status
-                .option_name(work_item.project_item.status.as_ref()),
-            ChangeData::Blocked(_) => fields
-                .blocked
-                .option_name(work_item.project_item.blocked.expect_loaded().as_ref()),
-            ChangeData::Epic(_) => fields
-                .epic
-                .option_name(work_item.project_item.epic.as_ref()),
-            ChangeData::Iteration(_) => fields
-                .iteration
-                .option_name(work_item.project_item.iteration.expect_loaded().as_ref()),
-            ChangeData::Kind(_) => fields
-                .iteration
-                .option_name(work_item.project_item.kind.expect_loaded().as_ref()),
-            ChangeData::Workstream(_) => fields
-                .workstream
-                .option_name(work_item.project_item.workstream.expect_loaded().as_ref()),
-            ChangeData::Estimate(_) => fields
-                .estimate
-                .option_name(work_item.project_item.kind.expect_loaded().as_ref()),
-            ChangeData::Priority(_) => fields
-                .priority
-                .option_name(work_item.project_item.kind.expect_loaded().as_ref()),
-            ChangeData::SetParent(_) => match &work_item.data {
-                WorkItemData::Issue(issue) => issue.parent_id.as_ref().map(|v| v.0.as_str()),
-                _ => None,
-            },
-            ChangeData::AddToProject => None,
-        }
-        .unwrap_or("<>");
+        let old_value = self
+            .old_value_name(fields, work_items)
+            .unwrap_or("<>".to_owned());
 
         let name = match self.data {
             ChangeData::IssueType(_) => "IssueType",
@@ -635,25 +753,75 @@ impl Change {
             ChangeData::Estimate(_) => "Estimate",
             ChangeData::Priority(_) => "Priority",
             ChangeData::SetParent(_) => "SetParent",
+            ChangeData::RemoveParent(_) => "RemoveParent",
             ChangeData::AddToProject => "AddToProject",
+            ChangeData::RemoveFromProject => "RemoveFromProject",
         };
 
-        let new_value = match &self.data {
-            ChangeData::IssueType(value) => value.as_ref().map(|v| v.as_str()),
-            ChangeData::Status(value) => fields.status.option_name(value.as_ref()),
-            ChangeData::Blocked(value) => fields.blocked.option_name(value.as_ref()),
-            ChangeData::Epic(value) => fields.epic.option_name(value.as_ref()),
-            ChangeData::Iteration(value) => fields.iteration.option_name(value.as_ref()),
-            ChangeData::Kind(value) => fields.kind.option_name(value.as_ref()),
-            ChangeData::Workstream(value) => fields.workstream.option_name(value.as_ref()),
-            ChangeData::Estimate(value) => fields.estimate.option_name(value.as_ref()),
-            ChangeData::Priority(value) => fields.priority.option_name(value.as_ref()),
-            ChangeData::SetParent(value) => Some(value.0.as_str()),
-            ChangeData::AddToProject => None,
+        let new_value = self
+            .new_value_name(fields, work_items)
+            .unwrap_or("<>".to_owned());
+
+        format!("{name}({old_value} -> {new_value})")
+    }
+
+    /// Returns the human-readable value that this change will overwrite, or
+    /// `None` if the field is currently unset or not loaded.
+    pub fn old_value_name(&self, fields: &Fields, work_items: &WorkItems) -> Option<String> {
+        let work_item = work_items.get(&self.work_item_id)?;
+        let project_item = &work_item.project_item;
+
+        match self.data {
+            ChangeData::IssueType(_) => match &work_item.data {
+                WorkItemData::Issue(issue) => match &issue.issue_type {
+                    DelayLoad::Loaded(issue_type) => issue_type.clone(),
+                    DelayLoad::NotLoaded => None,
+                },
+                _ => None,
+            },
+            ChangeData::Status(_) => option_name(&fields.status, project_item.status.as_ref()),
+            ChangeData::Blocked(_) => {
+                option_name(&fields.blocked, project_item.blocked.flatten().as_ref())
+            }
+            ChangeData::Epic(_) => option_name(&fields.epic, project_item.epic.as_ref()),
+            ChangeData::Iteration(_) => {
+                option_name(&fields.iteration, project_item.iteration.flatten().as_ref())
+            }
+            ChangeData::Kind(_) => option_name(&fields.kind, project_item.kind.flatten().as_ref()),
+            ChangeData::Workstream(_) => option_name(
+                &fields.workstream,
+                project_item.workstream.flatten().as_ref(),
+            ),
+            ChangeData::Estimate(_) => {
+                option_name(&fields.estimate, project_item.estimate.as_ref())
+            }
+            ChangeData::Priority(_) => {
+                option_name(&fields.priority, project_item.priority.as_ref())
+            }
+            ChangeData::SetParent(_) | ChangeData::RemoveParent(_) => work_item
+                .get_parent()
+                .map(|parent_id| describe_work_item(parent_id, work_items)),
+            ChangeData::AddToProject | ChangeData::RemoveFromProject => None,
         }
-        .unwrap_or("<>");
+    }
 
-        format!("{name}({old_value} -> {new_value})").to_owned()
+    /// Returns the human-readable value that this change sets, or `None` if it
+    /// clears the field.
+    pub fn new_value_name(&self, fields: &Fields, work_items: &WorkItems) -> Option<String> {
+        match &self.data {
+            ChangeData::IssueType(value) => value.clone(),
+            ChangeData::Status(value) => option_name(&fields.status, value.as_ref()),
+            ChangeData::Blocked(value) => option_name(&fields.blocked, value.as_ref()),
+            ChangeData::Epic(value) => option_name(&fields.epic, value.as_ref()),
+            ChangeData::Iteration(value) => option_name(&fields.iteration, value.as_ref()),
+            ChangeData::Kind(value) => option_name(&fields.kind, value.as_ref()),
+            ChangeData::Workstream(value) => option_name(&fields.workstream, value.as_ref()),
+            ChangeData::Estimate(value) => option_name(&fields.estimate, value.as_ref()),
+            ChangeData::Priority(value) => option_name(&fields.priority, value.as_ref()),
+            ChangeData::SetParent(value) => Some(describe_work_item(value, work_items)),
+            ChangeData::RemoveParent(_) => None,
+            ChangeData::AddToProject | ChangeData::RemoveFromProject => None,
+        }
     }
 
     /// Returns the field name for this change, suitable for telemetry.
@@ -669,7 +837,9 @@ impl Change {
             ChangeData::Estimate(_) => "estimate",
             ChangeData::Priority(_) => "priority",
             ChangeData::SetParent(_) => "set_parent",
+            ChangeData::RemoveParent(_) => "remove_parent",
             ChangeData::AddToProject => "add_to_project",
+            ChangeData::RemoveFromProject => "remove_from_project",
         }
     }
 
@@ -688,11 +858,30 @@ impl Change {
             | ChangeData::Estimate(v)
             | ChangeData::Priority(v) => v.as_ref().map(|id| id.0.clone()),
             ChangeData::SetParent(id) => Some(id.0.clone()),
-            ChangeData::AddToProject => None,
+            ChangeData::RemoveParent(_) => None,
+            ChangeData::AddToProject | ChangeData::RemoveFromProject => None,
         }
     }
 }
 
+/// Returns the option name for `id`, falling back to the raw id if the option
+/// no longer exists.
+fn option_name<T>(field: &Field<T>, id: Option<&FieldOptionId>) -> Option<String> {
+    id.map(|id| {
+        field
+            .option_name(Some(id))
+            .map(|name| name.to_owned())
+            .unwrap_or_else(|| id.0.clone())
+    })
+}
+
+fn describe_work_item(id: &WorkItemId, work_items: &WorkItems) -> String {
+    work_items
+        .get(id)
+        .map(|work_item| work_item.describe())
+        .unwrap_or_else(|| id.0.clone())
+}
+
 impl WorkItems {
     pub fn apply_changes(&mut self, changes: &Changes) -> HashMap<WorkItemId, WorkItem> {
         let mut originals = HashMap::<WorkItemId, WorkItem>::default();
@@ -788,6 +977,28 @@ impl WorkItems {
                         warn!("new parent '{0}' not found", new_parent_id.0);
                     }
                 }
+                ChangeData::RemoveParent(parent_id) => {
+                    let child_id = &change.work_item_id;
+
+                    if let WorkItemData::Issue(issue) = &mut work_item.data {
+                        if issue.parent_id.as_ref() == Some(parent_id) {
+                            issue.parent_id = None;
+                        }
+                    } else {
+                        warn!("child '{0}' not an issue", child_id.0);
+                    }
+
+                    if let Some(parent) = self.get_mut(parent_id) {
+                        if let WorkItemData::Issue(issue) = &mut parent.data {
+                            issue.sub_issues.retain(|i| i != child_id);
+                        }
+                    } else {
+                        warn!("parent '{0}' not found", parent_id.0);
+                    }
+                }
+                // The item stays visible until the save completes, so that it
+                // can still be reviewed.
+                ChangeData::RemoveFromProject => {}
                 ChangeData::AddToProject => {
                     panic!("This shouldn't happen, because this item isn't in the project and so we shouldn't get here");
                 }
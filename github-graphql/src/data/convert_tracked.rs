@@ -0,0 +1,102 @@
+use super::{
+    Change, ChangeData, Changes, DelayLoad, SanitizeConflict, WorkItem, WorkItemData, WorkItemId,
+    WorkItems,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use ts_rs::TS;
+
+/// A tracked issue that wasn't made a sub-issue of its tracker because it
+/// already has a parent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TrackedConflict {
+    /// The tracked issue.
+    pub work_item_id: WorkItemId,
+    /// The issue that tracks it.
+    pub tracker_id: WorkItemId,
+    /// The issue's current parent, or the tracker it has already been given
+    /// to earlier in the same conversion.
+    pub parent_id: WorkItemId,
+}
+
+impl From<TrackedConflict> for SanitizeConflict {
+    fn from(conflict: TrackedConflict) -> Self {
+        SanitizeConflict {
+            work_item_id: conflict.work_item_id,
+            field: "set_parent".to_owned(),
+            current: ChangeData::SetParent(conflict.parent_id),
+            proposed: ChangeData::SetParent(conflict.tracker_id.clone()),
+            reason: "Tracked by another issue".to_owned(),
+            source_item_id: Some(conflict.tracker_id),
+            rule: "convert-tracked".to_owned(),
+        }
+    }
+}
+
+/// The result of converting every tracked issue in the project to a
+/// sub-issue.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TrackedConversion {
+    pub changes: Changes,
+    pub conflicts: Vec<TrackedConflict>,
+}
+
+impl WorkItems {
+    /// Like `convert_tracked_to_sub_issues`, but for every issue in the
+    /// project.  Trackers are visited in project order; a tracked issue that
+    /// already has a parent, or is tracked by more than one issue, is reported
+    /// as a conflict rather than reparented.
+    pub fn convert_all_tracked_to_sub_issues(&self) -> TrackedConversion {
+        let mut conversion = TrackedConversion::default();
+        let mut assigned: HashMap<&WorkItemId, &WorkItemId> = HashMap::new();
+
+        for tracker_id in self.iter() {
+            let Some(WorkItem {
+                data: WorkItemData::Issue(tracker),
+                ..
+            }) = self.get(tracker_id)
+            else {
+                continue;
+            };
+
+            let DelayLoad::Loaded(tracked_issues) = &tracker.tracked_issues else {
+                continue;
+            };
+
+            for tracked_id in tracked_issues {
+                let Some(WorkItem {
+                    data: WorkItemData::Issue(tracked),
+                    ..
+                }) = self.get(tracked_id)
+                else {
+                    continue;
+                };
+
+                let parent_id = tracked
+                    .parent_id
+                    .as_ref()
+                    .or_else(|| assigned.get(tracked_id).copied());
+
+                match parent_id {
+                    None => {
+                        assigned.insert(tracked_id, tracker_id);
+                        conversion.changes.add(Change {
+                            work_item_id: tracked_id.clone(),
+                            data: ChangeData::SetParent(tracker_id.clone()),
+                        });
+                    }
+                    Some(parent_id) if parent_id == tracker_id => {}
+                    Some(parent_id) => conversion.conflicts.push(TrackedConflict {
+                        work_item_id: tracked_id.clone(),
+                        tracker_id: tracker_id.clone(),
+                        parent_id: parent_id.clone(),
+                    }),
+                }
+            }
+        }
+
+        conversion
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use time::{Date, Duration, Month};
 use ts_rs::TS;
 
 #[derive(Debug, Serialize, Deserialize, TS, Clone)]
@@ -31,6 +32,23 @@ pub struct Iteration {
     pub duration: i64,
 }
 
+impl Iteration {
+    /// The first day of the iteration, or `None` if `start_date` isn't a
+    /// valid `YYYY-MM-DD` date.
+    pub fn start(&self) -> Option<Date> {
+        let mut parts = self.start_date.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month: u8 = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        Date::from_calendar_date(year, Month::try_from(month).ok()?, day).ok()
+    }
+
+    /// The day after the last day of the iteration.
+    pub fn end(&self) -> Option<Date> {
+        self.start()?.checked_add(Duration::days(self.duration))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, TS, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Fields {
@@ -0,0 +1,280 @@
+use super::{Change, ChangeData, Changes, WorkItemData, WorkItemId, WorkItems};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Something wrong with the shape of the hierarchy loaded from GitHub.
+/// Everything that walks the hierarchy assumes a tree, so these can make items
+/// appear in the wrong place or not at all.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase",
+    tag = "type"
+)]
+#[ts(export)]
+pub enum IntegrityProblem {
+    /// The item's parent isn't in the project.
+    ParentNotInProject { parent_id: WorkItemId },
+    /// One of the item's sub-issues isn't in the project.
+    SubIssueNotInProject { sub_issue_id: WorkItemId },
+    /// The item's parent doesn't list it as a sub-issue, usually because one
+    /// of them is stale in the cache.
+    NotListedByParent { parent_id: WorkItemId },
+    /// The item lists a sub-issue whose parent is some other item (or none).
+    SubIssueHasOtherParent { sub_issue_id: WorkItemId },
+    /// The item's parent is in a different repository.
+    CrossRepoParent {
+        parent_id: WorkItemId,
+        parent_repo: String,
+    },
+    /// The item tracks issues that have no parent, and so don't show up
+    /// under it.
+    TrackedNotSubIssues { tracked_ids: Vec<WorkItemId> },
+    /// Following parents from the item leads back to the item.
+    ParentCycle { cycle: Vec<WorkItemId> },
+}
+
+impl IntegrityProblem {
+    pub fn describe(&self) -> String {
+        match self {
+            IntegrityProblem::ParentNotInProject { parent_id } => {
+                format!("parent {} is not in the project", parent_id.0)
+            }
+            IntegrityProblem::SubIssueNotInProject { sub_issue_id } => {
+                format!("sub-issue {} is not in the project", sub_issue_id.0)
+            }
+            IntegrityProblem::NotListedByParent { parent_id } => {
+                format!("parent {} does not list it as a sub-issue", parent_id.0)
+            }
+            IntegrityProblem::SubIssueHasOtherParent { sub_issue_id } => {
+                format!("sub-issue {} has a different parent", sub_issue_id.0)
+            }
+            IntegrityProblem::CrossRepoParent {
+                parent_id,
+                parent_repo,
+            } => format!(
+                "parent {} is in another repository ({parent_repo})",
+                parent_id.0
+            ),
+            IntegrityProblem::TrackedNotSubIssues { tracked_ids } => {
+                format!(
+                    "tracks {} issue(s) that are not sub-issues",
+                    tracked_ids.len()
+                )
+            }
+            IntegrityProblem::ParentCycle { cycle } => {
+                format!("is part of a parent cycle of {} item(s)", cycle.len())
+            }
+        }
+    }
+}
+
+/// A suggested way of fixing an [`IntegrityProblem`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase",
+    tag = "type"
+)]
+#[ts(export)]
+pub enum IntegrityFix {
+    /// Stage adding the item to the project.
+    AddToProject { work_item_id: WorkItemId },
+    /// Fetch the items from GitHub again.
+    Reload { work_item_ids: Vec<WorkItemId> },
+    /// Stage converting the item's tracked issues to sub-issues.
+    ConvertTrackedToSubIssues { work_item_id: WorkItemId },
+}
+
+impl IntegrityFix {
+    pub fn describe(&self) -> String {
+        match self {
+            IntegrityFix::AddToProject { work_item_id } => {
+                format!("add {} to the project", work_item_id.0)
+            }
+            IntegrityFix::Reload { work_item_ids } => {
+                format!("reload {} item(s)", work_item_ids.len())
+            }
+            IntegrityFix::ConvertTrackedToSubIssues { .. } => {
+                "convert tracked issues to sub-issues".to_owned()
+            }
+        }
+    }
+
+    /// Returns the changes to stage for this fix.  Reloading doesn't need any
+    /// changes, so returns none.
+    pub fn changes(&self, work_items: &WorkItems) -> Changes {
+        match self {
+            IntegrityFix::AddToProject { work_item_id } => {
+                let mut changes = Changes::default();
+                changes.add(Change {
+                    work_item_id: work_item_id.clone(),
+                    data: ChangeData::AddToProject,
+                });
+                changes
+            }
+            IntegrityFix::Reload { .. } => Changes::default(),
+            IntegrityFix::ConvertTrackedToSubIssues { work_item_id } => {
+                work_items.convert_tracked_to_sub_issues(work_item_id)
+            }
+        }
+    }
+}
+
+/// A problem found by [`WorkItems::check_integrity`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct IntegrityIssue {
+    pub work_item_id: WorkItemId,
+    pub problem: IntegrityProblem,
+    /// `None` if there's nothing ghui can do about it.
+    pub fix: Option<IntegrityFix>,
+}
+
+impl WorkItems {
+    /// Looks for inconsistencies in the hierarchy, in project order.
+    pub fn check_integrity(&self) -> Vec<IntegrityIssue> {
+        let mut issues = Vec::new();
+        let mut add = |work_item_id: &WorkItemId, problem, fix| {
+            issues.push(IntegrityIssue {
+                work_item_id: work_item_id.clone(),
+                problem,
+                fix,
+            })
+        };
+
+        for id in self.iter() {
+            let Some(item) = self.get(id) else {
+                continue;
+            };
+
+            if let Some(parent_id) = item.get_parent() {
+                match self.get(parent_id) {
+                    None => add(
+                        id,
+                        IntegrityProblem::ParentNotInProject {
+                            parent_id: parent_id.clone(),
+                        },
+                        Some(IntegrityFix::AddToProject {
+                            work_item_id: parent_id.clone(),
+                        }),
+                    ),
+                    Some(parent) => {
+                        let listed = parent
+                            .get_sub_issues()
+                            .is_some_and(|sub_issues| sub_issues.contains(id));
+                        if !listed {
+                            add(
+                                id,
+                                IntegrityProblem::NotListedByParent {
+                                    parent_id: parent_id.clone(),
+                                },
+                                Some(IntegrityFix::Reload {
+                                    work_item_ids: vec![id.clone(), parent_id.clone()],
+                                }),
+                            );
+                        }
+
+                        if let (Some(repo), Some(parent_repo)) =
+                            (&item.repo_name_with_owner, &parent.repo_name_with_owner)
+                        {
+                            if repo != parent_repo {
+                                add(
+                                    id,
+                                    IntegrityProblem::CrossRepoParent {
+                                        parent_id: parent_id.clone(),
+                                        parent_repo: parent_repo.clone(),
+                                    },
+                                    None,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+
+            for sub_issue_id in item.get_sub_issues().into_iter().flatten() {
+                match self.get(sub_issue_id) {
+                    None => add(
+                        id,
+                        IntegrityProblem::SubIssueNotInProject {
+                            sub_issue_id: sub_issue_id.clone(),
+                        },
+                        Some(IntegrityFix::AddToProject {
+                            work_item_id: sub_issue_id.clone(),
+                        }),
+                    ),
+                    Some(sub_issue) if sub_issue.get_parent() != Some(id) => add(
+                        id,
+                        IntegrityProblem::SubIssueHasOtherParent {
+                            sub_issue_id: sub_issue_id.clone(),
+                        },
+                        Some(IntegrityFix::Reload {
+                            work_item_ids: vec![id.clone(), sub_issue_id.clone()],
+                        }),
+                    ),
+                    Some(_) => {}
+                }
+            }
+
+            if let WorkItemData::Issue(issue) = &item.data {
+                if issue.tracked_issues.is_loaded() {
+                    let changes = self.convert_tracked_to_sub_issues(id);
+                    let mut tracked_ids: Vec<_> = changes
+                        .into_iter()
+                        .map(|change| change.work_item_id.clone())
+                        .collect();
+                    tracked_ids.sort_by(|a, b| a.0.cmp(&b.0));
+                    if !tracked_ids.is_empty() {
+                        add(
+                            id,
+                            IntegrityProblem::TrackedNotSubIssues { tracked_ids },
+                            Some(IntegrityFix::ConvertTrackedToSubIssues {
+                                work_item_id: id.clone(),
+                            }),
+                        );
+                    }
+                }
+            }
+
+            if let Some(cycle) = self.find_parent_cycle(id) {
+                // Only report the cycle once, from the member with the lowest id.
+                if cycle.iter().all(|other| other.0 >= id.0) {
+                    add(
+                        id,
+                        IntegrityProblem::ParentCycle {
+                            cycle: cycle.clone(),
+                        },
+                        Some(IntegrityFix::Reload {
+                            work_item_ids: cycle,
+                        }),
+                    );
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Returns the items on the path from `start` back to itself when
+    /// following parents, starting with `start`.
+    fn find_parent_cycle(&self, start: &WorkItemId) -> Option<Vec<WorkItemId>> {
+        let mut path = vec![start.clone()];
+        let mut current = start;
+
+        while let Some(parent_id) = self.get(current).and_then(|item| item.get_parent()) {
+            if parent_id == start {
+                return Some(path);
+            }
+            if path.contains(parent_id) {
+                // A cycle that `start` leads into but isn't part of.
+                return None;
+            }
+            path.push(parent_id.clone());
+            current = parent_id;
+        }
+
+        None
+    }
+}
@@ -0,0 +1,116 @@
+use super::{Result, WorkItem};
+use crate::{
+    client::{
+        graphql::get_issue_types::{get_repo_issue_types, IssueTypes},
+        transport::Client,
+    },
+    Error,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Issue types for a single repository, along with when they were fetched.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryIssueTypes {
+    pub issue_types: IssueTypes,
+    /// Seconds since the Unix epoch at which `issue_types` was fetched.
+    pub fetched_at: u64,
+}
+
+/// Caches the issue types available in each repository, keyed by
+/// `owner/name`.
+///
+/// Issue types are defined per repository (or organization), so a project that
+/// spans several repositories can have a different set of valid types for
+/// each item.  The registry is loaded lazily: the first lookup for a
+/// repository queries GitHub and subsequent lookups reuse the result.  It can
+/// be serialized so that callers can persist it alongside other caches, using
+/// [`IssueTypeRegistry::remove_expired`] to discard stale entries.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueTypeRegistry {
+    repositories: HashMap<String, RepositoryIssueTypes>,
+}
+
+impl IssueTypeRegistry {
+    /// Returns the cached issue types for `owner/name`, if they have been
+    /// loaded.
+    pub fn get(&self, owner: &str, name: &str) -> Option<&IssueTypes> {
+        self.repositories
+            .get(&repository_key(owner, name))
+            .map(|r| &r.issue_types)
+    }
+
+    /// Returns the cached issue types for the repository that `work_item`
+    /// belongs to, if they have been loaded.
+    pub fn get_for_work_item(&self, work_item: &WorkItem) -> Option<&IssueTypes> {
+        let (owner, name) = work_item.get_repository_info()?;
+        self.get(&owner, &name)
+    }
+
+    pub fn insert(&mut self, owner: &str, name: &str, issue_types: IssueTypes) {
+        self.repositories.insert(
+            repository_key(owner, name),
+            RepositoryIssueTypes {
+                issue_types,
+                fetched_at: now(),
+            },
+        );
+    }
+
+    /// Returns the issue types for `owner/name`, querying GitHub only if they
+    /// are not already cached.
+    pub async fn get_or_load(
+        &mut self,
+        client: &impl Client,
+        owner: &str,
+        name: &str,
+    ) -> Result<&IssueTypes> {
+        let key = repository_key(owner, name);
+        if !self.repositories.contains_key(&key) {
+            let issue_types = get_repo_issue_types(client, owner, name).await?;
+            self.insert(owner, name, issue_types);
+        }
+
+        self.repositories
+            .get(&key)
+            .map(|r| &r.issue_types)
+            .ok_or(Error::Unknown(format!("issue types for {key} not cached")))
+    }
+
+    /// Returns the sorted issue type names valid for `owner/name`, loading them
+    /// if necessary.
+    pub async fn names(
+        &mut self,
+        client: &impl Client,
+        owner: &str,
+        name: &str,
+    ) -> Result<Vec<String>> {
+        let issue_types = self.get_or_load(client, owner, name).await?;
+        let mut names: Vec<String> = issue_types.name_to_id.keys().cloned().collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Removes entries that were fetched more than `max_age` ago.
+    pub fn remove_expired(&mut self, max_age: Duration) {
+        let now = now();
+        self.repositories
+            .retain(|_, r| now.saturating_sub(r.fetched_at) <= max_age.as_secs());
+    }
+}
+
+fn repository_key(owner: &str, name: &str) -> String {
+    format!("{owner}/{name}")
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
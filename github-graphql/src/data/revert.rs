@@ -0,0 +1,131 @@
+use super::{
+    Change, ChangeData, ChangeOutcome, Changes, DelayLoad, SaveReport, WorkItemData, WorkItems,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A change that was committed to GitHub, along with the change that would
+/// undo it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SavedChange {
+    pub change: Change,
+    /// `None` if the value that was overwritten isn't known (for example,
+    /// because the field hadn't been loaded yet).
+    pub inverse: Option<Change>,
+}
+
+/// The changes committed by a single save.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SaveRecord {
+    /// Sequential number identifying this save, starting at 1.
+    pub number: usize,
+    pub changes: Vec<SavedChange>,
+}
+
+impl SaveRecord {
+    /// Returns the changes that undo this save on GitHub.  Changes that can't
+    /// be reverted are left out.
+    pub fn revert_changes(&self) -> Changes {
+        let mut changes = Changes::default();
+        for saved in &self.changes {
+            if let Some(inverse) = &saved.inverse {
+                changes.add(inverse.clone());
+            }
+        }
+        changes
+    }
+}
+
+/// Remembers the values that each save overwrote so that the save can be
+/// reverted later.
+///
+/// Unlike `UndoHistory`, which only restores unsaved local edits, reverting a
+/// save produces new changes that must themselves be reviewed and saved.  The
+/// history can be serialized so that saves can be reverted after a restart.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SaveHistory {
+    records: Vec<SaveRecord>,
+}
+
+impl SaveHistory {
+    /// Records the successful changes in `report`.  `work_items` must be the
+    /// state from *before* the save.  Returns the number of the new record, or
+    /// `None` if nothing was committed.
+    pub fn record(&mut self, report: &SaveReport, work_items: &WorkItems) -> Option<usize> {
+        let changes: Vec<SavedChange> = report
+            .results
+            .iter()
+            .filter(|result| result.outcome == ChangeOutcome::Succeeded)
+            .map(|result| SavedChange {
+                change: result.change.clone(),
+                inverse: result.change.inverse(work_items),
+            })
+            .collect();
+
+        if changes.is_empty() {
+            return None;
+        }
+
+        let number = self.records.last().map(|r| r.number).unwrap_or_default() + 1;
+        self.records.push(SaveRecord { number, changes });
+        Some(number)
+    }
+
+    pub fn get(&self, number: usize) -> Option<&SaveRecord> {
+        self.records.iter().find(|r| r.number == number)
+    }
+
+    pub fn records(&self) -> &[SaveRecord] {
+        &self.records
+    }
+}
+
+impl Change {
+    /// Returns the change that restores what this change overwrites, based on
+    /// the state in `work_items` before this change is saved.
+    ///
+    /// Removing an item from the project is reverted by adding it back, but
+    /// its field values are not restored.
+    pub fn inverse(&self, work_items: &WorkItems) -> Option<Change> {
+        let work_item = work_items.get(&self.work_item_id);
+        let project_item = work_item.map(|w| &w.project_item);
+
+        let data = match &self.data {
+            ChangeData::IssueType(_) => match &work_item?.data {
+                WorkItemData::Issue(issue) => ChangeData::IssueType(loaded(&issue.issue_type)?),
+                _ => return None,
+            },
+            ChangeData::Status(_) => ChangeData::Status(project_item?.status.clone()),
+            ChangeData::Blocked(_) => ChangeData::Blocked(loaded(&project_item?.blocked)?),
+            ChangeData::Epic(_) => ChangeData::Epic(project_item?.epic.clone()),
+            ChangeData::Iteration(_) => ChangeData::Iteration(loaded(&project_item?.iteration)?),
+            ChangeData::Kind(_) => ChangeData::Kind(loaded(&project_item?.kind)?),
+            ChangeData::Workstream(_) => ChangeData::Workstream(loaded(&project_item?.workstream)?),
+            ChangeData::Estimate(_) => ChangeData::Estimate(project_item?.estimate.clone()),
+            ChangeData::Priority(_) => ChangeData::Priority(project_item?.priority.clone()),
+            ChangeData::SetParent(new_parent) => match work_item?.get_parent() {
+                Some(old_parent) => ChangeData::SetParent(old_parent.clone()),
+                None => ChangeData::RemoveParent(new_parent.clone()),
+            },
+            ChangeData::RemoveParent(old_parent) => ChangeData::SetParent(old_parent.clone()),
+            ChangeData::AddToProject => ChangeData::RemoveFromProject,
+            ChangeData::RemoveFromProject => ChangeData::AddToProject,
+        };
+
+        Some(Change {
+            work_item_id: self.work_item_id.clone(),
+            data,
+        })
+    }
+}
+
+fn loaded<T: Clone>(value: &DelayLoad<T>) -> Option<T> {
+    match value {
+        DelayLoad::Loaded(value) => Some(value.clone()),
+        DelayLoad::NotLoaded => None,
+    }
+}
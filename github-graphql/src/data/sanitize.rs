@@ -0,0 +1,458 @@
+use super::{Change, ChangeData, Changes, Fields, WorkItemId, WorkItems};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use ts_rs::TS;
+
+mod rules;
+pub use rules::*;
+
+/// How serious the problems found by a sanitize rule are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum RuleSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A check run by [`WorkItems::sanitize`].  Rules look at the whole project
+/// and propose changes (or conflicts and problems, when the right fix needs a
+/// human) for the items that don't follow the team's conventions.
+pub trait SanitizeRule {
+    /// Stable identifier used in config files and reports, e.g.
+    /// `inherit-epic`.
+    fn id(&self) -> &'static str;
+
+    /// One-line summary of what the rule enforces.
+    fn description(&self) -> &'static str;
+
+    fn default_severity(&self) -> RuleSeverity {
+        RuleSeverity::Warning
+    }
+
+    /// Whether the rule's changes can be saved without anyone reviewing them,
+    /// e.g. by an unattended hygiene run.
+    fn safe_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, work_items: &WorkItems, fields: &Fields, findings: &mut SanitizeFindings);
+}
+
+/// Collects the results of a single rule's check.
+pub struct SanitizeFindings {
+    rule: &'static str,
+    changes: Changes,
+    conflicts: Vec<SanitizeConflict>,
+    problems: Vec<(WorkItemId, String)>,
+}
+
+impl SanitizeFindings {
+    fn new(rule: &'static str) -> Self {
+        SanitizeFindings {
+            rule,
+            changes: Changes::default(),
+            conflicts: Vec::new(),
+            problems: Vec::new(),
+        }
+    }
+
+    pub fn add_change(&mut self, change: Change) {
+        self.changes.add(change);
+    }
+
+    /// Reports a value that should change, but that the rule won't overwrite
+    /// without the user's say-so.  `source_item_id` is the item the proposed
+    /// value comes from, if any.
+    pub fn add_conflict(
+        &mut self,
+        work_item_id: WorkItemId,
+        current: ChangeData,
+        proposed: ChangeData,
+        reason: impl Into<String>,
+        source_item_id: Option<WorkItemId>,
+    ) {
+        let field = Change {
+            work_item_id: work_item_id.clone(),
+            data: proposed.clone(),
+        }
+        .field_name()
+        .to_owned();
+
+        self.conflicts.push(SanitizeConflict {
+            work_item_id,
+            field,
+            current,
+            proposed,
+            reason: reason.into(),
+            source_item_id,
+            rule: self.rule.to_owned(),
+        });
+    }
+
+    /// Reports something wrong with an item that has no automatic fix.
+    pub fn add_problem(&mut self, work_item_id: WorkItemId, description: impl Into<String>) {
+        self.problems.push((work_item_id, description.into()));
+    }
+}
+
+/// A conflict found during sanitize: a rule wants to change a value that is
+/// already set, so the change is left for the user to review.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SanitizeConflict {
+    pub work_item_id: WorkItemId,
+    /// The field in conflict, as named by `Change::field_name`.
+    pub field: String,
+    /// The value currently set on the item.
+    pub current: ChangeData,
+    /// The value the rule says this item should have.
+    pub proposed: ChangeData,
+    /// Why the rule proposes the new value.
+    pub reason: String,
+    /// The item the proposed value comes from, e.g. the parent it is
+    /// inherited from.
+    pub source_item_id: Option<WorkItemId>,
+    /// Id of the rule that found the conflict.
+    pub rule: String,
+}
+
+impl SanitizeConflict {
+    /// Returns the choice that resolves this conflict with `resolution`.
+    pub fn choose(&self, resolution: ConflictResolution) -> ConflictChoice {
+        ConflictChoice {
+            work_item_id: self.work_item_id.clone(),
+            field: self.field.clone(),
+            source_item_id: self.source_item_id.clone(),
+            resolution,
+        }
+    }
+
+    fn matches(&self, choice: &ConflictChoice) -> bool {
+        self.work_item_id == choice.work_item_id
+            && self.field == choice.field
+            && self.source_item_id == choice.source_item_id
+    }
+}
+
+/// How the user chose to resolve a conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum ConflictResolution {
+    KeepCurrent,
+    UseProposed,
+}
+
+/// The resolution chosen for the conflict on `field` of `work_item_id`.
+///
+/// An item can have several conflicts on the same field, e.g. when it is
+/// tracked by more than one issue, so `source_item_id` identifies which
+/// proposal the choice is for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ConflictChoice {
+    pub work_item_id: WorkItemId,
+    pub field: String,
+    #[serde(default)]
+    pub source_item_id: Option<WorkItemId>,
+    pub resolution: ConflictResolution,
+}
+
+/// Applies `choices` to `conflicts`: resolved conflicts are removed, and the
+/// changes for those resolved with `UseProposed` are returned for staging.
+/// Choices that don't match a conflict are ignored.
+///
+/// Using a proposed value settles the field, so the other conflicts on the
+/// same field of that item are removed too.  If several proposals for one
+/// field are chosen, only the first is used.
+pub fn resolve_conflicts(
+    conflicts: &mut Vec<SanitizeConflict>,
+    choices: &[ConflictChoice],
+) -> Changes {
+    let mut changes = Changes::default();
+    let mut settled: HashSet<(WorkItemId, String)> = HashSet::new();
+
+    for conflict in conflicts.iter() {
+        let use_proposed = choices.iter().any(|choice| {
+            conflict.matches(choice) && choice.resolution == ConflictResolution::UseProposed
+        });
+        if use_proposed && settled.insert((conflict.work_item_id.clone(), conflict.field.clone())) {
+            changes.add(Change {
+                work_item_id: conflict.work_item_id.clone(),
+                data: conflict.proposed.clone(),
+            });
+        }
+    }
+
+    conflicts.retain(|conflict| {
+        !choices.iter().any(|choice| conflict.matches(choice))
+            && !settled.contains(&(conflict.work_item_id.clone(), conflict.field.clone()))
+    });
+
+    changes
+}
+
+/// An item flagged by a sanitize rule that needs a human to fix it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SanitizeProblem {
+    pub work_item_id: WorkItemId,
+    pub rule: String,
+    pub severity: RuleSeverity,
+    /// What's wrong with the item, e.g. "missing Kind, Priority".
+    pub description: String,
+}
+
+/// A change proposed by a sanitize rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SanitizeEntry {
+    pub rule: String,
+    pub severity: RuleSeverity,
+    /// Whether the rule is marked safe to apply without review.
+    pub safe: bool,
+    pub change: Change,
+}
+
+/// The result of a `sanitize()` call.
+///
+/// `changes` are the staged field updates (items with no conflicts), merged
+/// from `entries`, which record the rule that proposed each one.
+/// `conflicts` are values that were already set and were therefore skipped —
+/// the caller can present these for user review.  `problems` are issues that
+/// no change can fix.
+#[derive(Debug, Default)]
+pub struct SanitizeReport {
+    pub changes: Changes,
+    pub entries: Vec<SanitizeEntry>,
+    pub conflicts: Vec<SanitizeConflict>,
+    pub problems: Vec<SanitizeProblem>,
+}
+
+impl SanitizeReport {
+    /// Keeps only the entries, conflicts and problems from rules for which
+    /// `keep` returns true.
+    pub fn retain_rules(&mut self, keep: impl Fn(&str) -> bool) {
+        self.entries.retain(|entry| keep(&entry.rule));
+        self.conflicts.retain(|conflict| keep(&conflict.rule));
+        self.problems.retain(|problem| keep(&problem.rule));
+
+        self.changes = Changes::default();
+        for entry in &self.entries {
+            self.changes.add(entry.change.clone());
+        }
+    }
+
+    /// Returns the changes proposed by rules marked safe.
+    pub fn safe_changes(&self) -> Changes {
+        let mut changes = Changes::default();
+        for entry in self.entries.iter().filter(|entry| entry.safe) {
+            changes.add(entry.change.clone());
+        }
+        changes
+    }
+}
+
+/// Settings for a single rule.  Unset values fall back to the rule's
+/// defaults.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<RuleSeverity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safe: Option<bool>,
+}
+
+/// Per-rule settings, keyed by rule id, typically loaded from a config file.
+/// Rules that aren't listed are enabled with their default severity.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SanitizeConfig {
+    #[serde(default)]
+    pub rules: HashMap<String, RuleConfig>,
+}
+
+impl SanitizeConfig {
+    pub fn is_enabled(&self, rule: &str) -> bool {
+        self.rules
+            .get(rule)
+            .and_then(|config| config.enabled)
+            .unwrap_or(true)
+    }
+
+    pub fn severity(&self, rule: &dyn SanitizeRule) -> RuleSeverity {
+        self.rules
+            .get(rule.id())
+            .and_then(|config| config.severity)
+            .unwrap_or_else(|| rule.default_severity())
+    }
+
+    pub fn is_safe(&self, rule: &dyn SanitizeRule) -> bool {
+        self.rules
+            .get(rule.id())
+            .and_then(|config| config.safe)
+            .unwrap_or_else(|| rule.safe_by_default())
+    }
+}
+
+/// Describes a rule and its effective settings, for display.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SanitizeRuleInfo {
+    pub id: String,
+    pub description: String,
+    pub severity: RuleSeverity,
+    pub enabled: bool,
+    pub safe: bool,
+}
+
+/// The set of rules that sanitize runs.  `SanitizeRules::default()` contains
+/// the built-in rules; callers can add their own with
+/// [`SanitizeRules::add`].
+pub struct SanitizeRules {
+    rules: Vec<Box<dyn SanitizeRule>>,
+}
+
+impl Default for SanitizeRules {
+    fn default() -> Self {
+        SanitizeRules {
+            rules: builtin_rules(),
+        }
+    }
+}
+
+impl SanitizeRules {
+    pub fn empty() -> Self {
+        SanitizeRules { rules: Vec::new() }
+    }
+
+    pub fn add(&mut self, rule: impl SanitizeRule + 'static) {
+        self.rules.push(Box::new(rule));
+    }
+
+    pub fn describe(&self, config: &SanitizeConfig) -> Vec<SanitizeRuleInfo> {
+        self.rules
+            .iter()
+            .map(|rule| SanitizeRuleInfo {
+                id: rule.id().to_owned(),
+                description: rule.description().to_owned(),
+                severity: config.severity(rule.as_ref()),
+                enabled: config.is_enabled(rule.id()),
+                safe: config.is_safe(rule.as_ref()),
+            })
+            .collect()
+    }
+
+    /// Runs every rule that `config` enables.
+    pub fn run(
+        &self,
+        work_items: &WorkItems,
+        fields: &Fields,
+        config: &SanitizeConfig,
+    ) -> SanitizeReport {
+        let mut report = SanitizeReport::default();
+        let mut proposed: HashMap<(WorkItemId, String), SanitizeEntry> = HashMap::new();
+
+        for rule in self
+            .rules
+            .iter()
+            .filter(|rule| config.is_enabled(rule.id()))
+        {
+            let mut findings = SanitizeFindings::new(rule.id());
+            rule.check(work_items, fields, &mut findings);
+
+            let severity = config.severity(rule.as_ref());
+            let safe = config.is_safe(rule.as_ref());
+            let mut entries = Vec::new();
+            for change in &findings.changes {
+                entries.push(SanitizeEntry {
+                    rule: rule.id().to_owned(),
+                    severity,
+                    safe,
+                    change: change.clone(),
+                });
+            }
+            entries.sort_by(|a, b| {
+                (&a.change.work_item_id.0, a.change.field_name())
+                    .cmp(&(&b.change.work_item_id.0, b.change.field_name()))
+            });
+
+            // Changes holds one value per field, so when an earlier rule has
+            // already proposed something else for the same field, the later
+            // proposal is dropped and reported rather than silently replacing
+            // it.
+            let mut overridden = Vec::new();
+            for entry in entries {
+                let key = (
+                    entry.change.work_item_id.clone(),
+                    entry.change.field_name().to_owned(),
+                );
+                match proposed.get(&key) {
+                    Some(earlier) if earlier.change != entry.change => {
+                        let value_name = |change: &Change| {
+                            change
+                                .new_value_name(fields, work_items)
+                                .unwrap_or_else(|| "<none>".to_owned())
+                        };
+                        overridden.push((
+                            entry.change.work_item_id.clone(),
+                            format!(
+                                "wants {} set to {}, but `{}` already proposes {}; not applied",
+                                entry.change.field_name(),
+                                value_name(&entry.change),
+                                earlier.rule,
+                                value_name(&earlier.change)
+                            ),
+                        ));
+                        continue;
+                    }
+                    Some(_) => {}
+                    None => {
+                        proposed.insert(key, entry.clone());
+                    }
+                }
+                report.changes.add(entry.change.clone());
+                report.entries.push(entry);
+            }
+            report.conflicts.extend(findings.conflicts);
+
+            let mut problems: Vec<_> = findings
+                .problems
+                .into_iter()
+                .chain(overridden)
+                .map(|(work_item_id, description)| SanitizeProblem {
+                    work_item_id,
+                    rule: rule.id().to_owned(),
+                    severity,
+                    description,
+                })
+                .collect();
+            problems.sort_by(|a, b| a.work_item_id.0.cmp(&b.work_item_id.0));
+            report.problems.extend(problems);
+        }
+
+        report
+    }
+}
+
+impl WorkItems {
+    /// Runs the built-in sanitize rules with their default settings.
+    pub fn sanitize(&self, fields: &Fields) -> SanitizeReport {
+        self.sanitize_with(fields, &SanitizeConfig::default())
+    }
+
+    /// Runs the built-in sanitize rules enabled by `config`.
+    pub fn sanitize_with(&self, fields: &Fields, config: &SanitizeConfig) -> SanitizeReport {
+        SanitizeRules::default().run(self, fields, config)
+    }
+}
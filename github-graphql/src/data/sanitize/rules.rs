@@ -0,0 +1,484 @@
+//! The sanitize rules that ship with ghui.
+
+use super::{RuleSeverity, SanitizeFindings, SanitizeRule};
+use crate::data::{
+    Change, ChangeData, DelayLoad, FieldOptionId, Fields, WorkItem, WorkItemData, WorkItemId,
+    WorkItems,
+};
+use time::{Date, OffsetDateTime};
+
+pub fn builtin_rules() -> Vec<Box<dyn SanitizeRule>> {
+    vec![
+        Box::new(ClosedStatusRule),
+        Box::new(BugIssueTypeRule),
+        Box::new(AssignedPlanningRule),
+        Box::new(AddMissingToProjectRule),
+        Box::new(InheritEpicRule),
+        Box::new(MatchParentWorkstreamRule),
+        Box::new(ClosedParentOpenSubIssuesRule),
+        Box::new(OpenInPastIterationRule::default()),
+        Box::new(MissingFieldsRule),
+        Box::new(PullRequestWithoutIssueRule),
+    ]
+}
+
+/// Closed items should have status set to Closed.
+pub struct ClosedStatusRule;
+
+impl SanitizeRule for ClosedStatusRule {
+    fn id(&self) -> &'static str {
+        "closed-status"
+    }
+
+    fn description(&self) -> &'static str {
+        "Closed items have their Status set to Closed"
+    }
+
+    fn safe_by_default(&self) -> bool {
+        true
+    }
+
+    fn check(&self, work_items: &WorkItems, fields: &Fields, findings: &mut SanitizeFindings) {
+        let closed_option_id = fields.status.option_id("Closed".into()).cloned();
+
+        for item in work_items.work_items.values() {
+            if *item.is_closed().expect_loaded() && item.project_item.status != closed_option_id {
+                findings.add_change(Change {
+                    work_item_id: item.id.clone(),
+                    data: ChangeData::Status(closed_option_id.clone()),
+                });
+            }
+        }
+    }
+}
+
+/// Items that are Bugs should set their issue type to Bug.
+pub struct BugIssueTypeRule;
+
+impl SanitizeRule for BugIssueTypeRule {
+    fn id(&self) -> &'static str {
+        "bug-issue-type"
+    }
+
+    fn description(&self) -> &'static str {
+        "Issues with Kind Bug have the Bug issue type"
+    }
+
+    fn default_severity(&self) -> RuleSeverity {
+        RuleSeverity::Info
+    }
+
+    fn check(&self, work_items: &WorkItems, fields: &Fields, findings: &mut SanitizeFindings) {
+        let bug_kind_id = fields.kind.option_id("Bug".into()).cloned();
+
+        for item in work_items.work_items.values() {
+            if let WorkItemData::Issue(issue) = &item.data {
+                let kind = item.project_item.kind.expect_loaded();
+
+                if *kind == bug_kind_id
+                    && issue.issue_type.expect_loaded().as_deref() != Some("Bug")
+                {
+                    findings.add_change(Change {
+                        work_item_id: item.id.clone(),
+                        data: ChangeData::IssueType(Some("Bug".to_owned())),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Assigned issues with no status should be set to Planning.
+pub struct AssignedPlanningRule;
+
+impl SanitizeRule for AssignedPlanningRule {
+    fn id(&self) -> &'static str {
+        "assigned-planning"
+    }
+
+    fn description(&self) -> &'static str {
+        "Open, assigned issues without a Status are set to Planning"
+    }
+
+    fn default_severity(&self) -> RuleSeverity {
+        RuleSeverity::Info
+    }
+
+    fn check(&self, work_items: &WorkItems, fields: &Fields, findings: &mut SanitizeFindings) {
+        let planning_option_id = fields.status.option_id("Planning".into()).cloned();
+
+        for item in work_items.work_items.values() {
+            if let WorkItemData::Issue(issue) = &item.data {
+                if !issue.assignees.is_empty()
+                    && item.project_item.status.is_none()
+                    && !*item.is_closed().expect_loaded()
+                {
+                    findings.add_change(Change {
+                        work_item_id: item.id.clone(),
+                        data: ChangeData::Status(planning_option_id.clone()),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Parents and sub-issues of items in the project should be in the project
+/// too, otherwise the hierarchy can't be shown.
+pub struct AddMissingToProjectRule;
+
+impl SanitizeRule for AddMissingToProjectRule {
+    fn id(&self) -> &'static str {
+        "add-missing-to-project"
+    }
+
+    fn description(&self) -> &'static str {
+        "Parents and sub-issues of project items are added to the project"
+    }
+
+    fn check(&self, work_items: &WorkItems, _: &Fields, findings: &mut SanitizeFindings) {
+        let mut add_if_missing = |id: &WorkItemId| {
+            if work_items.get(id).is_none() {
+                findings.add_change(Change {
+                    work_item_id: id.clone(),
+                    data: ChangeData::AddToProject,
+                });
+            }
+        };
+
+        for item in work_items.work_items.values() {
+            if let Some(parent_id) = item.get_parent() {
+                add_if_missing(parent_id);
+            }
+
+            for sub_issue_id in item.get_sub_issues().into_iter().flatten() {
+                add_if_missing(sub_issue_id);
+            }
+        }
+    }
+}
+
+/// Children inherit their parent's Epic.  A child whose Epic is later than
+/// its parent's is reported as a conflict rather than changed.
+pub struct InheritEpicRule;
+
+impl SanitizeRule for InheritEpicRule {
+    fn id(&self) -> &'static str {
+        "inherit-epic"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sub-issues inherit their parent's Epic unless theirs is later"
+    }
+
+    fn safe_by_default(&self) -> bool {
+        true
+    }
+
+    fn check(&self, work_items: &WorkItems, fields: &Fields, findings: &mut SanitizeFindings) {
+        let epic_field = &fields.epic;
+
+        walk_hierarchy(work_items, None, &mut |item, inherited: &InheritedValue| {
+            let this_item_epic = &item.project_item.epic;
+
+            if let Some((parent_epic_id, source_id)) = inherited {
+                if let Some(current) = this_item_epic {
+                    let current_idx = epic_field.option_index(Some(current));
+                    let parent_idx = epic_field.option_index(Some(parent_epic_id));
+                    if current_idx > parent_idx {
+                        // Child epic is later than parent epic — record
+                        // conflict so the user can review and selectively
+                        // override it.
+                        findings.add_conflict(
+                            item.id.clone(),
+                            ChangeData::Epic(Some(current.clone())),
+                            ChangeData::Epic(Some(parent_epic_id.clone())),
+                            "Epic is later than its parent's",
+                            Some(source_id.clone()),
+                        );
+                    }
+                } else {
+                    findings.add_change(Change {
+                        work_item_id: item.id.clone(),
+                        data: ChangeData::Epic(Some(parent_epic_id.clone())),
+                    });
+                }
+            }
+
+            match this_item_epic {
+                Some(epic) => Some((epic.clone(), item.id.clone())),
+                None => inherited.clone(),
+            }
+        });
+    }
+}
+
+/// Each item must match its parent's Workstream.  Only enforced when the
+/// parent has a value; an item with a different Workstream already set is
+/// reported as a conflict.
+pub struct MatchParentWorkstreamRule;
+
+impl SanitizeRule for MatchParentWorkstreamRule {
+    fn id(&self) -> &'static str {
+        "match-parent-workstream"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sub-issues have the same Workstream as their parent"
+    }
+
+    fn safe_by_default(&self) -> bool {
+        true
+    }
+
+    fn check(&self, work_items: &WorkItems, _: &Fields, findings: &mut SanitizeFindings) {
+        walk_hierarchy(
+            work_items,
+            None,
+            &mut |item, parent_workstream: &InheritedValue| {
+                let current_ws = item.project_item.workstream.expect_loaded();
+
+                if let Some((parent_ws, parent_id)) = parent_workstream {
+                    match current_ws {
+                        None => findings.add_change(Change {
+                            work_item_id: item.id.clone(),
+                            data: ChangeData::Workstream(Some(parent_ws.clone())),
+                        }),
+                        Some(current) if current != parent_ws => findings.add_conflict(
+                            item.id.clone(),
+                            ChangeData::Workstream(Some(current.clone())),
+                            ChangeData::Workstream(Some(parent_ws.clone())),
+                            "Workstream differs from its parent's",
+                            Some(parent_id.clone()),
+                        ),
+                        Some(_) => {}
+                    }
+                }
+
+                // Only propagate an explicit, non-blank workstream.
+                current_ws.as_ref().map(|ws| (ws.clone(), item.id.clone()))
+            },
+        );
+    }
+}
+
+/// A closed issue shouldn't have sub-issues that are still open.  Whether to
+/// reopen the parent or close the sub-issues is up to a human.
+pub struct ClosedParentOpenSubIssuesRule;
+
+impl SanitizeRule for ClosedParentOpenSubIssuesRule {
+    fn id(&self) -> &'static str {
+        "closed-parent-open-sub-issues"
+    }
+
+    fn description(&self) -> &'static str {
+        "Closed issues have no open sub-issues"
+    }
+
+    fn check(&self, work_items: &WorkItems, _: &Fields, findings: &mut SanitizeFindings) {
+        for item in work_items.work_items.values() {
+            if item.is_closed() != DelayLoad::Loaded(true) {
+                continue;
+            }
+
+            let open_sub_issues = item
+                .get_sub_issues()
+                .into_iter()
+                .flatten()
+                .filter_map(|id| work_items.get(id))
+                .filter(|sub_issue| sub_issue.is_closed() == DelayLoad::Loaded(false))
+                .count();
+
+            if open_sub_issues > 0 {
+                findings.add_problem(
+                    item.id.clone(),
+                    format!("closed, but {open_sub_issues} sub-issue(s) are still open"),
+                );
+            }
+        }
+    }
+}
+
+/// Open items shouldn't be left in an iteration that has finished.  Moving
+/// them to the current iteration, if there is one, is proposed as a conflict
+/// since the work may need replanning instead.
+pub struct OpenInPastIterationRule {
+    today: Date,
+}
+
+impl OpenInPastIterationRule {
+    pub fn new(today: Date) -> Self {
+        OpenInPastIterationRule { today }
+    }
+}
+
+impl Default for OpenInPastIterationRule {
+    fn default() -> Self {
+        Self::new(OffsetDateTime::now_utc().date())
+    }
+}
+
+impl SanitizeRule for OpenInPastIterationRule {
+    fn id(&self) -> &'static str {
+        "open-in-past-iteration"
+    }
+
+    fn description(&self) -> &'static str {
+        "Open items are not left in a completed iteration"
+    }
+
+    fn check(&self, work_items: &WorkItems, fields: &Fields, findings: &mut SanitizeFindings) {
+        let current_iteration = fields.iteration.options.iter().find(|option| {
+            option.data.start().is_some_and(|start| start <= self.today)
+                && option.data.end().is_some_and(|end| self.today < end)
+        });
+
+        for item in work_items.work_items.values() {
+            if item.is_closed() != DelayLoad::Loaded(false) {
+                continue;
+            }
+
+            let DelayLoad::Loaded(Some(iteration_id)) = &item.project_item.iteration else {
+                continue;
+            };
+
+            let Some(iteration) = fields
+                .iteration
+                .options
+                .iter()
+                .find(|option| option.id == *iteration_id)
+            else {
+                continue;
+            };
+
+            let Some(end) = iteration.data.end() else {
+                continue;
+            };
+
+            if end > self.today {
+                continue;
+            }
+
+            match current_iteration {
+                Some(current) => findings.add_conflict(
+                    item.id.clone(),
+                    ChangeData::Iteration(Some(iteration_id.clone())),
+                    ChangeData::Iteration(Some(current.id.clone())),
+                    format!("still open in {}, which has ended", iteration.value),
+                    None,
+                ),
+                None => findings.add_problem(
+                    item.id.clone(),
+                    format!("still open in {}, which has ended", iteration.value),
+                ),
+            }
+        }
+    }
+}
+
+/// Open issues should have Kind, Priority and Estimate set so that they can
+/// be planned.
+pub struct MissingFieldsRule;
+
+impl SanitizeRule for MissingFieldsRule {
+    fn id(&self) -> &'static str {
+        "missing-fields"
+    }
+
+    fn description(&self) -> &'static str {
+        "Open issues have Kind, Priority and Estimate set"
+    }
+
+    fn default_severity(&self) -> RuleSeverity {
+        RuleSeverity::Info
+    }
+
+    fn check(&self, work_items: &WorkItems, fields: &Fields, findings: &mut SanitizeFindings) {
+        for item in work_items.work_items.values() {
+            if !matches!(item.data, WorkItemData::Issue(_))
+                || item.is_closed() != DelayLoad::Loaded(false)
+            {
+                continue;
+            }
+
+            let project_item = &item.project_item;
+            let mut missing = Vec::new();
+            if project_item.kind == DelayLoad::Loaded(None) {
+                missing.push(fields.kind.name.as_str());
+            }
+            if project_item.priority.is_none() {
+                missing.push(fields.priority.name.as_str());
+            }
+            if project_item.estimate.is_none() {
+                missing.push(fields.estimate.name.as_str());
+            }
+
+            if !missing.is_empty() {
+                findings.add_problem(item.id.clone(), format!("missing {}", missing.join(", ")));
+            }
+        }
+    }
+}
+
+/// Open pull requests in the project should be linked to the issue they
+/// close, so that the work shows up in the hierarchy.
+pub struct PullRequestWithoutIssueRule;
+
+impl SanitizeRule for PullRequestWithoutIssueRule {
+    fn id(&self) -> &'static str {
+        "pr-without-issue"
+    }
+
+    fn description(&self) -> &'static str {
+        "Open pull requests are linked to an issue"
+    }
+
+    fn default_severity(&self) -> RuleSeverity {
+        RuleSeverity::Info
+    }
+
+    fn check(&self, work_items: &WorkItems, _: &Fields, findings: &mut SanitizeFindings) {
+        for item in work_items.work_items.values() {
+            if let WorkItemData::PullRequest(pull_request) = &item.data {
+                if item.is_closed() == DelayLoad::Loaded(false)
+                    && pull_request.linked_issues == DelayLoad::Loaded(Vec::new())
+                {
+                    findings.add_problem(item.id.clone(), "not linked to an issue");
+                }
+            }
+        }
+    }
+}
+
+/// A field value passed down the hierarchy, along with the item it was set on.
+type InheritedValue = Option<(FieldOptionId, WorkItemId)>;
+
+/// Visits every item in the project reachable from the roots, parents before
+/// children.  `visit` is given the value returned for the item's parent (or
+/// `root_value` for roots) and returns the value to pass to its sub-issues.
+fn walk_hierarchy<T>(
+    work_items: &WorkItems,
+    root_value: T,
+    visit: &mut impl FnMut(&WorkItem, &T) -> T,
+) {
+    fn walk<T>(
+        work_items: &WorkItems,
+        item: &WorkItem,
+        parent_value: &T,
+        visit: &mut impl FnMut(&WorkItem, &T) -> T,
+    ) {
+        let value = visit(item, parent_value);
+
+        for child_id in item.get_sub_issues().into_iter().flatten() {
+            if let Some(child) = work_items.get(child_id) {
+                walk(work_items, child, &value, visit);
+            }
+        }
+    }
+
+    for root_id in work_items.get_roots() {
+        if let Some(root) = work_items.get(&root_id) {
+            walk(work_items, root, &root_value, visit);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use super::{Change, ChangeProblem, ProjectItemId, WorkItemId};
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use ts_rs::TS;
+
+/// Broad classification of a save failure, so callers can decide whether a
+/// retry is likely to help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum SaveErrorKind {
+    /// GitHub was unreachable.
+    Connectivity,
+    /// GitHub rejected the request and returned GraphQL errors.
+    GraphQl,
+    /// GitHub's response could not be understood.
+    UnexpectedResponse,
+    /// The local data needed to build the request was missing or wrong.
+    UnexpectedData,
+    /// The `gh` CLI failed.
+    GhCli,
+    Other,
+}
+
+impl From<&Error> for SaveErrorKind {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::Connectivity(_) => SaveErrorKind::Connectivity,
+            Error::GraphQlResponseErrors(_) => SaveErrorKind::GraphQl,
+            Error::GraphQlResponseUnexpected(_) => SaveErrorKind::UnexpectedResponse,
+            Error::UnexpectedData(_) => SaveErrorKind::UnexpectedData,
+            Error::GhCli(_) => SaveErrorKind::GhCli,
+            Error::Unknown(_) | Error::JoinError(_) => SaveErrorKind::Other,
+        }
+    }
+}
+
+/// What happened to a single change during a save.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", tag = "status")]
+#[ts(export)]
+pub enum ChangeOutcome {
+    Succeeded,
+    /// The change would have been saved, but this was a dry run.
+    DryRun,
+    Failed {
+        kind: SaveErrorKind,
+        message: String,
+        /// Messages from GitHub's GraphQL `errors` array, if any.
+        #[serde(rename = "graphqlMessages")]
+        graphql_messages: Vec<String>,
+    },
+    /// Not attempted because an earlier change hit a connectivity error.
+    SkippedConnectivity,
+    /// Not attempted because a change it depends on (adding `dependency` to
+    /// the project) failed.
+    SkippedDependency {
+        dependency: WorkItemId,
+    },
+    /// Not attempted because the change failed validation.
+    Invalid {
+        problem: ChangeProblem,
+    },
+}
+
+impl ChangeOutcome {
+    pub fn failed(error: &Error) -> Self {
+        let graphql_messages = match error {
+            Error::GraphQlResponseErrors(errors) => {
+                errors.iter().map(|e| e.message.clone()).collect()
+            }
+            _ => Vec::new(),
+        };
+
+        ChangeOutcome::Failed {
+            kind: error.into(),
+            message: error.to_string(),
+            graphql_messages,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, ChangeOutcome::Succeeded | ChangeOutcome::DryRun)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            ChangeOutcome::Succeeded => "succeeded".to_owned(),
+            ChangeOutcome::DryRun => "dry run".to_owned(),
+            ChangeOutcome::Failed {
+                kind,
+                message,
+                graphql_messages,
+            } => {
+                if graphql_messages.is_empty() {
+                    format!("failed ({kind:?}): {message}")
+                } else {
+                    format!("failed ({kind:?}): {}", graphql_messages.join("; "))
+                }
+            }
+            ChangeOutcome::SkippedConnectivity => "skipped (network unavailable)".to_owned(),
+            ChangeOutcome::SkippedDependency { dependency } => {
+                format!("skipped (adding {} to project failed)", dependency.0)
+            }
+            ChangeOutcome::Invalid { problem } => format!("invalid: {}", problem.describe()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ChangeResult {
+    pub change: Change,
+    pub outcome: ChangeOutcome,
+    #[ts(type = "number")]
+    pub duration_ms: u64,
+}
+
+impl ChangeResult {
+    pub(crate) fn new(change: Change, outcome: ChangeOutcome, started: Instant) -> Self {
+        ChangeResult {
+            change,
+            outcome,
+            duration_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+/// The result of `Changes::save`: one entry per change that was
+/// pending when the save started.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SaveReport {
+    pub results: Vec<ChangeResult>,
+    #[ts(type = "number")]
+    pub duration_ms: u64,
+
+    /// Project items that need to be re-fetched from GitHub to update the
+    /// local state.
+    #[serde(skip)]
+    pub project_item_ids: Vec<ProjectItemId>,
+
+    /// Work items that were removed from the project and should be dropped
+    /// from the local state.
+    #[serde(skip)]
+    pub removed_work_item_ids: Vec<WorkItemId>,
+}
+
+impl SaveReport {
+    pub fn succeeded_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.outcome.is_success())
+            .count()
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, ChangeOutcome::Failed { .. }))
+            .count()
+    }
+
+    pub fn skipped_count(&self) -> usize {
+        self.results.len() - self.succeeded_count() - self.failed_count()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|r| r.outcome.is_success())
+    }
+}
@@ -142,6 +142,11 @@ impl TestDataWorkItemBuilder<'_> {
         self
     }
 
+    pub fn repository(mut self, name_with_owner: &str) -> Self {
+        self.item.repo_name_with_owner = Some(name_with_owner.to_owned());
+        self
+    }
+
     pub fn assignees(mut self, names: &[&str]) -> Self {
         self.get_issue().assignees = names.iter().map(|s| s.to_string()).collect();
         self
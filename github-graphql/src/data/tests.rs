@@ -1,6 +1,14 @@
 mod apply_changes;
+mod convert_tracked;
+mod describe;
+mod integrity;
+mod issue_types;
+mod item_text;
+mod mock_client;
 mod resolve;
+mod revert;
 mod sanitize;
 mod save;
 mod undo_redo;
 mod update;
+mod validation;
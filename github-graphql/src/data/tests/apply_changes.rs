@@ -126,3 +126,24 @@ fn test_apply_changes_item_not_found() {
     assert_eq!(expected_original_work_items, actual_original_work_items);
     assert_eq!(work_items_before, data.work_items.work_items);
 }
+
+#[test]
+fn test_apply_remove_parent() {
+    let mut data = TestData::default();
+    let child = data.build().issue().add();
+    let parent = data.add_blank_issue([&child], []);
+
+    let mut changes = Changes::default();
+    changes.add(Change {
+        work_item_id: child.clone(),
+        data: ChangeData::RemoveParent(parent.clone()),
+    });
+
+    data.work_items.apply_changes(&changes);
+
+    assert_eq!(data.work_items.get(&child).unwrap().get_parent(), None);
+    assert_eq!(
+        data.work_items.get(&parent).unwrap().get_sub_issues(),
+        Some(&vec![])
+    );
+}
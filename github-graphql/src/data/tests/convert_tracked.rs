@@ -0,0 +1,110 @@
+use crate::data::{test_helpers::TestData, *};
+
+#[test]
+fn test_convert_all_tracked_to_sub_issues() {
+    let mut data = TestData::default();
+
+    let tracked_a = data.build().issue().add();
+    let tracked_b = data.build().issue().add();
+    let sub_issue = data.build().issue().add();
+    let issue_with_other_parent = data.build().issue().add();
+
+    let tracker_a = data
+        .build()
+        .tracked_issues(&[&tracked_a, &sub_issue])
+        .sub_issues(&[&sub_issue])
+        .add();
+    let tracker_b = data
+        .build()
+        .tracked_issues(&[&tracked_b, &issue_with_other_parent])
+        .add();
+    let other_parent = data.build().sub_issues(&[&issue_with_other_parent]).add();
+
+    let conversion = data.work_items.convert_all_tracked_to_sub_issues();
+
+    let mut expected_changes = Changes::default();
+    expected_changes.add(Change {
+        work_item_id: tracked_a,
+        data: ChangeData::SetParent(tracker_a),
+    });
+    expected_changes.add(Change {
+        work_item_id: tracked_b,
+        data: ChangeData::SetParent(tracker_b.clone()),
+    });
+
+    assert_eq!(conversion.changes, expected_changes);
+    assert_eq!(
+        conversion.conflicts,
+        vec![TrackedConflict {
+            work_item_id: issue_with_other_parent,
+            tracker_id: tracker_b,
+            parent_id: other_parent,
+        }]
+    );
+}
+
+#[test]
+fn test_convert_all_tracked_issue_tracked_twice() {
+    let mut data = TestData::default();
+
+    let tracked = data.build().issue().add();
+    let first_tracker = data.build().tracked_issues(&[&tracked]).add();
+    let second_tracker = data.build().tracked_issues(&[&tracked]).add();
+
+    let conversion = data.work_items.convert_all_tracked_to_sub_issues();
+
+    let mut expected_changes = Changes::default();
+    expected_changes.add(Change {
+        work_item_id: tracked.clone(),
+        data: ChangeData::SetParent(first_tracker.clone()),
+    });
+
+    assert_eq!(conversion.changes, expected_changes);
+    assert_eq!(
+        conversion.conflicts,
+        vec![TrackedConflict {
+            work_item_id: tracked,
+            tracker_id: second_tracker,
+            parent_id: first_tracker,
+        }]
+    );
+}
+
+/// Choosing one of several trackers must reparent the issue onto that tracker,
+/// not whichever conflict happens to come last.
+#[test]
+fn test_resolve_conflicts_between_two_trackers() {
+    let mut data = TestData::default();
+
+    let tracked = data.build().issue().add();
+    let parent = data.build().sub_issues(&[&tracked]).add();
+    let first_tracker = data.build().tracked_issues(&[&tracked]).add();
+    data.build().tracked_issues(&[&tracked]).add();
+
+    let mut conflicts: Vec<SanitizeConflict> = data
+        .work_items
+        .convert_all_tracked_to_sub_issues()
+        .conflicts
+        .into_iter()
+        .map(SanitizeConflict::from)
+        .collect();
+    assert_eq!(conflicts.len(), 2);
+    assert!(conflicts
+        .iter()
+        .all(|c| c.current == ChangeData::SetParent(parent.clone())));
+
+    let choice = conflicts
+        .iter()
+        .find(|c| c.source_item_id.as_ref() == Some(&first_tracker))
+        .unwrap()
+        .choose(ConflictResolution::UseProposed);
+    let changes = resolve_conflicts(&mut conflicts, &[choice]);
+
+    let mut expected_changes = Changes::default();
+    expected_changes.add(Change {
+        work_item_id: tracked,
+        data: ChangeData::SetParent(first_tracker),
+    });
+    assert_eq!(changes, expected_changes);
+    assert!(conflicts.is_empty(), "{conflicts:?}");
+}
@@ -0,0 +1,68 @@
+use crate::data::{test_helpers::TestData, *};
+
+#[test]
+fn test_describe_field_change_uses_option_names() {
+    let mut data = TestData::default();
+    let id = data.build().status("Active").add();
+
+    let change = Change {
+        work_item_id: id,
+        data: ChangeData::Status(data.fields.status.option_id(Some("Closed")).cloned()),
+    };
+
+    assert_eq!(
+        change.describe(&data.fields, &data.work_items),
+        "Status(Active -> Closed)"
+    );
+}
+
+#[test]
+fn test_describe_value_names_use_matching_field() {
+    let mut data = TestData::default();
+    let id = data.build().issue().add();
+    let kind = data.fields.kind.option_id(Some("Bug")).cloned();
+    let priority = data.fields.priority.option_id(Some("High")).cloned();
+    data.work_items.get_mut(&id).unwrap().project_item.kind = kind.clone().into();
+    data.work_items.get_mut(&id).unwrap().project_item.priority = priority.clone();
+
+    let kind_change = Change {
+        work_item_id: id.clone(),
+        data: ChangeData::Kind(data.fields.kind.option_id(Some("Task")).cloned()),
+    };
+    assert_eq!(
+        kind_change.old_value_name(&data.fields, &data.work_items),
+        Some("Bug".to_owned())
+    );
+    assert_eq!(
+        kind_change.new_value_name(&data.fields, &data.work_items),
+        Some("Task".to_owned())
+    );
+
+    let priority_change = Change {
+        work_item_id: id,
+        data: ChangeData::Priority(None),
+    };
+    assert_eq!(
+        priority_change.old_value_name(&data.fields, &data.work_items),
+        Some("High".to_owned())
+    );
+    assert_eq!(
+        priority_change.new_value_name(&data.fields, &data.work_items),
+        None
+    );
+}
+
+#[test]
+fn test_describe_unknown_work_item_does_not_panic() {
+    let data = TestData::default();
+
+    let change = Change {
+        work_item_id: WorkItemId("missing".into()),
+        data: ChangeData::AddToProject,
+    };
+
+    assert_eq!(
+        change.describe(&data.fields, &data.work_items),
+        "AddToProject(<> -> <>)"
+    );
+}
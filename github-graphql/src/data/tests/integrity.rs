@@ -0,0 +1,174 @@
+use crate::data::{test_helpers::TestData, *};
+
+fn set_parent(data: &mut TestData, id: &WorkItemId, parent_id: Option<&WorkItemId>) {
+    if let WorkItemData::Issue(issue) = &mut data.work_items.get_mut(id).unwrap().data {
+        issue.parent_id = parent_id.cloned();
+    }
+}
+
+#[test]
+fn test_consistent_hierarchy_has_no_issues() {
+    let mut data = TestData::default();
+    let child = data.build().issue().add();
+    data.build().issue().sub_issues(&[&child]).add();
+
+    assert!(data.work_items.check_integrity().is_empty());
+}
+
+#[test]
+fn test_parent_and_sub_issue_not_in_project() {
+    let mut data = TestData::default();
+    let missing = WorkItemId::from("missing".to_owned());
+
+    let child = data.build().issue().add();
+    set_parent(&mut data, &child, Some(&missing));
+    let parent = data.build().issue().sub_issues(&[&missing]).add();
+
+    assert_eq!(
+        data.work_items.check_integrity(),
+        vec![
+            IntegrityIssue {
+                work_item_id: child,
+                problem: IntegrityProblem::ParentNotInProject {
+                    parent_id: missing.clone()
+                },
+                fix: Some(IntegrityFix::AddToProject {
+                    work_item_id: missing.clone()
+                }),
+            },
+            IntegrityIssue {
+                work_item_id: parent,
+                problem: IntegrityProblem::SubIssueNotInProject {
+                    sub_issue_id: missing.clone()
+                },
+                fix: Some(IntegrityFix::AddToProject {
+                    work_item_id: missing
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_stale_parent_links_suggest_reload() {
+    let mut data = TestData::default();
+
+    let child = data.build().issue().add();
+    let old_parent = data.build().issue().sub_issues(&[&child]).add();
+    let new_parent = data.build().issue().add();
+    set_parent(&mut data, &child, Some(&new_parent));
+
+    assert_eq!(
+        data.work_items.check_integrity(),
+        vec![
+            IntegrityIssue {
+                work_item_id: child.clone(),
+                problem: IntegrityProblem::NotListedByParent {
+                    parent_id: new_parent.clone()
+                },
+                fix: Some(IntegrityFix::Reload {
+                    work_item_ids: vec![child.clone(), new_parent]
+                }),
+            },
+            IntegrityIssue {
+                work_item_id: old_parent.clone(),
+                problem: IntegrityProblem::SubIssueHasOtherParent {
+                    sub_issue_id: child.clone()
+                },
+                fix: Some(IntegrityFix::Reload {
+                    work_item_ids: vec![old_parent, child]
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_cross_repo_parent() {
+    let mut data = TestData::default();
+
+    let child = data.build().issue().repository("owner/child").add();
+    let parent = data
+        .build()
+        .issue()
+        .repository("owner/parent")
+        .sub_issues(&[&child])
+        .add();
+
+    assert_eq!(
+        data.work_items.check_integrity(),
+        vec![IntegrityIssue {
+            work_item_id: child,
+            problem: IntegrityProblem::CrossRepoParent {
+                parent_id: parent,
+                parent_repo: "owner/parent".to_owned(),
+            },
+            fix: None,
+        }]
+    );
+}
+
+#[test]
+fn test_tracked_issues_without_parent() {
+    let mut data = TestData::default();
+
+    let tracked = data.build().issue().add();
+    let tracker = data.add_blank_issue([], [&tracked]);
+
+    let issues = data.work_items.check_integrity();
+    assert_eq!(
+        issues,
+        vec![IntegrityIssue {
+            work_item_id: tracker.clone(),
+            problem: IntegrityProblem::TrackedNotSubIssues {
+                tracked_ids: vec![tracked.clone()]
+            },
+            fix: Some(IntegrityFix::ConvertTrackedToSubIssues {
+                work_item_id: tracker.clone()
+            }),
+        }]
+    );
+
+    let mut expected = Changes::default();
+    expected.add(Change {
+        work_item_id: tracked,
+        data: ChangeData::SetParent(tracker),
+    });
+    assert_eq!(
+        issues[0].fix.as_ref().unwrap().changes(&data.work_items),
+        expected
+    );
+}
+
+#[test]
+fn test_parent_cycle_reported_once() {
+    let mut data = TestData::default();
+
+    let a = data.build().issue().add();
+    let b = data.build().issue().sub_issues(&[&a]).add();
+    let c = data.build().issue().sub_issues(&[&b]).add();
+    if let WorkItemData::Issue(issue) = &mut data.work_items.get_mut(&a).unwrap().data {
+        issue.sub_issues = vec![c.clone()];
+    }
+    set_parent(&mut data, &c, Some(&a));
+
+    let cycles: Vec<_> = data
+        .work_items
+        .check_integrity()
+        .into_iter()
+        .filter(|issue| matches!(issue.problem, IntegrityProblem::ParentCycle { .. }))
+        .collect();
+
+    assert_eq!(
+        cycles,
+        vec![IntegrityIssue {
+            work_item_id: a.clone(),
+            problem: IntegrityProblem::ParentCycle {
+                cycle: vec![a.clone(), b.clone(), c.clone()]
+            },
+            fix: Some(IntegrityFix::Reload {
+                work_item_ids: vec![a, b, c]
+            }),
+        }]
+    );
+}
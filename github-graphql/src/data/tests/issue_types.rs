@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use super::mock_client::{issue_types_response, MockClient};
+use crate::data::{test_helpers::TestData, IssueTypeRegistry, WorkItemId};
+
+#[tokio::test]
+async fn test_issue_types_get_or_load_queries_each_repository_once() {
+    // Only one response is queued per repository; a second query would panic
+    // in the mock.
+    let client = MockClient::new()
+        .on_mutation("GetIssueTypes", issue_types_response(&["Bug", "Task"]))
+        .on_mutation("GetIssueTypes", issue_types_response(&["Epic"]));
+
+    let mut issue_types = IssueTypeRegistry::default();
+    assert!(issue_types.get("owner", "repo").is_none());
+
+    for _ in 0..2 {
+        let loaded = issue_types
+            .get_or_load(&client, "owner", "repo")
+            .await
+            .unwrap();
+        assert_eq!(loaded.name_to_id.len(), 2);
+    }
+
+    let names = issue_types.names(&client, "owner", "other").await.unwrap();
+    assert_eq!(names, vec!["Epic".to_owned()]);
+
+    let names = issue_types.names(&client, "owner", "repo").await.unwrap();
+    assert_eq!(names, vec!["Bug".to_owned(), "Task".to_owned()]);
+
+    client.assert_all_consumed();
+}
+
+#[tokio::test]
+async fn test_issue_types_get_for_work_item_uses_item_repository() {
+    let mut data = TestData::default();
+    let issue = data.build().issue().repository("owner/repo").add();
+    let elsewhere = data.build().issue().repository("owner/other").add();
+    let draft = data.build().add();
+
+    let client = MockClient::new().on_mutation("GetIssueTypes", issue_types_response(&["Bug"]));
+    let mut issue_types = IssueTypeRegistry::default();
+    issue_types
+        .get_or_load(&client, "owner", "repo")
+        .await
+        .unwrap();
+
+    let get = |id: &WorkItemId| issue_types.get_for_work_item(data.work_items.get(id).unwrap());
+    assert!(get(&issue).unwrap().name_to_id.contains_key("Bug"));
+    assert!(get(&elsewhere).is_none());
+    assert!(get(&draft).is_none());
+}
+
+#[test]
+fn test_issue_types_remove_expired() {
+    let mut issue_types: IssueTypeRegistry = serde_json::from_value(serde_json::json!({
+        "repositories": {
+            "owner/stale": {
+                "issueTypes": {
+                    "id_to_name": { "IT_Bug": "Bug" },
+                    "name_to_id": { "Bug": "IT_Bug" }
+                },
+                "fetchedAt": 0
+            }
+        }
+    }))
+    .unwrap();
+    issue_types.insert("owner", "fresh", Default::default());
+
+    issue_types.remove_expired(Duration::from_secs(60 * 60));
+
+    assert!(issue_types.get("owner", "stale").is_none());
+    assert!(issue_types.get("owner", "fresh").is_some());
+}
@@ -0,0 +1,53 @@
+use super::mock_client::MockClient;
+use crate::client::graphql::get_item_text::{get_work_item_text, WorkItemText};
+use crate::data::WorkItemId;
+
+#[tokio::test]
+async fn test_get_work_item_text() {
+    let client = MockClient::new().on_mutation(
+        "GetItemText",
+        serde_json::json!({
+            "data": {
+                "nodes": [
+                    { "__typename": "DraftIssue", "id": "draft", "body": "draft body" },
+                    {
+                        "__typename": "Issue",
+                        "id": "issue",
+                        "body": "issue body",
+                        "comments": { "nodes": [{ "body": "first" }, null, { "body": "second" }] }
+                    },
+                    {
+                        "__typename": "PullRequest",
+                        "id": "pr",
+                        "body": "",
+                        "comments": { "nodes": [] }
+                    },
+                    null
+                ]
+            }
+        }),
+    );
+
+    let ids = ["draft", "issue", "pr", "gone"]
+        .map(|id| WorkItemId(id.to_owned()))
+        .to_vec();
+    let texts = get_work_item_text(&client, ids).await.unwrap();
+
+    assert_eq!(texts.len(), 3);
+    assert_eq!(
+        texts[&WorkItemId("draft".to_owned())],
+        WorkItemText {
+            body: "draft body".to_owned(),
+            comments: Vec::new(),
+        }
+    );
+    assert_eq!(
+        texts[&WorkItemId("issue".to_owned())],
+        WorkItemText {
+            body: "issue body".to_owned(),
+            comments: vec!["first".to_owned(), "second".to_owned()],
+        }
+    );
+    assert_eq!(texts[&WorkItemId("pr".to_owned())], WorkItemText::default());
+    client.assert_all_consumed();
+}
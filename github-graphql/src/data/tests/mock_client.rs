@@ -0,0 +1,129 @@
+//! A mock [`Client`] shared by the tests that need canned GitHub responses.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::client::transport::Client;
+
+/// A mock Client that inspects each request's query string to determine the
+/// mutation type and returns the corresponding pre-configured response.
+#[derive(Clone)]
+pub(super) struct MockClient {
+    responses: Arc<Mutex<HashMap<String, VecDeque<MockResponse>>>>,
+}
+
+/// A queued response for a mutation keyword.
+enum MockResponse {
+    /// A successful JSON response body.
+    Json(serde_json::Value),
+    /// Simulates the network being unreachable.
+    ConnectivityError,
+}
+
+impl MockClient {
+    pub(super) fn new() -> Self {
+        Self {
+            responses: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub(super) fn on_mutation(self, keyword: &str, response: serde_json::Value) -> Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(keyword.to_string())
+            .or_default()
+            .push_back(MockResponse::Json(response));
+        self
+    }
+
+    /// Queues a connectivity (network-down) failure for the given mutation.
+    pub(super) fn on_mutation_connectivity_error(self, keyword: &str) -> Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(keyword.to_string())
+            .or_default()
+            .push_back(MockResponse::ConnectivityError);
+        self
+    }
+
+    /// Asserts that all queued mock responses were consumed.  Call this after
+    /// `save()` to verify no expected mutations were silently skipped.
+    pub(super) fn assert_all_consumed(&self) {
+        let responses = self.responses.lock().unwrap();
+        for (keyword, queue) in responses.iter() {
+            assert!(
+                queue.is_empty(),
+                "MockClient: {count} unconsumed response(s) for '{keyword}'",
+                count = queue.len()
+            );
+        }
+    }
+}
+
+/// Builds a connectivity error equivalent to gh failing because GitHub is
+/// unreachable.
+fn connectivity_error() -> crate::Error {
+    crate::Error::Connectivity("dial tcp: no such host".into())
+}
+
+impl Client for MockClient {
+    async fn request<Q, R>(&self, request: &Q) -> crate::Result<R>
+    where
+        Q: Serialize + Sync,
+        R: DeserializeOwned,
+    {
+        let request_json = serde_json::to_value(request).unwrap();
+        let query = request_json["query"].as_str().unwrap_or("");
+
+        let response = {
+            let mut responses = self.responses.lock().unwrap();
+            responses
+                .iter_mut()
+                .find_map(|(keyword, queue)| {
+                    if query.contains(keyword.as_str()) {
+                        queue.pop_front()
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or_else(|| panic!("MockClient: no response for query containing: {query}"))
+        };
+
+        match response {
+            MockResponse::Json(value) => serde_json::from_value(value)
+                .map_err(|e| crate::Error::GraphQlResponseUnexpected(e.to_string())),
+            MockResponse::ConnectivityError => Err(connectivity_error()),
+        }
+    }
+}
+
+/// JSON response for the `GetIssueTypes` query.
+pub(super) fn issue_types_response(names: &[&str]) -> serde_json::Value {
+    let nodes: Vec<_> = names
+        .iter()
+        .map(|name| {
+            serde_json::json!({
+                "color": "GRAY",
+                "description": null,
+                "id": format!("IT_{name}"),
+                "isEnabled": true,
+                "name": name
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "data": {
+            "repository": {
+                "issueTypes": {
+                    "nodes": nodes
+                }
+            }
+        }
+    })
+}
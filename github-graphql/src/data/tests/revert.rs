@@ -0,0 +1,226 @@
+use crate::data::{test_helpers::TestData, *};
+
+fn succeeded(changes: &[Change]) -> SaveReport {
+    SaveReport {
+        results: changes
+            .iter()
+            .map(|change| ChangeResult {
+                change: change.clone(),
+                outcome: ChangeOutcome::Succeeded,
+                duration_ms: 0,
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_inverse_restores_field_values() {
+    let mut data = TestData::default();
+    let id = data.build().status("Active").epic("EpicA").add();
+
+    let set_status = Change {
+        work_item_id: id.clone(),
+        data: ChangeData::Status(data.fields.status.option_id(Some("Closed")).cloned()),
+    };
+    let clear_epic = Change {
+        work_item_id: id.clone(),
+        data: ChangeData::Epic(None),
+    };
+
+    assert_eq!(
+        set_status.inverse(&data.work_items),
+        Some(Change {
+            work_item_id: id.clone(),
+            data: ChangeData::Status(data.fields.status.option_id(Some("Active")).cloned()),
+        })
+    );
+    assert_eq!(
+        clear_epic.inverse(&data.work_items),
+        Some(Change {
+            work_item_id: id,
+            data: ChangeData::Epic(data.fields.epic.option_id(Some("EpicA")).cloned()),
+        })
+    );
+}
+
+#[test]
+fn test_inverse_of_set_parent() {
+    let mut data = TestData::default();
+    let orphan = data.build().issue().add();
+    let child = data.build().issue().add();
+    let old_parent = data.add_blank_issue([&child], []);
+    let new_parent = data.build().issue().add();
+
+    let reparent = Change {
+        work_item_id: child.clone(),
+        data: ChangeData::SetParent(new_parent.clone()),
+    };
+    assert_eq!(
+        reparent.inverse(&data.work_items),
+        Some(Change {
+            work_item_id: child,
+            data: ChangeData::SetParent(old_parent),
+        })
+    );
+
+    let adopt = Change {
+        work_item_id: orphan.clone(),
+        data: ChangeData::SetParent(new_parent.clone()),
+    };
+    assert_eq!(
+        adopt.inverse(&data.work_items),
+        Some(Change {
+            work_item_id: orphan,
+            data: ChangeData::RemoveParent(new_parent),
+        })
+    );
+}
+
+#[test]
+fn test_inverse_of_add_to_project_removes_item() {
+    let data = TestData::default();
+    let id = WorkItemId::from("new_item".to_owned());
+
+    let add = Change {
+        work_item_id: id.clone(),
+        data: ChangeData::AddToProject,
+    };
+
+    assert_eq!(
+        add.inverse(&data.work_items),
+        Some(Change {
+            work_item_id: id,
+            data: ChangeData::RemoveFromProject,
+        })
+    );
+}
+
+#[test]
+fn test_inverse_unknown_when_value_not_loaded() {
+    let mut data = TestData::default();
+    let id = data.build().add();
+    data.work_items.get_mut(&id).unwrap().project_item.kind = DelayLoad::NotLoaded;
+
+    let change = Change {
+        work_item_id: id,
+        data: ChangeData::Kind(None),
+    };
+
+    assert_eq!(change.inverse(&data.work_items), None);
+}
+
+#[test]
+fn test_save_history_records_only_succeeded_changes() {
+    let mut data = TestData::default();
+    let saved = data.build().status("Active").add();
+    let failed = data.build().status("Active").add();
+    let closed = data.fields.status.option_id(Some("Closed")).cloned();
+
+    let change = |id: &WorkItemId| Change {
+        work_item_id: id.clone(),
+        data: ChangeData::Status(closed.clone()),
+    };
+
+    let mut report = succeeded(&[change(&saved), change(&failed)]);
+    report.results[1].outcome = ChangeOutcome::SkippedConnectivity;
+
+    let mut history = SaveHistory::default();
+    assert_eq!(history.record(&report, &data.work_items), Some(1));
+
+    let record = history.get(1).unwrap();
+    assert_eq!(record.changes.len(), 1);
+    assert_eq!(record.changes[0].change, change(&saved));
+}
+
+#[test]
+fn test_save_history_numbers_saves_and_ignores_empty_ones() {
+    let mut data = TestData::default();
+    let id = data.build().status("Active").add();
+    let change = Change {
+        work_item_id: id,
+        data: ChangeData::Status(None),
+    };
+
+    let mut history = SaveHistory::default();
+    assert_eq!(
+        history.record(&SaveReport::default(), &data.work_items),
+        None
+    );
+    assert_eq!(
+        history.record(&succeeded(&[change.clone()]), &data.work_items),
+        Some(1)
+    );
+    assert_eq!(
+        history.record(&succeeded(&[change]), &data.work_items),
+        Some(2)
+    );
+    assert_eq!(history.records().len(), 2);
+}
+
+/// The history is persisted between runs, so numbering must carry on from the
+/// reloaded records.
+#[test]
+fn test_save_history_round_trips_through_json() {
+    let mut data = TestData::default();
+    let id = data.build().status("Active").add();
+    let change = Change {
+        work_item_id: id,
+        data: ChangeData::Status(None),
+    };
+
+    let mut history = SaveHistory::default();
+    history.record(&succeeded(&[change.clone()]), &data.work_items);
+
+    let json = serde_json::to_string(&history).unwrap();
+    let mut reloaded: SaveHistory = serde_json::from_str(&json).unwrap();
+    assert_eq!(reloaded.records(), history.records());
+    assert_eq!(
+        reloaded.record(&succeeded(&[change]), &data.work_items),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_revert_changes_stages_inverses() {
+    let mut data = TestData::default();
+    let child = data.build().issue().status("Active").add();
+    let parent = data.build().issue().add();
+    let new_item = WorkItemId::from("new_item".to_owned());
+
+    let saved = [
+        Change {
+            work_item_id: child.clone(),
+            data: ChangeData::Status(None),
+        },
+        Change {
+            work_item_id: child.clone(),
+            data: ChangeData::SetParent(parent.clone()),
+        },
+        Change {
+            work_item_id: new_item.clone(),
+            data: ChangeData::AddToProject,
+        },
+    ];
+
+    let mut history = SaveHistory::default();
+    let number = history
+        .record(&succeeded(&saved), &data.work_items)
+        .unwrap();
+
+    let mut expected = Changes::default();
+    expected.add(Change {
+        work_item_id: child.clone(),
+        data: ChangeData::Status(data.fields.status.option_id(Some("Active")).cloned()),
+    });
+    expected.add(Change {
+        work_item_id: child,
+        data: ChangeData::RemoveParent(parent),
+    });
+    expected.add(Change {
+        work_item_id: new_item,
+        data: ChangeData::RemoveFromProject,
+    });
+
+    assert_eq!(history.get(number).unwrap().revert_changes(), expected);
+}
@@ -28,7 +28,7 @@ fn test_closed_issues_set_state_to_closed() {
     });
 
     assert_eq!(report.changes, expected_changes);
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -58,23 +58,15 @@ fn test_set_epic_from_parent() {
     assert_eq!(report.changes, expected_changes);
 
     // child_later_epic has a conflicting Epic — it must appear in conflicts.
-    assert_eq!(report.epic_conflicts.len(), 1);
-    assert_eq!(report.epic_conflicts[0].work_item_id, child_later_epic);
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].work_item_id, child_later_epic);
     assert_eq!(
-        report.epic_conflicts[0].proposed_epic,
-        data.fields
-            .epic
-            .option_id(RIGHT_EPIC.into())
-            .cloned()
-            .unwrap()
+        report.conflicts[0].proposed,
+        ChangeData::Epic(data.fields.epic.option_id(RIGHT_EPIC.into()).cloned())
     );
     assert_eq!(
-        report.epic_conflicts[0].current_epic,
-        data.fields
-            .epic
-            .option_id(LATER_EPIC.into())
-            .cloned()
-            .unwrap()
+        report.conflicts[0].current,
+        ChangeData::Epic(data.fields.epic.option_id(LATER_EPIC.into()).cloned())
     );
     // child_right_epic already has the correct Epic — no conflict.
     let _ = child_right_epic;
@@ -93,7 +85,7 @@ fn test_child_epic_before_parent_no_conflict() {
     let report = data.work_items.sanitize(&data.fields);
 
     assert_eq!(report.changes, Changes::default());
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -128,7 +120,7 @@ fn test_child_epic_propagates_to_grandchild() {
     });
 
     assert_eq!(report.changes, expected_changes);
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -158,24 +150,21 @@ fn test_child_epic_after_parent_with_grandchild() {
     });
     assert_eq!(report.changes, expected_changes);
 
-    assert_eq!(report.epic_conflicts.len(), 1);
-    let conflict = &report.epic_conflicts[0];
+    assert_eq!(report.conflicts.len(), 1);
+    let conflict = &report.conflicts[0];
     assert_eq!(conflict.work_item_id, child);
     assert_eq!(
-        conflict.current_epic,
-        data.fields
-            .epic
-            .option_id(CHILD_LATER_EPIC.into())
-            .cloned()
-            .unwrap()
+        conflict.current,
+        ChangeData::Epic(data.fields.epic.option_id(CHILD_LATER_EPIC.into()).cloned())
     );
     assert_eq!(
-        conflict.proposed_epic,
-        data.fields
-            .epic
-            .option_id(PARENT_EARLIER_EPIC.into())
-            .cloned()
-            .unwrap()
+        conflict.proposed,
+        ChangeData::Epic(
+            data.fields
+                .epic
+                .option_id(PARENT_EARLIER_EPIC.into())
+                .cloned()
+        )
     );
 }
 
@@ -215,7 +204,7 @@ fn test_set_epic_from_grandparent() {
     });
 
     assert_eq!(report.changes, expected_changes);
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -243,13 +232,18 @@ fn test_set_workstream_from_parent() {
         work_item_id: child_blank,
         data: ws.clone(),
     });
-    expected_changes.add(Change {
-        work_item_id: child_wrong,
-        data: ws,
-    });
 
     assert_eq!(report.changes, expected_changes);
-    assert!(report.epic_conflicts.is_empty());
+
+    // child_wrong already has a Workstream, so it is left for review.
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].work_item_id, child_wrong);
+    assert_eq!(report.conflicts[0].field, "workstream");
+    assert_eq!(
+        report.conflicts[0].current,
+        ChangeData::Workstream(data.fields.workstream.option_id(WS_WRONG.into()).cloned())
+    );
+    assert_eq!(report.conflicts[0].proposed, ws);
 }
 
 #[test]
@@ -265,7 +259,7 @@ fn test_set_workstream_on_blank_parent_from_child() {
 
     // Parent is blank: sanitize does not change workstreams.
     assert_eq!(report.changes, Changes::default());
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -292,7 +286,7 @@ fn test_add_missing_parent_to_project() {
     });
 
     assert_eq!(report.changes, expected_changes);
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -322,7 +316,7 @@ fn test_add_shared_missing_parent_to_project_once() {
     });
 
     assert_eq!(report.changes, expected_changes);
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -350,7 +344,7 @@ fn test_add_missing_grandparent_to_project() {
     });
 
     assert_eq!(report.changes, expected_changes);
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -366,7 +360,7 @@ fn test_blank_parent_with_conflicting_children_workstreams_no_change() {
 
     // Parent is blank: sanitize does not change workstreams.
     assert_eq!(report.changes, Changes::default());
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -385,7 +379,7 @@ fn test_assigned_issue_with_no_status_gets_planning() {
     });
 
     assert_eq!(report.changes, expected_changes);
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -397,7 +391,7 @@ fn test_assigned_issue_with_status_set_no_change() {
     let report = data.work_items.sanitize(&data.fields);
 
     assert_eq!(report.changes, Changes::default());
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -409,7 +403,7 @@ fn test_unassigned_issue_with_no_status_no_change() {
     let report = data.work_items.sanitize(&data.fields);
 
     assert_eq!(report.changes, Changes::default());
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -432,7 +426,7 @@ fn test_closed_assigned_issue_gets_closed_not_planning() {
     });
 
     assert_eq!(report.changes, expected_changes);
-    assert!(report.epic_conflicts.is_empty());
+    assert!(report.conflicts.is_empty());
 }
 
 #[test]
@@ -443,7 +437,8 @@ fn test_epic_conflict_recorded_not_changed() {
     const CHILD_EPIC: &str = "MiniEngine Demo";
 
     let child_id = data.build().epic(CHILD_EPIC).add();
-    data.build()
+    let parent_id = data
+        .build()
         .epic(PARENT_EPIC)
         .sub_issues(&[&child_id])
         .add();
@@ -454,23 +449,449 @@ fn test_epic_conflict_recorded_not_changed() {
     assert_eq!(report.changes, Changes::default());
 
     // But the conflict is recorded for the user to review.
-    assert_eq!(report.epic_conflicts.len(), 1);
-    let conflict = &report.epic_conflicts[0];
+    assert_eq!(report.conflicts.len(), 1);
+    let conflict = &report.conflicts[0];
     assert_eq!(conflict.work_item_id, child_id);
     assert_eq!(
-        conflict.current_epic,
-        data.fields
-            .epic
-            .option_id(CHILD_EPIC.into())
-            .cloned()
-            .unwrap()
+        conflict.current,
+        ChangeData::Epic(data.fields.epic.option_id(CHILD_EPIC.into()).cloned())
     );
     assert_eq!(
-        conflict.proposed_epic,
-        data.fields
-            .epic
-            .option_id(PARENT_EPIC.into())
-            .cloned()
-            .unwrap()
+        conflict.proposed,
+        ChangeData::Epic(data.fields.epic.option_id(PARENT_EPIC.into()).cloned())
+    );
+    assert_eq!(conflict.field, "epic");
+    assert_eq!(conflict.source_item_id, Some(parent_id));
+}
+
+#[test]
+fn test_entries_are_tagged_with_rule() {
+    let mut data = TestData::default();
+
+    let closed_id = data
+        .build()
+        .issue_state(IssueState::CLOSED)
+        .status("Active")
+        .add();
+    let child_id = data.build().add();
+    data.build().epic("EpicA").sub_issues(&[&child_id]).add();
+
+    let report = data.work_items.sanitize(&data.fields);
+
+    let mut rules: Vec<_> = report
+        .entries
+        .iter()
+        .map(|entry| (entry.rule.as_str(), entry.change.work_item_id.clone()))
+        .collect();
+    rules.sort();
+    assert_eq!(
+        rules,
+        vec![("closed-status", closed_id), ("inherit-epic", child_id)]
+    );
+    assert!(report
+        .entries
+        .iter()
+        .all(|entry| entry.severity == RuleSeverity::Warning));
+}
+
+#[test]
+fn test_config_disables_rule_and_overrides_severity() {
+    let mut data = TestData::default();
+
+    data.build()
+        .issue_state(IssueState::CLOSED)
+        .status("Active")
+        .add();
+    let child_id = data.build().add();
+    data.build().epic("EpicA").sub_issues(&[&child_id]).add();
+
+    let config: SanitizeConfig = serde_json::from_value(serde_json::json!({
+        "rules": {
+            "closed-status": { "enabled": false },
+            "inherit-epic": { "severity": "error" }
+        }
+    }))
+    .unwrap();
+
+    let report = data.work_items.sanitize_with(&data.fields, &config);
+
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(report.entries[0].rule, "inherit-epic");
+    assert_eq!(report.entries[0].severity, RuleSeverity::Error);
+    assert_eq!(report.changes.len(), 1);
+}
+
+#[test]
+fn test_retain_rules_filters_changes_and_conflicts() {
+    let mut data = TestData::default();
+
+    data.build()
+        .issue_state(IssueState::CLOSED)
+        .status("Active")
+        .add();
+    let child_id = data.build().epic("MiniEngine Demo").add();
+    data.build().epic("DML Demo").sub_issues(&[&child_id]).add();
+
+    let mut report = data.work_items.sanitize(&data.fields);
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].rule, "inherit-epic");
+
+    report.retain_rules(|rule| rule == "closed-status");
+
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(report.changes.len(), 1);
+    assert!(report.conflicts.is_empty());
+}
+
+#[test]
+fn test_safe_changes() {
+    let mut data = TestData::default();
+
+    let closed_id = data
+        .build()
+        .issue_state(IssueState::CLOSED)
+        .status("Active")
+        .add();
+    let assigned_id = data.build().issue().assignees(&["someone"]).add();
+
+    let report = data.work_items.sanitize(&data.fields);
+    assert_eq!(report.changes.len(), 2);
+
+    let safe: Vec<_> = report
+        .safe_changes()
+        .into_iter()
+        .map(|change| change.work_item_id.clone())
+        .collect();
+    assert_eq!(safe, vec![closed_id]);
+
+    let config: SanitizeConfig = serde_json::from_value(serde_json::json!({
+        "rules": {
+            "closed-status": { "safe": false },
+            "assigned-planning": { "safe": true }
+        }
+    }))
+    .unwrap();
+
+    let report = data.work_items.sanitize_with(&data.fields, &config);
+    let safe: Vec<_> = report
+        .safe_changes()
+        .into_iter()
+        .map(|change| change.work_item_id.clone())
+        .collect();
+    assert_eq!(safe, vec![assigned_id]);
+}
+
+struct DraftsNeedStatusRule;
+
+impl SanitizeRule for DraftsNeedStatusRule {
+    fn id(&self) -> &'static str {
+        "drafts-need-status"
+    }
+
+    fn description(&self) -> &'static str {
+        "Draft issues are set to Planning"
+    }
+
+    fn check(&self, work_items: &WorkItems, fields: &Fields, findings: &mut SanitizeFindings) {
+        for item in work_items.work_items.values() {
+            if let WorkItemData::DraftIssue = item.data {
+                findings.add_change(Change {
+                    work_item_id: item.id.clone(),
+                    data: ChangeData::Status(fields.status.option_id(Some("Planning")).cloned()),
+                });
+            }
+        }
+    }
+}
+
+#[test]
+fn test_custom_rule() {
+    let mut data = TestData::default();
+    let draft_id = data.build().add();
+
+    let mut rules = SanitizeRules::empty();
+    rules.add(DraftsNeedStatusRule);
+
+    let report = rules.run(&data.work_items, &data.fields, &SanitizeConfig::default());
+
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(report.entries[0].rule, "drafts-need-status");
+    assert_eq!(report.entries[0].change.work_item_id, draft_id);
+    assert_eq!(
+        rules.describe(&SanitizeConfig::default()),
+        vec![SanitizeRuleInfo {
+            id: "drafts-need-status".to_owned(),
+            description: "Draft issues are set to Planning".to_owned(),
+            severity: RuleSeverity::Warning,
+            enabled: true,
+            safe: false,
+        }]
     );
 }
+
+struct DraftsAreActiveRule;
+
+impl SanitizeRule for DraftsAreActiveRule {
+    fn id(&self) -> &'static str {
+        "drafts-are-active"
+    }
+
+    fn description(&self) -> &'static str {
+        "Draft issues are set to Active"
+    }
+
+    fn check(&self, work_items: &WorkItems, fields: &Fields, findings: &mut SanitizeFindings) {
+        for item in work_items.work_items.values() {
+            if let WorkItemData::DraftIssue = item.data {
+                findings.add_change(Change {
+                    work_item_id: item.id.clone(),
+                    data: ChangeData::Status(fields.status.option_id(Some("Active")).cloned()),
+                });
+            }
+        }
+    }
+}
+
+#[test]
+fn test_later_rule_proposing_same_field_is_reported_not_applied() {
+    let mut data = TestData::default();
+    let draft_id = data.build().add();
+
+    let mut rules = SanitizeRules::empty();
+    rules.add(DraftsNeedStatusRule);
+    rules.add(DraftsAreActiveRule);
+
+    let mut report = rules.run(&data.work_items, &data.fields, &SanitizeConfig::default());
+
+    let mut expected_changes = Changes::default();
+    expected_changes.add(Change {
+        work_item_id: draft_id.clone(),
+        data: ChangeData::Status(data.fields.status.option_id(Some("Planning")).cloned()),
+    });
+
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(report.entries[0].rule, "drafts-need-status");
+    assert_eq!(report.changes, expected_changes);
+    assert_eq!(report.problems.len(), 1);
+    assert_eq!(report.problems[0].work_item_id, draft_id);
+    assert_eq!(report.problems[0].rule, "drafts-are-active");
+    assert_eq!(
+        report.problems[0].description,
+        "wants status set to Active, but `drafts-need-status` already proposes Planning; not applied"
+    );
+
+    report.retain_rules(|rule| rule == "drafts-are-active");
+    assert!(report.changes.is_empty());
+    assert_eq!(report.problems.len(), 1);
+}
+
+fn run_rule(rule: impl SanitizeRule + 'static, data: &TestData) -> SanitizeReport {
+    let mut rules = SanitizeRules::empty();
+    rules.add(rule);
+    rules.run(&data.work_items, &data.fields, &SanitizeConfig::default())
+}
+
+#[test]
+fn test_closed_parent_with_open_sub_issue_is_a_problem() {
+    let mut data = TestData::default();
+
+    let open_child = data.build().issue_state(IssueState::OPEN).add();
+    let closed_child = data.build().issue_state(IssueState::CLOSED).add();
+    let parent = data
+        .build()
+        .issue_state(IssueState::CLOSED)
+        .sub_issues(&[&open_child, &closed_child])
+        .add();
+    data.build()
+        .issue_state(IssueState::CLOSED)
+        .sub_issues(&[&closed_child])
+        .add();
+
+    let report = run_rule(ClosedParentOpenSubIssuesRule, &data);
+
+    assert!(report.changes.is_empty());
+    assert_eq!(report.problems.len(), 1);
+    assert_eq!(report.problems[0].work_item_id, parent);
+    assert_eq!(report.problems[0].rule, "closed-parent-open-sub-issues");
+    assert_eq!(
+        report.problems[0].description,
+        "closed, but 1 sub-issue(s) are still open"
+    );
+}
+
+fn set_iterations(data: &mut TestData) {
+    data.fields.iteration.options[0].data = Iteration {
+        start_date: "2026-01-01".to_owned(),
+        duration: 14,
+    };
+    data.fields.iteration.options[1].data = Iteration {
+        start_date: "2026-01-15".to_owned(),
+        duration: 14,
+    };
+}
+
+#[test]
+fn test_open_item_in_past_iteration_moves_to_current() {
+    let mut data = TestData::default();
+    set_iterations(&mut data);
+
+    let open_id = data.build().issue().iteration("S1").add();
+    data.build()
+        .issue_state(IssueState::CLOSED)
+        .iteration("S1")
+        .add();
+    data.build().issue().iteration("S2").add();
+
+    let today = time::Date::from_calendar_date(2026, time::Month::January, 20).unwrap();
+    let report = run_rule(OpenInPastIterationRule::new(today), &data);
+
+    assert!(report.changes.is_empty());
+    assert!(report.problems.is_empty());
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].work_item_id, open_id);
+    assert_eq!(
+        report.conflicts[0].proposed,
+        ChangeData::Iteration(data.fields.iteration.option_id(Some("S2")).cloned())
+    );
+}
+
+#[test]
+fn test_open_item_in_past_iteration_without_current_is_a_problem() {
+    let mut data = TestData::default();
+    set_iterations(&mut data);
+
+    let open_id = data.build().issue().iteration("S2").add();
+
+    let today = time::Date::from_calendar_date(2026, time::Month::March, 1).unwrap();
+    let report = run_rule(OpenInPastIterationRule::new(today), &data);
+
+    assert!(report.changes.is_empty());
+    assert_eq!(report.problems.len(), 1);
+    assert_eq!(report.problems[0].work_item_id, open_id);
+    assert_eq!(
+        report.problems[0].description,
+        "still open in S2, which has ended"
+    );
+}
+
+#[test]
+fn test_open_issue_missing_fields_is_a_problem() {
+    let mut data = TestData::default();
+
+    let missing_id = data.build().issue().add();
+    let complete_id = data.build().issue().add();
+    data.build().issue_state(IssueState::CLOSED).add();
+    data.build().add();
+
+    let complete = &mut data.work_items.get_mut(&complete_id).unwrap().project_item;
+    complete.kind = data.fields.kind.option_id(Some("Task")).cloned().into();
+    complete.priority = data.fields.priority.option_id(Some("High")).cloned();
+    complete.estimate = data.fields.estimate.option_id(Some("Days")).cloned();
+
+    let report = run_rule(MissingFieldsRule, &data);
+
+    assert_eq!(report.problems.len(), 1);
+    assert_eq!(report.problems[0].work_item_id, missing_id);
+    assert_eq!(report.problems[0].severity, RuleSeverity::Info);
+    assert_eq!(
+        report.problems[0].description,
+        "missing Kind, Priority, Estimate"
+    );
+}
+
+#[test]
+fn test_open_pull_request_without_linked_issue_is_a_problem() {
+    let mut data = TestData::default();
+
+    let issue_id = data.build().issue().add();
+    let unlinked_id = data.build().add();
+    let linked_id = data.build().add();
+    let merged_id = data.build().add();
+
+    let mut set_pull_request = |id: &WorkItemId, state, linked_issues: Vec<WorkItemId>| {
+        data.work_items.get_mut(id).unwrap().data = WorkItemData::PullRequest(PullRequest {
+            state: DelayLoad::Loaded(state),
+            linked_issues: linked_issues.into(),
+            ..Default::default()
+        });
+    };
+    set_pull_request(&unlinked_id, PullRequestState::OPEN, vec![]);
+    set_pull_request(&linked_id, PullRequestState::OPEN, vec![issue_id]);
+    set_pull_request(&merged_id, PullRequestState::MERGED, vec![]);
+
+    let report = run_rule(PullRequestWithoutIssueRule, &data);
+
+    assert_eq!(report.problems.len(), 1);
+    assert_eq!(report.problems[0].work_item_id, unlinked_id);
+    assert_eq!(report.problems[0].rule, "pr-without-issue");
+}
+
+#[test]
+fn test_retain_rules_filters_problems() {
+    let mut data = TestData::default();
+    data.build().issue().add();
+
+    let mut report = data.work_items.sanitize(&data.fields);
+    assert!(report
+        .problems
+        .iter()
+        .any(|problem| problem.rule == "missing-fields"));
+
+    report.retain_rules(|rule| rule == "closed-status");
+
+    assert!(report.problems.is_empty());
+}
+
+#[test]
+fn test_inherited_epic_conflict_names_source_item() {
+    let mut data = TestData::default();
+
+    let grandchild_id = data.build().epic("MiniEngine Demo").add();
+    let child_id = data.build().sub_issues(&[&grandchild_id]).add();
+    let grandparent_id = data.build().epic("DML Demo").sub_issues(&[&child_id]).add();
+
+    let report = data.work_items.sanitize(&data.fields);
+
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].work_item_id, grandchild_id);
+    assert_eq!(report.conflicts[0].source_item_id, Some(grandparent_id));
+}
+
+#[test]
+fn test_resolve_conflicts() {
+    let mut data = TestData::default();
+
+    let keep_id = data.build().workstream("WS2").add();
+    let use_id = data.build().workstream("WS2").add();
+    let unresolved_id = data.build().workstream("WS2").add();
+    data.build()
+        .workstream("WS1")
+        .sub_issues(&[&keep_id, &use_id, &unresolved_id])
+        .add();
+
+    let mut conflicts = data.work_items.sanitize(&data.fields).conflicts;
+    assert_eq!(conflicts.len(), 3);
+
+    let choice = |id: &WorkItemId, resolution| {
+        conflicts
+            .iter()
+            .find(|conflict| &conflict.work_item_id == id)
+            .unwrap()
+            .choose(resolution)
+    };
+    let choices = [
+        choice(&keep_id, ConflictResolution::KeepCurrent),
+        choice(&use_id, ConflictResolution::UseProposed),
+    ];
+    let changes = resolve_conflicts(&mut conflicts, &choices);
+
+    let mut expected_changes = Changes::default();
+    expected_changes.add(Change {
+        work_item_id: use_id,
+        data: ChangeData::Workstream(data.fields.workstream.option_id(Some("WS1")).cloned()),
+    });
+    assert_eq!(changes, expected_changes);
+
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].work_item_id, unresolved_id);
+}
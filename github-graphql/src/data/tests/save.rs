@@ -1,111 +1,9 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    sync::{Arc, Mutex},
+use super::mock_client::{issue_types_response, MockClient};
+use crate::data::{
+    test_helpers::TestData, Change, ChangeData, ChangeOutcome, ChangeProblem, Changes,
+    FieldOptionId, IssueTypeRegistry, ProjectItemId, SaveErrorKind, SaveMode,
 };
 
-use serde::{de::DeserializeOwned, Serialize};
-
-use crate::{
-    client::transport::Client,
-    data::{
-        test_helpers::TestData, Change, ChangeData, Changes, FieldOptionId, ProjectItemId, SaveMode,
-    },
-};
-
-/// A mock Client that inspects each request's query string to determine the
-/// mutation type and returns the corresponding pre-configured response.
-#[derive(Clone)]
-struct MockClient {
-    responses: Arc<Mutex<HashMap<String, VecDeque<MockResponse>>>>,
-}
-
-/// A queued response for a mutation keyword.
-enum MockResponse {
-    /// A successful JSON response body.
-    Json(serde_json::Value),
-    /// Simulates the network being unreachable.
-    ConnectivityError,
-}
-
-impl MockClient {
-    fn new() -> Self {
-        Self {
-            responses: Arc::new(Mutex::new(HashMap::new())),
-        }
-    }
-
-    fn on_mutation(self, keyword: &str, response: serde_json::Value) -> Self {
-        self.responses
-            .lock()
-            .unwrap()
-            .entry(keyword.to_string())
-            .or_default()
-            .push_back(MockResponse::Json(response));
-        self
-    }
-
-    /// Queues a connectivity (network-down) failure for the given mutation.
-    fn on_mutation_connectivity_error(self, keyword: &str) -> Self {
-        self.responses
-            .lock()
-            .unwrap()
-            .entry(keyword.to_string())
-            .or_default()
-            .push_back(MockResponse::ConnectivityError);
-        self
-    }
-
-    /// Asserts that all queued mock responses were consumed.  Call this after
-    /// `save()` to verify no expected mutations were silently skipped.
-    fn assert_all_consumed(&self) {
-        let responses = self.responses.lock().unwrap();
-        for (keyword, queue) in responses.iter() {
-            assert!(
-                queue.is_empty(),
-                "MockClient: {count} unconsumed response(s) for '{keyword}'",
-                count = queue.len()
-            );
-        }
-    }
-}
-
-/// Builds a connectivity error equivalent to gh failing because GitHub is
-/// unreachable.
-fn connectivity_error() -> crate::Error {
-    crate::Error::Connectivity("dial tcp: no such host".into())
-}
-
-impl Client for MockClient {
-    async fn request<Q, R>(&self, request: &Q) -> crate::Result<R>
-    where
-        Q: Serialize + Sync,
-        R: DeserializeOwned,
-    {
-        let request_json = serde_json::to_value(request).unwrap();
-        let query = request_json["query"].as_str().unwrap_or("");
-
-        let response = {
-            let mut responses = self.responses.lock().unwrap();
-            responses
-                .iter_mut()
-                .find_map(|(keyword, queue)| {
-                    if query.contains(keyword.as_str()) {
-                        queue.pop_front()
-                    } else {
-                        None
-                    }
-                })
-                .unwrap_or_else(|| panic!("MockClient: no response for query containing: {query}"))
-        };
-
-        match response {
-            MockResponse::Json(value) => serde_json::from_value(value)
-                .map_err(|e| crate::Error::GraphQlResponseUnexpected(e.to_string())),
-            MockResponse::ConnectivityError => Err(connectivity_error()),
-        }
-    }
-}
-
 /// JSON response for `add_to_project` mutation.
 fn add_to_project_response(project_item_id: &str) -> serde_json::Value {
     serde_json::json!({
@@ -159,11 +57,13 @@ async fn test_save_dry_run_returns_project_item_ids_for_existing_items() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::DryRun,
             &noop_progress,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .project_item_ids;
 
     result.sort_by(|a, b| a.0.cmp(&b.0));
 
@@ -190,11 +90,13 @@ async fn test_save_dry_run_skips_items_not_in_work_items() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::DryRun,
             &noop_progress,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .project_item_ids;
 
     // AddToProject for nonexistent item: the WorkItemId can't be resolved to a
     // ProjectItemId (not in work_items), and DryRun doesn't call add_to_project,
@@ -220,11 +122,13 @@ async fn test_save_commit_add_to_project_returns_new_project_item_id() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::Commit,
             &noop_progress,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .project_item_ids;
 
     assert!(result.contains(&ProjectItemId("PVTI_new".into())));
 }
@@ -249,11 +153,13 @@ async fn test_save_commit_field_change_returns_project_item_id() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::Commit,
             &noop_progress,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .project_item_ids;
 
     assert!(result.contains(&project_item_id));
 }
@@ -292,11 +198,13 @@ async fn test_save_commit_mixed_changes_returns_all_project_item_ids() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::Commit,
             &noop_progress,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .project_item_ids;
 
     assert!(result.contains(&existing_project_item_id));
     assert!(result.contains(&ProjectItemId("PVTI_added".into())));
@@ -313,11 +221,13 @@ async fn test_save_empty_changes_returns_empty() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::Commit,
             &noop_progress,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .project_item_ids;
 
     assert!(result.is_empty());
 }
@@ -357,11 +267,13 @@ async fn test_save_field_change_uses_project_item_id_from_add_to_project() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::Commit,
             &noop_progress,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .project_item_ids;
 
     // AddToProject returns a ProjectItemId directly.
     assert!(result.contains(&ProjectItemId("PVTI_new".into())));
@@ -391,11 +303,13 @@ async fn test_save_field_change_for_existing_item_still_works() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::Commit,
             &noop_progress,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .project_item_ids;
 
     assert!(result.contains(&project_item_id));
 }
@@ -428,11 +342,13 @@ async fn test_save_aborts_remaining_changes_on_connectivity_error() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::Commit,
             &noop_progress,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .project_item_ids;
 
     // Nothing was saved successfully.
     assert!(result.is_empty());
@@ -472,6 +388,7 @@ async fn test_save_continues_after_non_connectivity_error() {
             &client,
             &data.fields,
             &data.work_items,
+            &mut IssueTypeRegistry::default(),
             SaveMode::Commit,
             &noop_progress,
         )
@@ -482,3 +399,412 @@ async fn test_save_continues_after_non_connectivity_error() {
     assert_eq!(changes.len(), 1);
     client.assert_all_consumed();
 }
+
+/// JSON response for the `updateIssueIssueType` mutation.
+fn set_issue_type_response() -> serde_json::Value {
+    serde_json::json!({
+        "data": {
+            "updateIssueIssueType": {
+                "clientMutationId": null
+            }
+        }
+    })
+}
+
+/// Issue types should be fetched once per repository, not once per change.
+#[tokio::test]
+async fn test_save_issue_types_queried_once_per_repository() {
+    let mut data = TestData::default();
+    let id1 = data.build().issue().repository("owner/repo").add();
+    let id2 = data.build().issue().repository("owner/repo").add();
+
+    let mut changes = Changes::default();
+    for id in [id1, id2] {
+        changes.add(Change {
+            work_item_id: id,
+            data: ChangeData::IssueType(Some("Bug".into())),
+        });
+    }
+
+    // Only a single GetIssueTypes response is queued; a second query would
+    // panic in the mock.
+    let client = MockClient::new()
+        .on_mutation("GetIssueTypes", issue_types_response(&["Bug", "Task"]))
+        .on_mutation("updateIssueIssueType", set_issue_type_response())
+        .on_mutation("updateIssueIssueType", set_issue_type_response());
+
+    let mut issue_types = IssueTypeRegistry::default();
+    changes
+        .save(
+            &client,
+            &data.fields,
+            &data.work_items,
+            &mut issue_types,
+            SaveMode::Commit,
+            &noop_progress,
+        )
+        .await
+        .unwrap();
+
+    assert!(changes.is_empty());
+    client.assert_all_consumed();
+
+    let names: Vec<_> = issue_types
+        .get("owner", "repo")
+        .unwrap()
+        .name_to_id
+        .keys()
+        .cloned()
+        .collect();
+    assert_eq!(names.len(), 2);
+}
+
+/// Issue types already in the registry are reused without querying GitHub.
+#[tokio::test]
+async fn test_save_issue_types_uses_cached_registry() {
+    let mut data = TestData::default();
+    let id = data.build().issue().repository("owner/repo").add();
+
+    let mut changes = Changes::default();
+    changes.add(Change {
+        work_item_id: id,
+        data: ChangeData::IssueType(Some("Task".into())),
+    });
+
+    let mut issue_types = IssueTypeRegistry::default();
+    let warm_client =
+        MockClient::new().on_mutation("GetIssueTypes", issue_types_response(&["Task"]));
+    issue_types
+        .get_or_load(&warm_client, "owner", "repo")
+        .await
+        .unwrap();
+
+    let client = MockClient::new().on_mutation("updateIssueIssueType", set_issue_type_response());
+
+    changes
+        .save(
+            &client,
+            &data.fields,
+            &data.work_items,
+            &mut issue_types,
+            SaveMode::Commit,
+            &noop_progress,
+        )
+        .await
+        .unwrap();
+
+    assert!(changes.is_empty());
+    client.assert_all_consumed();
+}
+
+/// An issue type name that doesn't exist in the repository must not silently
+/// clear the issue type; the change fails and stays queued.
+#[tokio::test]
+async fn test_save_issue_type_unknown_name_stays_queued() {
+    let mut data = TestData::default();
+    let id = data.build().issue().repository("owner/repo").add();
+
+    let mut changes = Changes::default();
+    changes.add(Change {
+        work_item_id: id,
+        data: ChangeData::IssueType(Some("Epic".into())),
+    });
+
+    let client =
+        MockClient::new().on_mutation("GetIssueTypes", issue_types_response(&["Bug", "Task"]));
+
+    changes
+        .save(
+            &client,
+            &data.fields,
+            &data.work_items,
+            &mut IssueTypeRegistry::default(),
+            SaveMode::Commit,
+            &noop_progress,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(changes.len(), 1);
+    client.assert_all_consumed();
+}
+
+/// Changes that fail validation are not sent to GitHub and remain queued.
+#[tokio::test]
+async fn test_save_skips_invalid_changes() {
+    let mut data = TestData::default();
+    let id1 = data.build().status("Active").add();
+    let id2 = data.build().status("Active").add();
+
+    let mut changes = Changes::default();
+    changes.add(Change {
+        work_item_id: id1,
+        data: ChangeData::Status(Some(FieldOptionId("id(Deleted)".into()))),
+    });
+    changes.add(Change {
+        work_item_id: id2,
+        data: ChangeData::Status(Some(FieldOptionId("id(Closed)".into()))),
+    });
+
+    // Only the valid change should reach the client.
+    let client =
+        MockClient::new().on_mutation("updateProjectV2ItemFieldValue", field_mutation_response());
+
+    changes
+        .save(
+            &client,
+            &data.fields,
+            &data.work_items,
+            &mut IssueTypeRegistry::default(),
+            SaveMode::Commit,
+            &noop_progress,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(changes.len(), 1);
+    client.assert_all_consumed();
+}
+
+/// The report records the failing change, and every change that was not
+/// attempted after a connectivity error.
+#[tokio::test]
+async fn test_save_report_connectivity_abort() {
+    let mut data = TestData::default();
+    let id1 = data.build().status("Active").add();
+    let id2 = data.build().status("Active").add();
+
+    let mut changes = Changes::default();
+    for id in [id1, id2] {
+        changes.add(Change {
+            work_item_id: id,
+            data: ChangeData::Status(Some(FieldOptionId("id(Closed)".into()))),
+        });
+    }
+
+    let client = MockClient::new().on_mutation_connectivity_error("updateProjectV2ItemFieldValue");
+
+    let report = changes
+        .save(
+            &client,
+            &data.fields,
+            &data.work_items,
+            &mut IssueTypeRegistry::default(),
+            SaveMode::Commit,
+            &noop_progress,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(report.results.len(), 2);
+    assert!(matches!(
+        report.results[0].outcome,
+        ChangeOutcome::Failed {
+            kind: SaveErrorKind::Connectivity,
+            ..
+        }
+    ));
+    assert_eq!(
+        report.results[1].outcome,
+        ChangeOutcome::SkippedConnectivity
+    );
+    assert_eq!(report.failed_count(), 1);
+    assert_eq!(report.skipped_count(), 1);
+    assert!(!report.is_success());
+}
+
+/// When adding an item to the project fails, the field changes for that item
+/// are skipped rather than attempted, and GitHub's error messages are kept.
+#[tokio::test]
+async fn test_save_report_skips_changes_depending_on_failed_add_to_project() {
+    let data = TestData::default();
+
+    let mut changes = Changes::default();
+    changes.add(Change {
+        work_item_id: "new_item".to_string().into(),
+        data: ChangeData::AddToProject,
+    });
+    changes.add(Change {
+        work_item_id: "new_item".to_string().into(),
+        data: ChangeData::Epic(Some(FieldOptionId("id(EpicA)".into()))),
+    });
+
+    let client = MockClient::new().on_mutation(
+        "addProjectV2ItemById",
+        serde_json::json!({
+            "data": null,
+            "errors": [{ "message": "Could not resolve to a node" }]
+        }),
+    );
+
+    let report = changes
+        .save(
+            &client,
+            &data.fields,
+            &data.work_items,
+            &mut IssueTypeRegistry::default(),
+            SaveMode::Commit,
+            &noop_progress,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(changes.len(), 2);
+    client.assert_all_consumed();
+
+    let ChangeOutcome::Failed {
+        kind,
+        graphql_messages,
+        ..
+    } = &report.results[0].outcome
+    else {
+        panic!("expected failure, got {:?}", report.results[0].outcome);
+    };
+    assert_eq!(*kind, SaveErrorKind::GraphQl);
+    assert_eq!(
+        graphql_messages,
+        &vec!["Could not resolve to a node".to_string()]
+    );
+    assert_eq!(
+        report.results[1].outcome,
+        ChangeOutcome::SkippedDependency {
+            dependency: "new_item".to_string().into()
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_save_report_dry_run_and_invalid_outcomes() {
+    let mut data = TestData::default();
+    let id1 = data.build().status("Active").add();
+    let id2 = data.build().status("Active").add();
+
+    let mut changes = Changes::default();
+    changes.add(Change {
+        work_item_id: id1,
+        data: ChangeData::Status(Some(FieldOptionId("id(Closed)".into()))),
+    });
+    changes.add(Change {
+        work_item_id: id2,
+        data: ChangeData::Status(Some(FieldOptionId("id(Deleted)".into()))),
+    });
+
+    let report = changes
+        .save(
+            &MockClient::new(),
+            &data.fields,
+            &data.work_items,
+            &mut IssueTypeRegistry::default(),
+            SaveMode::DryRun,
+            &noop_progress,
+        )
+        .await
+        .unwrap();
+
+    let mut outcomes: Vec<_> = report.results.iter().map(|r| &r.outcome).collect();
+    outcomes.sort_by_key(|o| o.is_success());
+    assert_eq!(
+        outcomes,
+        vec![
+            &ChangeOutcome::Invalid {
+                problem: ChangeProblem::UnknownFieldOption {
+                    field: "status".into(),
+                    option: FieldOptionId("id(Deleted)".into()),
+                }
+            },
+            &ChangeOutcome::DryRun,
+        ]
+    );
+    assert_eq!(report.succeeded_count(), 1);
+    assert_eq!(report.skipped_count(), 1);
+}
+
+#[tokio::test]
+async fn test_save_remove_from_project_runs_last_and_drops_item() {
+    let mut data = TestData::default();
+    let id = data.build().status("Active").add();
+    let project_item_id = data.work_items.get(&id).unwrap().project_item.id.clone();
+
+    let mut changes = Changes::default();
+    changes.add(Change {
+        work_item_id: id.clone(),
+        data: ChangeData::RemoveFromProject,
+    });
+    changes.add(Change {
+        work_item_id: id.clone(),
+        data: ChangeData::Status(Some(FieldOptionId("id(Closed)".into()))),
+    });
+
+    let client = MockClient::new()
+        .on_mutation("updateProjectV2ItemFieldValue", field_mutation_response())
+        .on_mutation(
+            "deleteProjectV2Item",
+            serde_json::json!({
+                "data": { "deleteProjectV2Item": { "clientMutationId": null } }
+            }),
+        );
+
+    let report = changes
+        .save(
+            &client,
+            &data.fields,
+            &data.work_items,
+            &mut IssueTypeRegistry::default(),
+            SaveMode::Commit,
+            &noop_progress,
+        )
+        .await
+        .unwrap();
+
+    client.assert_all_consumed();
+    assert!(changes.is_empty());
+    assert_eq!(
+        report.results.last().unwrap().change.data,
+        ChangeData::RemoveFromProject
+    );
+    assert_eq!(report.removed_work_item_ids, vec![id]);
+    assert!(!report.project_item_ids.contains(&project_item_id));
+}
+
+#[tokio::test]
+async fn test_save_remove_parent_refreshes_old_parent() {
+    let mut data = TestData::default();
+    let child = data.build().issue().add();
+    let parent = data.add_blank_issue([&child], []);
+    let parent_project_item_id = data
+        .work_items
+        .get(&parent)
+        .unwrap()
+        .project_item
+        .id
+        .clone();
+
+    let mut changes = Changes::default();
+    changes.add(Change {
+        work_item_id: child,
+        data: ChangeData::RemoveParent(parent),
+    });
+
+    let client = MockClient::new().on_mutation(
+        "removeSubIssue",
+        serde_json::json!({
+            "data": { "removeSubIssue": { "clientMutationId": null } }
+        }),
+    );
+
+    let report = changes
+        .save(
+            &client,
+            &data.fields,
+            &data.work_items,
+            &mut IssueTypeRegistry::default(),
+            SaveMode::Commit,
+            &noop_progress,
+        )
+        .await
+        .unwrap();
+
+    client.assert_all_consumed();
+    assert!(report.is_success());
+    assert!(report.project_item_ids.contains(&parent_project_item_id));
+}
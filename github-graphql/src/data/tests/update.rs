@@ -19,6 +19,19 @@ fn test_update_existing_item_title_change() {
     let mut item = data.work_items.get(&id).unwrap().clone();
     item.title = "New Title".to_owned();
 
+    // Items can be sorted by title, and Parent and Root groups are labelled
+    // with it.
+    let update_type = data.work_items.update(item);
+    assert_eq!(update_type, UpdateType::ChangesHierarchy);
+}
+
+#[test]
+fn test_update_existing_item_updated_at_change() {
+    let mut data = TestData::default();
+    let id = data.build().status("Active").add();
+    let mut item = data.work_items.get(&id).unwrap().clone();
+    item.updated_at = "2025-01-02T00:00:00Z".to_owned();
+
     let update_type = data.work_items.update(item);
     assert_eq!(update_type, UpdateType::SimpleChange);
 }
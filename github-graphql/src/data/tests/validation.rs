@@ -0,0 +1,250 @@
+use super::mock_client::{issue_types_response, MockClient};
+use crate::{
+    client::graphql::get_issue_types::{IssueTypeId, IssueTypes},
+    data::{test_helpers::TestData, *},
+};
+
+fn validate(data: &TestData, changes: &Changes) -> Vec<ChangeProblem> {
+    changes
+        .validate(
+            &data.fields,
+            &data.work_items,
+            &IssueTypeRegistry::default(),
+        )
+        .into_iter()
+        .map(|d| d.problem)
+        .collect()
+}
+
+fn single_change(work_item_id: &WorkItemId, data: ChangeData) -> Changes {
+    let mut changes = Changes::default();
+    changes.add(Change {
+        work_item_id: work_item_id.clone(),
+        data,
+    });
+    changes
+}
+
+#[test]
+fn test_validate_valid_field_change_has_no_diagnostics() {
+    let mut data = TestData::default();
+    let id = data.build().issue().add();
+
+    let changes = single_change(
+        &id,
+        ChangeData::Status(Some(FieldOptionId("id(Closed)".into()))),
+    );
+
+    assert!(validate(&data, &changes).is_empty());
+}
+
+#[test]
+fn test_validate_unknown_field_option() {
+    let mut data = TestData::default();
+    let id = data.build().issue().add();
+
+    let changes = single_change(
+        &id,
+        ChangeData::Epic(Some(FieldOptionId("id(Deleted)".into()))),
+    );
+
+    assert_eq!(
+        validate(&data, &changes),
+        vec![ChangeProblem::UnknownFieldOption {
+            field: "epic".into(),
+            option: FieldOptionId("id(Deleted)".into()),
+        }]
+    );
+}
+
+#[test]
+fn test_validate_clearing_field_is_valid() {
+    let mut data = TestData::default();
+    let id = data.build().issue().epic("EpicA").add();
+
+    let changes = single_change(&id, ChangeData::Epic(None));
+
+    assert!(validate(&data, &changes).is_empty());
+}
+
+#[test]
+fn test_validate_work_item_not_found() {
+    let data = TestData::default();
+
+    let changes = single_change(
+        &WorkItemId("missing".into()),
+        ChangeData::Status(Some(FieldOptionId("id(Closed)".into()))),
+    );
+
+    assert_eq!(
+        validate(&data, &changes),
+        vec![ChangeProblem::WorkItemNotFound]
+    );
+}
+
+#[test]
+fn test_validate_field_change_for_item_being_added_to_project() {
+    let data = TestData::default();
+    let id = WorkItemId("new".into());
+
+    let mut changes = single_change(&id, ChangeData::AddToProject);
+    changes.add(Change {
+        work_item_id: id,
+        data: ChangeData::Epic(Some(FieldOptionId("id(EpicA)".into()))),
+    });
+
+    assert!(validate(&data, &changes).is_empty());
+}
+
+#[test]
+fn test_validate_issue_type_on_draft_issue() {
+    let mut data = TestData::default();
+    let draft = data.build().add();
+
+    let changes = single_change(&draft, ChangeData::IssueType(Some("Bug".into())));
+
+    assert_eq!(validate(&data, &changes), vec![ChangeProblem::NotAnIssue]);
+}
+
+#[test]
+fn test_validate_unknown_issue_type_in_repository() {
+    let mut data = TestData::default();
+    let id = data.build().issue().repository("owner/repo").add();
+
+    let mut issue_types = IssueTypeRegistry::default();
+    let mut repo_types = IssueTypes::default();
+    repo_types
+        .name_to_id
+        .insert("Bug".into(), IssueTypeId("IT_Bug".into()));
+    issue_types.insert("owner", "repo", repo_types);
+
+    let mut changes = single_change(&id, ChangeData::IssueType(Some("Epic".into())));
+    let problems: Vec<_> = changes
+        .validate(&data.fields, &data.work_items, &issue_types)
+        .into_iter()
+        .map(|d| d.problem)
+        .collect();
+    assert_eq!(
+        problems,
+        vec![ChangeProblem::UnknownIssueType {
+            name: "Epic".into()
+        }]
+    );
+
+    changes.add(Change {
+        work_item_id: id,
+        data: ChangeData::IssueType(Some("Bug".into())),
+    });
+    assert!(changes
+        .validate(&data.fields, &data.work_items, &issue_types)
+        .is_empty());
+}
+
+/// Repositories that haven't been loaded yet are queried, once each, so that
+/// unknown issue types are caught before saving.
+#[tokio::test]
+async fn test_validate_with_load_loads_issue_types() {
+    let mut data = TestData::default();
+    let bad = data.build().issue().repository("owner/repo").add();
+    let good = data.build().issue().repository("owner/repo").add();
+    let cleared = data.build().issue().repository("owner/other").add();
+
+    let mut changes = single_change(&bad, ChangeData::IssueType(Some("Epic".into())));
+    changes.add(Change {
+        work_item_id: good,
+        data: ChangeData::IssueType(Some("Bug".into())),
+    });
+    changes.add(Change {
+        work_item_id: cleared,
+        data: ChangeData::IssueType(None),
+    });
+
+    let client = MockClient::new().on_mutation("GetIssueTypes", issue_types_response(&["Bug"]));
+    let mut issue_types = IssueTypeRegistry::default();
+    let diagnostics = changes
+        .validate_with_load(&client, &data.fields, &data.work_items, &mut issue_types)
+        .await;
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].change.work_item_id, bad);
+    assert_eq!(
+        diagnostics[0].problem,
+        ChangeProblem::UnknownIssueType {
+            name: "Epic".into()
+        }
+    );
+    assert!(issue_types.get("owner", "repo").is_some());
+    assert!(issue_types.get("owner", "other").is_none());
+    client.assert_all_consumed();
+}
+
+#[test]
+fn test_validate_set_parent_to_self_is_cycle() {
+    let mut data = TestData::default();
+    let id = data.build().issue().add();
+
+    let changes = single_change(&id, ChangeData::SetParent(id.clone()));
+
+    assert_eq!(validate(&data, &changes), vec![ChangeProblem::ParentCycle]);
+}
+
+#[test]
+fn test_validate_set_parent_to_descendant_is_cycle() {
+    let mut data = TestData::default();
+    let grandchild = data.build().issue().add();
+    let child = data.build().issue().sub_issues(&[&grandchild]).add();
+    let root = data.build().issue().sub_issues(&[&child]).add();
+
+    let changes = single_change(&root, ChangeData::SetParent(grandchild));
+
+    assert_eq!(validate(&data, &changes), vec![ChangeProblem::ParentCycle]);
+}
+
+#[test]
+fn test_validate_cycle_across_staged_changes() {
+    let mut data = TestData::default();
+    let a = data.build().issue().add();
+    let b = data.build().issue().add();
+
+    let mut changes = single_change(&a, ChangeData::SetParent(b.clone()));
+    changes.add(Change {
+        work_item_id: b,
+        data: ChangeData::SetParent(a),
+    });
+
+    assert_eq!(
+        validate(&data, &changes),
+        vec![ChangeProblem::ParentCycle, ChangeProblem::ParentCycle]
+    );
+}
+
+#[test]
+fn test_validate_set_parent_to_pull_request() {
+    let mut data = TestData::default();
+    let child = data.build().issue().add();
+    let pr = data.next_id();
+    data.add_work_item(WorkItem {
+        id: pr.clone(),
+        data: WorkItemData::PullRequest(PullRequest::default()),
+        ..WorkItem::default_loaded()
+    });
+
+    let changes = single_change(&child, ChangeData::SetParent(pr));
+
+    assert_eq!(
+        validate(&data, &changes),
+        vec![ChangeProblem::ParentNotAnIssue]
+    );
+}
+
+#[test]
+fn test_validate_reparent_is_valid() {
+    let mut data = TestData::default();
+    let child = data.build().issue().add();
+    let _old_parent = data.build().issue().sub_issues(&[&child]).add();
+    let new_parent = data.build().issue().add();
+
+    let changes = single_change(&child, ChangeData::SetParent(new_parent));
+
+    assert!(validate(&data, &changes).is_empty());
+}
@@ -0,0 +1,250 @@
+use super::{
+    Change, ChangeData, Changes, Field, FieldOptionId, Fields, IssueTypeRegistry, WorkItem,
+    WorkItemData, WorkItemId, WorkItems,
+};
+use crate::client::transport::Client;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use ts_rs::TS;
+
+/// A problem found with a staged change that would prevent it from saving
+/// correctly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", tag = "type")]
+#[ts(export)]
+pub enum ChangeProblem {
+    /// The work item is not in the project and isn't being added to it.
+    WorkItemNotFound,
+    /// Issue types and parents can only be set on issues, not on draft issues
+    /// or pull requests.
+    NotAnIssue,
+    /// The new parent is a draft issue or pull request.
+    ParentNotAnIssue,
+    /// The issue type name doesn't exist in the item's repository.
+    UnknownIssueType { name: String },
+    /// The option no longer exists in the project field (for example, because
+    /// it was deleted on GitHub after the change was staged).
+    UnknownFieldOption {
+        field: String,
+        option: FieldOptionId,
+    },
+    /// Setting this parent would make the item an ancestor of itself.
+    ParentCycle,
+}
+
+impl ChangeProblem {
+    pub fn describe(&self) -> String {
+        match self {
+            ChangeProblem::WorkItemNotFound => "work item is not in the project".to_owned(),
+            ChangeProblem::NotAnIssue => "only issues support this change".to_owned(),
+            ChangeProblem::ParentNotAnIssue => "new parent is not an issue".to_owned(),
+            ChangeProblem::UnknownIssueType { name } => {
+                format!("issue type '{name}' does not exist in this repository")
+            }
+            ChangeProblem::UnknownFieldOption { field, option } => {
+                format!("option '{}' no longer exists in field {field}", option.0)
+            }
+            ChangeProblem::ParentCycle => "new parent would create a cycle".to_owned(),
+        }
+    }
+}
+
+/// A problem with a specific staged change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ChangeDiagnostic {
+    pub change: Change,
+    pub problem: ChangeProblem,
+}
+
+impl Changes {
+    /// Like [`Changes::validate`], but first loads the issue types of every
+    /// repository that a staged change sets an issue type in, so that unknown
+    /// issue types are reported rather than failing when saved.  Repositories
+    /// whose issue types can't be loaded are left unchecked.
+    pub async fn validate_with_load(
+        &self,
+        client: &impl Client,
+        fields: &Fields,
+        work_items: &WorkItems,
+        issue_types: &mut IssueTypeRegistry,
+    ) -> Vec<ChangeDiagnostic> {
+        let repositories: HashSet<(String, String)> = self
+            .into_iter()
+            .filter(|change| matches!(change.data, ChangeData::IssueType(Some(_))))
+            .filter_map(|change| work_items.get(&change.work_item_id)?.get_repository_info())
+            .collect();
+
+        for (owner, name) in repositories {
+            if let Err(error) = issue_types.get_or_load(client, &owner, &name).await {
+                warn!("failed to load issue types for {owner}/{name}: {error}");
+            }
+        }
+
+        self.validate(fields, work_items, issue_types)
+    }
+
+    /// Checks each staged change against the current project state and returns
+    /// a diagnostic for every change that cannot be saved as-is.  Changes
+    /// without diagnostics are valid.
+    ///
+    /// Issue types are only checked for repositories that have already been
+    /// loaded into `issue_types`.
+    pub fn validate(
+        &self,
+        fields: &Fields,
+        work_items: &WorkItems,
+        issue_types: &IssueTypeRegistry,
+    ) -> Vec<ChangeDiagnostic> {
+        let added_to_project: HashSet<&WorkItemId> = self
+            .into_iter()
+            .filter(|change| matches!(change.data, ChangeData::AddToProject))
+            .map(|change| &change.work_item_id)
+            .collect();
+
+        // Staged parents take precedence over the current ones, so cycles are
+        // detected across the whole batch rather than one change at a time.
+        let staged_parents: HashMap<&WorkItemId, &WorkItemId> = self
+            .into_iter()
+            .filter_map(|change| match &change.data {
+                ChangeData::SetParent(parent) => Some((&change.work_item_id, parent)),
+                _ => None,
+            })
+            .collect();
+
+        let mut diagnostics: Vec<ChangeDiagnostic> = self
+            .into_iter()
+            .filter_map(|change| {
+                change
+                    .validate(
+                        fields,
+                        work_items,
+                        issue_types,
+                        &added_to_project,
+                        &staged_parents,
+                    )
+                    .map(|problem| ChangeDiagnostic {
+                        change: change.clone(),
+                        problem,
+                    })
+            })
+            .collect();
+
+        diagnostics.sort_by(|a, b| {
+            (&a.change.work_item_id.0, a.change.field_name())
+                .cmp(&(&b.change.work_item_id.0, b.change.field_name()))
+        });
+        diagnostics
+    }
+}
+
+impl Change {
+    fn validate(
+        &self,
+        fields: &Fields,
+        work_items: &WorkItems,
+        issue_types: &IssueTypeRegistry,
+        added_to_project: &HashSet<&WorkItemId>,
+        staged_parents: &HashMap<&WorkItemId, &WorkItemId>,
+    ) -> Option<ChangeProblem> {
+        if let ChangeData::AddToProject = self.data {
+            return None;
+        }
+
+        let work_item = work_items.get(&self.work_item_id);
+        if work_item.is_none() && !added_to_project.contains(&self.work_item_id) {
+            return Some(ChangeProblem::WorkItemNotFound);
+        }
+
+        let is_issue = |item: Option<&WorkItem>| {
+            !matches!(
+                item.map(|item| &item.data),
+                Some(WorkItemData::DraftIssue | WorkItemData::PullRequest(_))
+            )
+        };
+
+        match &self.data {
+            ChangeData::IssueType(value) => {
+                if !is_issue(work_item) {
+                    return Some(ChangeProblem::NotAnIssue);
+                }
+                let types = work_item.and_then(|w| issue_types.get_for_work_item(w));
+                match (value, types) {
+                    (Some(name), Some(types)) if !types.name_to_id.contains_key(name) => {
+                        Some(ChangeProblem::UnknownIssueType { name: name.clone() })
+                    }
+                    _ => None,
+                }
+            }
+            ChangeData::Status(value) => check_option(&fields.status, value),
+            ChangeData::Blocked(value) => check_option(&fields.blocked, value),
+            ChangeData::Epic(value) => check_option(&fields.epic, value),
+            ChangeData::Iteration(value) => check_option(&fields.iteration, value),
+            ChangeData::Kind(value) => check_option(&fields.kind, value),
+            ChangeData::Workstream(value) => check_option(&fields.workstream, value),
+            ChangeData::Estimate(value) => check_option(&fields.estimate, value),
+            ChangeData::Priority(value) => check_option(&fields.priority, value),
+            ChangeData::SetParent(parent) => {
+                if !is_issue(work_item) {
+                    return Some(ChangeProblem::NotAnIssue);
+                }
+                if !is_issue(work_items.get(parent)) {
+                    return Some(ChangeProblem::ParentNotAnIssue);
+                }
+                if creates_cycle(&self.work_item_id, parent, work_items, staged_parents) {
+                    return Some(ChangeProblem::ParentCycle);
+                }
+                None
+            }
+            ChangeData::RemoveParent(_) => {
+                if !is_issue(work_item) {
+                    return Some(ChangeProblem::NotAnIssue);
+                }
+                None
+            }
+            ChangeData::AddToProject | ChangeData::RemoveFromProject => None,
+        }
+    }
+}
+
+fn check_option<T>(field: &Field<T>, value: &Option<FieldOptionId>) -> Option<ChangeProblem> {
+    match value {
+        Some(id) if field.option_name(Some(id)).is_none() => {
+            Some(ChangeProblem::UnknownFieldOption {
+                field: field.name.clone(),
+                option: id.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Walks up from `parent` to see whether `child` would become its own
+/// ancestor.
+fn creates_cycle(
+    child: &WorkItemId,
+    parent: &WorkItemId,
+    work_items: &WorkItems,
+    staged_parents: &HashMap<&WorkItemId, &WorkItemId>,
+) -> bool {
+    let mut visited = HashSet::new();
+    let mut current = Some(parent);
+
+    while let Some(id) = current {
+        if id == child {
+            return true;
+        }
+        if !visited.insert(id) {
+            // An existing cycle that doesn't involve `child`.
+            return false;
+        }
+        current = staged_parents
+            .get(id)
+            .copied()
+            .or_else(|| work_items.get(id).and_then(|w| w.get_parent()));
+    }
+
+    false
+}
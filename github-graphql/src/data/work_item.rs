@@ -11,8 +11,18 @@ pub struct WorkItem {
     pub id: WorkItemId,
     pub title: String,
     pub updated_at: String,
+    /// Items cached before this was fetched don't have it.
+    #[serde(default)]
+    pub created_at: String,
     pub resource_path: Option<String>,
     pub repo_name_with_owner: Option<String>,
+    /// Fetched separately from the rest of the item, when it is first
+    /// searched for.
+    #[serde(default)]
+    pub body: DelayLoad<String>,
+    /// The text of the most recent comments, oldest first.
+    #[serde(default)]
+    pub comments: DelayLoad<Vec<String>>,
     #[difference(recurse)]
     pub data: WorkItemData,
     #[difference(recurse)]
@@ -71,6 +81,15 @@ impl WorkItem {
         }
     }
 
+    /// The issue or pull request number, taken from the end of the resource
+    /// path (e.g. `/owner/repo/issues/123`).
+    pub fn number(&self) -> Option<u64> {
+        self.resource_path
+            .as_deref()
+            .and_then(|path| path.rsplit('/').next())
+            .and_then(|number| number.parse().ok())
+    }
+
     pub fn get_repository_info(&self) -> Option<(String, String)> {
         if let Some(repo) = &self.repo_name_with_owner {
             let mut parts = repo.splitn(2, '/');
@@ -150,6 +169,9 @@ pub enum IssueState {
 pub struct PullRequest {
     pub state: DelayLoad<PullRequestState>,
     pub assignees: Vec<String>,
+    /// Issues that this pull request closes when merged.
+    #[serde(default)]
+    pub linked_issues: DelayLoad<Vec<WorkItemId>>,
 }
 
 #[derive(Default, PartialEq, Debug, Eq, Hash, Clone, Serialize, Deserialize, TS, Difference)]
@@ -1,39 +1,12 @@
 use crate::data::{
-    FieldOptionId, IssueStructDiffEnumRef, ProjectItemStructDiffEnumRef,
-    PullRequestStructDiffEnumRef, WorkItemDataStructDiffEnumRef,
+    IssueStructDiffEnumRef, ProjectItemStructDiffEnumRef, PullRequestStructDiffEnumRef,
+    WorkItemDataStructDiffEnumRef,
 };
 
-use super::{
-    Change, ChangeData, Changes, Field, Fields, SingleSelect, WorkItem, WorkItemData, WorkItemId,
-};
+use super::{Change, ChangeData, Changes, WorkItem, WorkItemData, WorkItemId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use structdiff::StructDiff;
-use ts_rs::TS;
-
-/// A single Epic conflict found during sanitize: the item already has an Epic
-/// set, but the hierarchy says it should inherit a different one.
-#[derive(Debug, Clone, Serialize, TS)]
-#[serde(rename_all = "camelCase")]
-#[ts(export)]
-pub struct SanitizeConflict {
-    pub work_item_id: WorkItemId,
-    /// The Epic currently set on the item.
-    pub current_epic: FieldOptionId,
-    /// The Epic the hierarchy says this item should have.
-    pub proposed_epic: FieldOptionId,
-}
-
-/// The result of a `sanitize()` call.
-///
-/// `changes` are the staged field updates (items with no conflicts).
-/// `epic_conflicts` are items that already have an Epic and were therefore
-/// skipped — the caller can present these for user review.
-#[derive(Debug, Default)]
-pub struct SanitizeReport {
-    pub changes: Changes,
-    pub epic_conflicts: Vec<SanitizeConflict>,
-}
 
 #[derive(Default, Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
 pub struct WorkItems {
@@ -79,6 +52,13 @@ impl WorkItems {
         }
     }
 
+    /// Removes the item, for example because it was removed from the project.
+    /// References to it from other items are left in place.
+    pub fn remove(&mut self, id: &WorkItemId) -> Option<WorkItem> {
+        self.ordered_items.retain(|i| i != id);
+        self.work_items.remove(id)
+    }
+
     pub fn iter(&self) -> std::slice::Iter<'_, WorkItemId> {
         self.ordered_items.iter()
     }
@@ -143,142 +123,6 @@ impl WorkItems {
 
         changes
     }
-
-    pub fn sanitize(&self, fields: &Fields) -> SanitizeReport {
-        let mut report = SanitizeReport::default();
-
-        let closed_option_id = fields.status.option_id("Closed".into()).cloned();
-        let bug_kind_id = fields.kind.option_id("Bug".into()).cloned();
-        let planning_option_id = fields.status.option_id("Planning".into()).cloned();
-
-        for item in self.work_items.values() {
-            // Closed items should have status set to Closed
-            if *item.is_closed().expect_loaded() && item.project_item.status != closed_option_id {
-                report.changes.add(Change {
-                    work_item_id: item.id.clone(),
-                    data: ChangeData::Status(closed_option_id.clone()),
-                });
-            }
-
-            // Items that are Bugs shuold set their type to bug
-            if let WorkItemData::Issue(issue) = &item.data {
-                let kind = item.project_item.kind.expect_loaded();
-
-                if *kind == bug_kind_id
-                    && issue.issue_type.expect_loaded().as_deref() != Some("Bug")
-                {
-                    report.changes.add(Change {
-                        work_item_id: item.id.clone(),
-                        data: ChangeData::IssueType(Some("Bug".to_owned())),
-                    });
-                }
-            }
-
-            // Assigned issues with no status should be set to Planning
-            if let WorkItemData::Issue(issue) = &item.data {
-                if !issue.assignees.is_empty()
-                    && item.project_item.status.is_none()
-                    && !*item.is_closed().expect_loaded()
-                {
-                    report.changes.add(Change {
-                        work_item_id: item.id.clone(),
-                        data: ChangeData::Status(planning_option_id.clone()),
-                    });
-                }
-            }
-        }
-
-        for root_item_id in self.get_roots() {
-            sanitize_issue_hierarchy(self, &mut report, &root_item_id, &None, &None, &fields.epic);
-        }
-
-        fn sanitize_issue_hierarchy(
-            items: &WorkItems,
-            report: &mut SanitizeReport,
-            id: &WorkItemId,
-            parent_epic: &Option<FieldOptionId>,
-            parent_workstream: &Option<FieldOptionId>,
-            epic_field: &Field<SingleSelect>,
-        ) {
-            if let Some(item) = items.get(id) {
-                if let Some(parent_id) = item.get_parent() {
-                    if items.get(parent_id).is_none() {
-                        report.changes.add(Change {
-                            work_item_id: parent_id.clone(),
-                            data: ChangeData::AddToProject,
-                        });
-                    }
-                }
-
-                let this_item_epic = &item.project_item.epic;
-
-                if let Some(parent_epic_id) = parent_epic {
-                    if let Some(current) = this_item_epic {
-                        let current_idx = epic_field.option_index(Some(current));
-                        let parent_idx = epic_field.option_index(Some(parent_epic_id));
-                        if current_idx > parent_idx {
-                            // Child epic is later than parent epic — record conflict
-                            // so the user can review and selectively override it.
-                            report.epic_conflicts.push(SanitizeConflict {
-                                work_item_id: id.clone(),
-                                current_epic: current.clone(),
-                                proposed_epic: parent_epic_id.clone(),
-                            });
-                        }
-                    } else {
-                        report.changes.add(Change {
-                            work_item_id: id.clone(),
-                            data: ChangeData::Epic(Some(parent_epic_id.clone())),
-                        });
-                    }
-                }
-
-                let effective_epic = if this_item_epic.is_some() {
-                    this_item_epic
-                } else {
-                    parent_epic
-                };
-
-                // Workstream rule (minimal):
-                // - Each item must match its parent's workstream.
-                // We only enforce "match parent" when the parent has a value.
-                if let Some(parent_ws) = parent_workstream {
-                    let current_ws = item.project_item.workstream.expect_loaded();
-                    if current_ws.as_ref() != Some(parent_ws) {
-                        report.changes.add(Change {
-                            work_item_id: id.clone(),
-                            data: ChangeData::Workstream(Some(parent_ws.clone())),
-                        });
-                    }
-                }
-
-                if let WorkItemData::Issue(issue) = &item.data {
-                    // Determine what workstream should be used for children:
-                    // Only propagate an explicit, non-blank workstream.
-                    let this_workstream = item.project_item.workstream.expect_loaded().clone();
-
-                    for child_id in &issue.sub_issues {
-                        sanitize_issue_hierarchy(
-                            items,
-                            report,
-                            child_id,
-                            effective_epic,
-                            &this_workstream,
-                            epic_field,
-                        );
-                    }
-                }
-            } else {
-                // This work item isn't in the project - add it
-                report.changes.add(Change {
-                    work_item_id: id.clone(),
-                    data: ChangeData::AddToProject,
-                });
-            }
-        }
-
-        report
-    }
 }
 
 fn get_work_item_update_type(old: &WorkItem, new: &WorkItem) -> UpdateType {
@@ -294,9 +138,15 @@ fn get_work_item_update_type(old: &WorkItem, new: &WorkItem) -> UpdateType {
 
             match diff {
                 id(_) => UpdateType::SimpleChange,
-                title(_) => UpdateType::SimpleChange,
-                updated_at(_) => UpdateType::SimpleChange,
+                // Items can be sorted by title, and Parent and Root groups
+                // are labelled with it.
+                title(_) => UpdateType::ChangesHierarchy,
+                // Only recipes that pivot or sort by these need a rebuild, and
+                // the app checks for that itself.
+                updated_at(_) | created_at(_) => UpdateType::SimpleChange,
                 resource_path(_) => UpdateType::SimpleChange,
+                body(_) => UpdateType::SimpleChange,
+                comments(_) => UpdateType::SimpleChange,
                 repo_name_with_owner(_) => UpdateType::ChangesHierarchy,
                 data(diffs) => get_work_item_data_update_type(&old.data, diffs),
                 project_item(items) => get_project_item_update_type(items),
@@ -377,6 +227,7 @@ fn get_pull_request_update_type(diffs: Vec<PullRequestStructDiffEnumRef<'_>>) ->
             match diff {
                 state(_) => UpdateType::ChangesHierarchy,
                 assignees(_) => UpdateType::ChangesHierarchy,
+                linked_issues(_) => UpdateType::SimpleChange,
             }
         })
         .max()
@@ -1,6 +1,7 @@
 pub mod client;
 pub mod data;
 pub mod pivot;
+pub mod query;
 
 pub type Result<T = ()> = core::result::Result<T, Error>;
 
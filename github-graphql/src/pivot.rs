@@ -1,7 +1,15 @@
 use anyhow::{bail, Result};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use ts_rs::TS;
 
+use crate::{
+    data::{
+        DelayLoad, Field, FieldOptionId, Fields, Issue, IssueState, PullRequest, PullRequestState,
+        WorkItem, WorkItemData,
+    },
+    query::{parse_query, Query},
+};
+
 #[derive(Serialize, Deserialize, TS, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
@@ -19,16 +27,393 @@ pub enum PivotField {
     IssueType,
     Type,
     State,
+    /// The week the item was last updated in, starting on Monday.
+    UpdatedWeek,
+    /// The month the item was last updated in.
+    UpdatedMonth,
+    /// The month the item was created in.
+    CreatedMonth,
+    /// How long ago the item was last updated: under a week, one to four
+    /// weeks, one to three months, or longer.
+    UpdatedAge,
+    /// The start date of the item's iteration.
+    IterationStart,
+    /// The item's parent.
+    Parent,
+    /// The item's top-most ancestor, or the item itself if it has no parent.
+    Root,
+    /// The value of the field on the item's nearest ancestor that has one.
+    Ancestor(Box<PivotField>),
+}
+
+impl PivotField {
+    /// Whether the field's value for an item depends on items further up the
+    /// tree than its parent.
+    pub fn reads_ancestors(&self) -> bool {
+        matches!(self, PivotField::Root | PivotField::Ancestor(_))
+    }
+
+    /// Whether the field's value for an item comes from when it was created
+    /// or last updated.
+    pub fn reads_dates(&self) -> bool {
+        match self {
+            PivotField::UpdatedWeek
+            | PivotField::UpdatedMonth
+            | PivotField::CreatedMonth
+            | PivotField::UpdatedAge => true,
+            PivotField::Ancestor(field) => field.reads_dates(),
+            _ => false,
+        }
+    }
+}
+
+/// One of the values an item has for one of its own fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemFieldValue<'a> {
+    /// Identifies the value: the option id for single select and iteration
+    /// fields, otherwise the same as `name`.
+    pub key: &'a str,
+    pub name: &'a str,
+    /// The value's position in the field's fixed set of values, for fields
+    /// that have one.
+    pub index: Option<usize>,
+    pub option_id: Option<&'a FieldOptionId>,
+}
+
+impl<'a> ItemFieldValue<'a> {
+    fn text(text: &'a str) -> Self {
+        ItemFieldValue {
+            key: text,
+            name: text,
+            index: None,
+            option_id: None,
+        }
+    }
+
+    fn indexed(name: &'a str, index: usize) -> Self {
+        ItemFieldValue {
+            index: Some(index),
+            ..Self::text(name)
+        }
+    }
+
+    fn option<T>(field: &'a Field<T>, id: Option<&'a FieldOptionId>) -> Vec<Self> {
+        id.map(|id| {
+            vec![ItemFieldValue {
+                key: &id.0,
+                name: field.option_name(Some(id)).unwrap_or(&id.0),
+                index: Some(field.option_index(Some(id))),
+                option_id: Some(id),
+            }]
+        })
+        .unwrap_or_default()
+    }
+}
+
+/// The values `item` has for `field`; empty if it has none or they haven't
+/// been loaded.  Only fields read directly from the item have values here -
+/// the ones derived from its dates or its ancestors are left to the caller.
+pub fn item_field_values<'a>(
+    item: &'a WorkItem,
+    fields: &'a Fields,
+    field: &PivotField,
+) -> Vec<ItemFieldValue<'a>> {
+    let p = &item.project_item;
+    match field {
+        PivotField::Status => ItemFieldValue::option(&fields.status, p.status.as_ref()),
+        PivotField::Blocked => {
+            ItemFieldValue::option(&fields.blocked, p.blocked.flatten().as_ref())
+        }
+        PivotField::Epic => ItemFieldValue::option(&fields.epic, p.epic.as_ref()),
+        PivotField::Iteration => {
+            ItemFieldValue::option(&fields.iteration, p.iteration.flatten().as_ref())
+        }
+        PivotField::Kind => ItemFieldValue::option(&fields.kind, p.kind.flatten().as_ref()),
+        PivotField::Workstream => {
+            ItemFieldValue::option(&fields.workstream, p.workstream.flatten().as_ref())
+        }
+        PivotField::Estimate => ItemFieldValue::option(&fields.estimate, p.estimate.as_ref()),
+        PivotField::Priority => ItemFieldValue::option(&fields.priority, p.priority.as_ref()),
+        PivotField::Assignee => item
+            .assignees()
+            .iter()
+            .map(String::as_str)
+            .map(ItemFieldValue::text)
+            .collect(),
+        PivotField::Repository => item
+            .repo_name_with_owner
+            .as_deref()
+            .map(ItemFieldValue::text)
+            .into_iter()
+            .collect(),
+        PivotField::IssueType => match &item.data {
+            WorkItemData::Issue(Issue { issue_type, .. }) => issue_type
+                .flatten()
+                .as_deref()
+                .map(ItemFieldValue::text)
+                .into_iter()
+                .collect(),
+            WorkItemData::DraftIssue | WorkItemData::PullRequest(_) => Vec::new(),
+        },
+        PivotField::Type => vec![match item.data {
+            WorkItemData::Issue(_) => ItemFieldValue::indexed("issue", 0),
+            WorkItemData::PullRequest(_) => ItemFieldValue::indexed("pullRequest", 1),
+            WorkItemData::DraftIssue => ItemFieldValue::indexed("draftIssue", 2),
+        }],
+        PivotField::State => match &item.data {
+            WorkItemData::Issue(Issue {
+                state: DelayLoad::Loaded(state),
+                ..
+            }) => vec![match state {
+                IssueState::OPEN => ItemFieldValue::indexed("OPEN", 0),
+                IssueState::CLOSED => ItemFieldValue::indexed("CLOSED", 1),
+                IssueState::Other(value) => ItemFieldValue::indexed(value, 99),
+            }],
+            WorkItemData::PullRequest(PullRequest {
+                state: DelayLoad::Loaded(state),
+                ..
+            }) => vec![match state {
+                PullRequestState::OPEN => ItemFieldValue::indexed("OPEN", 0),
+                PullRequestState::CLOSED => ItemFieldValue::indexed("CLOSED", 1),
+                PullRequestState::MERGED => ItemFieldValue::indexed("MERGED", 2),
+                PullRequestState::Other(value) => ItemFieldValue::indexed(value, 99),
+            }],
+            WorkItemData::Issue(_) | WorkItemData::PullRequest(_) | WorkItemData::DraftIssue => {
+                Vec::new()
+            }
+        },
+        PivotField::UpdatedWeek
+        | PivotField::UpdatedMonth
+        | PivotField::CreatedMonth
+        | PivotField::UpdatedAge
+        | PivotField::IterationStart
+        | PivotField::Parent
+        | PivotField::Root
+        | PivotField::Ancestor(_) => Vec::new(),
+    }
 }
 
 #[derive(Serialize, Deserialize, TS, Debug, Clone, PartialEq, Eq)]
-#[serde(rename_all = "camelCase", tag = "kind", content = "field")]
-#[ts(export)]
+#[serde(from = "AxisRepr", into = "AxisRepr")]
+#[ts(export, as = "AxisRepr")]
 pub enum Axis {
-    Pivot(PivotField),
-    Group(PivotField),
-    Hierarchy,
-    Sort(PivotField),
+    Pivot(Bucketing),
+    Group(Bucketing),
+    Hierarchy(Option<HierarchyOptions>),
+    Sort(Vec<SortKey>),
+    /// Keep only the items in scope that match the query.
+    Filter(Query),
+}
+
+/// An `Axis` as stored: its kind, and its argument as `field`.  A `Hierarchy`
+/// without options leaves `field` out, as it did before there were any.
+#[derive(Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+enum AxisRepr {
+    Pivot {
+        field: Bucketing,
+    },
+    Group {
+        field: Bucketing,
+    },
+    Hierarchy {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        field: Option<HierarchyOptions>,
+    },
+    Sort {
+        #[serde(deserialize_with = "deserialize_sort_keys")]
+        field: Vec<SortKey>,
+    },
+    Filter {
+        #[ts(type = "string")]
+        field: Query,
+    },
+}
+
+impl From<AxisRepr> for Axis {
+    fn from(repr: AxisRepr) -> Self {
+        match repr {
+            AxisRepr::Pivot { field } => Axis::Pivot(field),
+            AxisRepr::Group { field } => Axis::Group(field),
+            AxisRepr::Hierarchy { field } => Axis::Hierarchy(field),
+            AxisRepr::Sort { field } => Axis::Sort(field),
+            AxisRepr::Filter { field } => Axis::Filter(field),
+        }
+    }
+}
+
+impl From<Axis> for AxisRepr {
+    fn from(axis: Axis) -> Self {
+        match axis {
+            Axis::Pivot(field) => AxisRepr::Pivot { field },
+            Axis::Group(field) => AxisRepr::Group { field },
+            Axis::Hierarchy(field) => AxisRepr::Hierarchy { field },
+            Axis::Sort(field) => AxisRepr::Sort { field },
+            Axis::Filter(field) => AxisRepr::Filter { field },
+        }
+    }
+}
+
+/// The field a `Pivot` or `Group` axis buckets items by, and how its buckets
+/// are shown.
+#[derive(Serialize, Deserialize, TS, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "BucketingRepr", into = "BucketingRepr")]
+#[ts(export, as = "BucketingRepr")]
+pub struct Bucketing {
+    pub field: PivotField,
+    /// Also show a bucket for each of the field's options that no item has,
+    /// in the order the options are declared.
+    pub show_empty: bool,
+    /// Merge a bucket whose only child is another bucket into one row.
+    pub collapse: bool,
+}
+
+impl From<PivotField> for Bucketing {
+    fn from(field: PivotField) -> Self {
+        Bucketing {
+            field,
+            show_empty: false,
+            collapse: false,
+        }
+    }
+}
+
+/// A `Bucketing` without options is stored as just its field, which is how
+/// `Pivot` and `Group` axes were stored before they had options.
+#[derive(Serialize, Deserialize, TS)]
+#[serde(untagged)]
+enum BucketingRepr {
+    Field(PivotField),
+    #[serde(rename_all = "camelCase")]
+    Options {
+        field: PivotField,
+        #[serde(default)]
+        show_empty: bool,
+        #[serde(default)]
+        collapse: bool,
+    },
+}
+
+impl From<BucketingRepr> for Bucketing {
+    fn from(repr: BucketingRepr) -> Self {
+        match repr {
+            BucketingRepr::Field(field) => field.into(),
+            BucketingRepr::Options {
+                field,
+                show_empty,
+                collapse,
+            } => Bucketing {
+                field,
+                show_empty,
+                collapse,
+            },
+        }
+    }
+}
+
+impl From<Bucketing> for BucketingRepr {
+    fn from(bucketing: Bucketing) -> Self {
+        if !bucketing.show_empty && !bucketing.collapse {
+            return BucketingRepr::Field(bucketing.field);
+        }
+        BucketingRepr::Options {
+            field: bucketing.field,
+            show_empty: bucketing.show_empty,
+            collapse: bucketing.collapse,
+        }
+    }
+}
+
+/// Options of a `Hierarchy` axis.
+#[derive(Default, Serialize, Deserialize, TS, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct HierarchyOptions {
+    /// How many levels of the tree to show; items below them are left out.
+    #[serde(default)]
+    pub depth: Option<u32>,
+    /// Whether to show the ancestors of items in scope that aren't in scope
+    /// themselves, overriding `PivotConfig::show_ghost_ancestors`.
+    #[serde(default)]
+    pub ghosts: Option<bool>,
+}
+
+/// One key of a `Sort` axis.  Keys are applied in order, each breaking ties
+/// left by the ones before it.
+#[derive(Serialize, Deserialize, TS, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SortKey {
+    pub field: SortField,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+impl SortKey {
+    pub fn ascending(field: impl Into<SortField>) -> Self {
+        SortKey {
+            field: field.into(),
+            descending: false,
+        }
+    }
+
+    pub fn descending(field: impl Into<SortField>) -> Self {
+        SortKey {
+            field: field.into(),
+            descending: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, TS, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+#[ts(export)]
+pub enum SortField {
+    Field(PivotField),
+    Attribute(ItemAttribute),
+}
+
+impl From<PivotField> for SortField {
+    fn from(field: PivotField) -> Self {
+        SortField::Field(field)
+    }
+}
+
+impl From<ItemAttribute> for SortField {
+    fn from(attribute: ItemAttribute) -> Self {
+        SortField::Attribute(attribute)
+    }
+}
+
+/// Properties of a work item that can be sorted on but aren't pivot fields.
+#[derive(Serialize, Deserialize, TS, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum ItemAttribute {
+    Title,
+    UpdatedAt,
+    /// The issue or pull request number.
+    Number,
+}
+
+/// Sort axes used to hold a single field; accept that form so that saved
+/// configurations keep loading.
+fn deserialize_sort_keys<'de, D>(deserializer: D) -> std::result::Result<Vec<SortKey>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SortKeys {
+        Keys(Vec<SortKey>),
+        Field(PivotField),
+    }
+
+    Ok(match SortKeys::deserialize(deserializer)? {
+        SortKeys::Keys(keys) => keys,
+        SortKeys::Field(field) => vec![SortKey::ascending(field)],
+    })
 }
 
 #[derive(Default, Serialize, Deserialize, TS, Debug, Clone, PartialEq, Eq)]
@@ -40,21 +425,81 @@ pub enum MultiValueStrategy {
     Explode,
 }
 
+/// A rollup computed over the work items under each group and parent node.
+#[derive(Serialize, Deserialize, TS, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum Aggregate {
+    /// Number of work items.
+    Count,
+    /// Number of open work items.
+    Open,
+    /// Number of closed (or merged) work items.
+    Closed,
+    /// Sum of the items' Estimate values, in days.
+    Estimate,
+    /// Percentage of work items whose Status is Done or Closed.
+    PercentDone,
+}
+
+/// A two dimensional pivot: one row per value of `rows` and one column per
+/// value of `columns`.
+#[derive(Serialize, Deserialize, TS, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CrossTabConfig {
+    pub rows: PivotField,
+    pub columns: PivotField,
+}
+
+/// What each cell of an exported cross-tab contains.
+#[derive(Default, Serialize, Deserialize, TS, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum CrossTabValue {
+    /// The number of work items in the cell.
+    #[default]
+    Count,
+    /// The titles of the work items in the cell.
+    Items,
+}
+
 #[derive(Serialize, Deserialize, TS, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct PivotConfig {
     pub recipe: Vec<Axis>,
     pub multi_value_strategy: MultiValueStrategy,
+    /// Whether `Hierarchy` axes show the ancestors of items in scope that
+    /// aren't in scope themselves, unless an axis says otherwise.
     pub show_ghost_ancestors: bool,
+    /// Rollups to compute for group and parent nodes.
+    #[serde(default)]
+    pub aggregates: Vec<Aggregate>,
+}
+
+impl PivotConfig {
+    /// Whether the recipe buckets or sorts items by when they were created or
+    /// last updated, so that a change to those dates can move items.
+    pub fn reads_dates(&self) -> bool {
+        self.recipe.iter().any(|axis| match axis {
+            Axis::Pivot(bucketing) | Axis::Group(bucketing) => bucketing.field.reads_dates(),
+            Axis::Sort(keys) => keys.iter().any(|key| match &key.field {
+                SortField::Field(field) => field.reads_dates(),
+                SortField::Attribute(attribute) => *attribute == ItemAttribute::UpdatedAt,
+            }),
+            Axis::Hierarchy(_) | Axis::Filter(_) => false,
+        })
+    }
 }
 
 impl Default for PivotConfig {
     fn default() -> Self {
         Self {
-            recipe: vec![Axis::Pivot(PivotField::Epic), Axis::Hierarchy],
+            recipe: vec![Axis::Pivot(PivotField::Epic.into()), Axis::Hierarchy(None)],
             multi_value_strategy: MultiValueStrategy::Combined,
             show_ghost_ancestors: true,
+            aggregates: Vec::new(),
         }
     }
 }
@@ -64,42 +509,56 @@ pub fn parse_recipe(text: &str) -> Result<Vec<Axis>> {
         return Ok(Vec::new());
     }
 
-    let normalised = normalize_recipe_separators(text);
-
-    let tokens = normalised
-        .split('|')
-        .map(str::trim)
+    let tokens = split_recipe(text);
+    let tokens = tokens
+        .iter()
+        .map(|token| token.trim())
         .filter(|token| !token.is_empty());
 
     let mut axes = Vec::new();
     for token in tokens {
+        if let Some(expression) = filter_expression(token) {
+            match parse_query(expression) {
+                Ok(query) => axes.push(Axis::Filter(query)),
+                Err(error) => bail!("Filter({expression}): {error}"),
+            }
+            continue;
+        }
+
         let (kind, arg) = parse_axis_token(token)?;
         let kind = kind.to_ascii_lowercase();
 
         if kind == "hierarchy" {
-            if arg.is_some() {
-                bail!("Hierarchy takes no argument");
-            }
-            axes.push(Axis::Hierarchy);
+            axes.push(Axis::Hierarchy(
+                arg.map(parse_hierarchy_options).transpose()?,
+            ));
             continue;
         }
 
-        if !matches!(kind.as_str(), "pivot" | "group" | "sort") {
-            bail!("Unknown axis: {token} (use Pivot, Group, Sort, or Hierarchy)");
+        if kind == "filter" {
+            bail!("Filter requires an expression, e.g. Filter(Status != Done)");
+        }
+
+        if kind == "sort" {
+            let Some(arg) = arg else {
+                bail!("sort requires a field argument, e.g. Sort(Priority desc, UpdatedAt)");
+            };
+            axes.push(Axis::Sort(parse_sort_keys(arg)?));
+            continue;
+        }
+
+        if !matches!(kind.as_str(), "pivot" | "group") {
+            bail!("Unknown axis: {token} (use Pivot, Group, Sort, Filter, or Hierarchy)");
         }
 
         let Some(arg) = arg else {
             bail!("{kind} requires a field argument, e.g. {kind}(Epic)");
         };
 
-        let Some(field) = resolve_field(arg) else {
-            bail!("Unknown field: {arg}");
-        };
-
+        let bucketing = parse_bucketing(&kind, arg)?;
         axes.push(match kind.as_str() {
-            "pivot" => Axis::Pivot(field),
-            "group" => Axis::Group(field),
-            "sort" => Axis::Sort(field),
+            "pivot" => Axis::Pivot(bucketing),
+            "group" => Axis::Group(bucketing),
             _ => unreachable!(),
         });
     }
@@ -111,19 +570,165 @@ pub fn recipe_to_string(recipe: &[Axis]) -> String {
     recipe
         .iter()
         .map(|axis| match axis {
-            Axis::Hierarchy => "Hierarchy".to_string(),
-            Axis::Pivot(field) => format!("Pivot({})", field_label(field)),
-            Axis::Group(field) => format!("Group({})", field_label(field)),
-            Axis::Sort(field) => format!("Sort({})", field_label(field)),
+            Axis::Hierarchy(None) => "Hierarchy".to_string(),
+            Axis::Hierarchy(Some(options)) => hierarchy_to_string(options),
+            Axis::Pivot(bucketing) => format!("Pivot({})", bucketing_to_string(bucketing)),
+            Axis::Group(bucketing) => format!("Group({})", bucketing_to_string(bucketing)),
+            Axis::Sort(keys) => format!("Sort({})", sort_keys_to_string(keys)),
+            Axis::Filter(query) => format!("Filter({query})"),
         })
         .collect::<Vec<_>>()
         .join(" → ")
 }
 
+fn bucketing_to_string(bucketing: &Bucketing) -> String {
+    let mut parts = vec![field_label(&bucketing.field)];
+    if bucketing.show_empty {
+        parts.push("empty".to_string());
+    }
+    if bucketing.collapse {
+        parts.push("collapse".to_string());
+    }
+    parts.join(", ")
+}
+
+fn hierarchy_to_string(options: &HierarchyOptions) -> String {
+    let mut parts = Vec::new();
+    if let Some(depth) = options.depth {
+        parts.push(format!("depth={depth}"));
+    }
+    match options.ghosts {
+        Some(true) => parts.push("ghosts".to_string()),
+        Some(false) => parts.push("noghosts".to_string()),
+        None => {}
+    }
+    if parts.is_empty() {
+        "Hierarchy".to_string()
+    } else {
+        format!("Hierarchy({})", parts.join(", "))
+    }
+}
+
+fn sort_keys_to_string(keys: &[SortKey]) -> String {
+    keys.iter()
+        .map(|key| {
+            let label = match &key.field {
+                SortField::Field(field) => field_label(field),
+                SortField::Attribute(attribute) => attribute_label(*attribute).to_string(),
+            };
+            if key.descending {
+                format!("{label} desc")
+            } else {
+                label
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parses the comma separated keys of a `Sort(...)` axis, each optionally
+/// followed by `asc` or `desc`.
+fn parse_sort_keys(arg: &str) -> Result<Vec<SortKey>> {
+    let mut keys = Vec::new();
+    for key in arg.split(',').map(str::trim) {
+        if key.is_empty() {
+            bail!("Sort keys must not be empty: Sort({arg})");
+        }
+
+        let (name, descending) = match key.rsplit_once(char::is_whitespace) {
+            Some((name, direction)) => match direction.to_ascii_lowercase().as_str() {
+                "asc" | "ascending" => (name.trim(), false),
+                "desc" | "descending" => (name.trim(), true),
+                _ => (key, false),
+            },
+            None => (key, false),
+        };
+
+        let Some(field) = resolve_sort_field(name) else {
+            bail!("Unknown sort key: {name}");
+        };
+        keys.push(SortKey { field, descending });
+    }
+    Ok(keys)
+}
+
+/// Parses the argument of a `Pivot(...)` or `Group(...)` axis: a field,
+/// optionally followed by `empty` and `collapse`.
+fn parse_bucketing(kind: &str, arg: &str) -> Result<Bucketing> {
+    let mut parts = arg.split(',').map(str::trim);
+    let name = parts.next().unwrap_or_default();
+    let Some(field) = resolve_pivot_field(name) else {
+        bail!("Unknown field: {name}");
+    };
+
+    let mut bucketing = Bucketing::from(field);
+    for option in parts {
+        match option.to_ascii_lowercase().as_str() {
+            "empty" => bucketing.show_empty = true,
+            "collapse" => bucketing.collapse = true,
+            _ => bail!("Unknown {kind} option: {option} (use empty or collapse)"),
+        }
+    }
+    Ok(bucketing)
+}
+
+/// Parses the comma separated options of a `Hierarchy(...)` axis:
+/// `depth=N`, and `ghosts` or `noghosts`.
+fn parse_hierarchy_options(arg: &str) -> Result<HierarchyOptions> {
+    let mut options = HierarchyOptions::default();
+    for option in arg.split(',').map(str::trim) {
+        let lowercase = option.to_ascii_lowercase();
+        if let Some((name, value)) = lowercase.split_once('=') {
+            if name.trim() != "depth" {
+                bail!("Unknown Hierarchy option: {option} (use depth=N, ghosts or noghosts)");
+            }
+            match value.trim().parse::<u32>() {
+                Ok(depth) if depth > 0 => options.depth = Some(depth),
+                _ => bail!("Hierarchy depth must be a positive number: {option}"),
+            }
+            continue;
+        }
+
+        match lowercase.as_str() {
+            "ghosts" => options.ghosts = Some(true),
+            "noghosts" => options.ghosts = Some(false),
+            _ => bail!("Unknown Hierarchy option: {option} (use depth=N, ghosts or noghosts)"),
+        }
+    }
+    Ok(options)
+}
+
+fn resolve_sort_field(name: &str) -> Option<SortField> {
+    if let Some(field) = resolve_pivot_field(name) {
+        return Some(SortField::Field(field));
+    }
+
+    let key = name
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<String>();
+
+    match key.as_str() {
+        "title" => Some(ItemAttribute::Title.into()),
+        "updatedat" | "updated" => Some(ItemAttribute::UpdatedAt.into()),
+        "number" | "issuenumber" => Some(ItemAttribute::Number.into()),
+        _ => None,
+    }
+}
+
+fn attribute_label(attribute: ItemAttribute) -> &'static str {
+    match attribute {
+        ItemAttribute::Title => "Title",
+        ItemAttribute::UpdatedAt => "UpdatedAt",
+        ItemAttribute::Number => "Number",
+    }
+}
+
 fn parse_axis_token(token: &str) -> Result<(&str, Option<&str>)> {
     if !token
         .chars()
-        .all(|c| c.is_ascii_alphabetic() || matches!(c, '_' | ' ' | '(' | ')'))
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | ' ' | ',' | '=' | '(' | ')'))
     {
         bail!("Could not parse axis: {token:?}");
     }
@@ -138,7 +743,12 @@ fn parse_axis_token(token: &str) -> Result<(&str, Option<&str>)> {
         }
         let kind = token[..open_paren].trim();
         let arg = token[(open_paren + 1)..close_paren].trim();
-        if kind.is_empty() || arg.is_empty() || arg.contains('(') || arg.contains(')') {
+        // Empty arguments are reported by the axis that parses them.
+        let plain_arguments = arg
+            .split(',')
+            .filter(|part| !part.trim().is_empty())
+            .all(is_field_argument);
+        if kind.is_empty() || arg.is_empty() || !plain_arguments {
             bail!("Could not parse axis: {token:?}");
         }
         if !kind.chars().all(|c| c.is_ascii_alphabetic()) {
@@ -161,7 +771,52 @@ fn parse_axis_token(token: &str) -> Result<(&str, Option<&str>)> {
     Ok((kind, None))
 }
 
-fn resolve_field(name: &str) -> Option<PivotField> {
+/// Whether `arg` is a plain name, or a name with a single plain argument such
+/// as `Ancestor(Epic)`.  Axes may take several of these, separated by commas.
+fn is_field_argument(arg: &str) -> bool {
+    let (name, rest) = match arg.split_once('(') {
+        Some((name, rest)) => match rest.strip_suffix(')') {
+            Some(inner) => (name, inner),
+            None => return false,
+        },
+        None => (arg, ""),
+    };
+    !name.trim().is_empty() && !name.contains(')') && !rest.contains(['(', ')'])
+}
+
+/// Resolves the fields that can be pivoted on: the item's own fields, which
+/// `resolve_field` handles, those derived from its dates, and those that
+/// come from its ancestors.
+fn resolve_pivot_field(name: &str) -> Option<PivotField> {
+    if let Some((kind, inner)) = name.split_once('(') {
+        let inner = inner.strip_suffix(')')?;
+        if !kind.trim().eq_ignore_ascii_case("ancestor") {
+            return None;
+        }
+        return resolve_field(inner).map(|field| PivotField::Ancestor(Box::new(field)));
+    }
+
+    let key = name
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<String>();
+
+    match key.as_str() {
+        "updatedweek" => Some(PivotField::UpdatedWeek),
+        "updatedmonth" => Some(PivotField::UpdatedMonth),
+        "createdmonth" => Some(PivotField::CreatedMonth),
+        "updatedage" | "age" => Some(PivotField::UpdatedAge),
+        "iterationstart" => Some(PivotField::IterationStart),
+        "parent" => Some(PivotField::Parent),
+        "root" => Some(PivotField::Root),
+        _ => resolve_field(name),
+    }
+}
+
+/// Resolves the names of an item's own fields.  Queries use this too, so it
+/// must only resolve fields that `item_field_values` has values for.
+pub(crate) fn resolve_field(name: &str) -> Option<PivotField> {
     let key = name
         .chars()
         .filter(|c| !c.is_whitespace())
@@ -186,26 +841,50 @@ fn resolve_field(name: &str) -> Option<PivotField> {
     }
 }
 
-fn normalize_recipe_separators(text: &str) -> String {
-    let mut out = String::with_capacity(text.len());
+/// Splits a recipe into axis tokens.  Separators inside parentheses or quotes,
+/// such as those in a `Filter` expression, don't split.
+fn split_recipe(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut in_quotes = false;
     let mut chars = text.chars().peekable();
 
     while let Some(ch) = chars.next() {
+        let top_level = depth == 0 && !in_quotes;
         match ch {
-            '-' if chars.peek() == Some(&'>') => {
+            '"' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => depth = depth.saturating_sub(1),
+            '-' if top_level && chars.peek() == Some(&'>') => {
                 let _ = chars.next();
-                out.push('|');
+                tokens.push(std::mem::take(&mut current));
+                continue;
+            }
+            '→' | '>' | ',' if top_level => {
+                tokens.push(std::mem::take(&mut current));
+                continue;
             }
-            '→' | '>' | ',' => out.push('|'),
-            _ => out.push(ch),
+            _ => {}
         }
+        current.push(ch);
     }
 
-    out
+    tokens.push(current);
+    tokens
 }
 
-fn field_label(field: &PivotField) -> &'static str {
-    match field {
+/// Returns the expression of a `Filter(...)` token.
+fn filter_expression(token: &str) -> Option<&str> {
+    let (kind, rest) = token.split_once('(')?;
+    if !kind.trim().eq_ignore_ascii_case("filter") {
+        return None;
+    }
+    rest.strip_suffix(')')
+}
+
+pub(crate) fn field_label(field: &PivotField) -> String {
+    let label = match field {
         PivotField::Status => "Status",
         PivotField::Blocked => "Blocked",
         PivotField::Epic => "Epic",
@@ -219,7 +898,16 @@ fn field_label(field: &PivotField) -> &'static str {
         PivotField::IssueType => "IssueType",
         PivotField::Type => "Type",
         PivotField::State => "State",
-    }
+        PivotField::UpdatedWeek => "UpdatedWeek",
+        PivotField::UpdatedMonth => "UpdatedMonth",
+        PivotField::CreatedMonth => "CreatedMonth",
+        PivotField::UpdatedAge => "UpdatedAge",
+        PivotField::IterationStart => "IterationStart",
+        PivotField::Parent => "Parent",
+        PivotField::Root => "Root",
+        PivotField::Ancestor(field) => return format!("Ancestor({})", field_label(field)),
+    };
+    label.to_string()
 }
 
 #[cfg(test)]
@@ -228,7 +916,7 @@ mod tests {
 
     use super::*;
 
-    const PRESETS: [&str; 15] = [
+    const PRESETS: [&str; 17] = [
         "Pivot(Epic) → Hierarchy",
         "Hierarchy → Pivot(Epic)",
         "Hierarchy → Group(Workstream)",
@@ -244,6 +932,8 @@ mod tests {
         "Sort(Priority) → Hierarchy",
         "Sort(Epic)",
         "Pivot(Type) → Group(Status)",
+        "Pivot(UpdatedAge) → Group(Status)",
+        "Pivot(Iteration, empty) → Hierarchy",
     ];
 
     #[test]
@@ -265,7 +955,7 @@ mod tests {
         let error = parse_recipe("Bucket(Epic)").unwrap_err();
         assert_eq!(
             error.to_string(),
-            "Unknown axis: Bucket(Epic) (use Pivot, Group, Sort, or Hierarchy)"
+            "Unknown axis: Bucket(Epic) (use Pivot, Group, Sort, Filter, or Hierarchy)"
         );
     }
 
@@ -278,7 +968,7 @@ mod tests {
     #[test]
     fn test_parse_recipe_dangling_separator_is_ignored() {
         let parsed = parse_recipe("Pivot(Epic) ->").unwrap();
-        assert_eq!(parsed, vec![Axis::Pivot(PivotField::Epic)]);
+        assert_eq!(parsed, vec![Axis::Pivot(PivotField::Epic.into())]);
     }
 
     #[test]
@@ -287,11 +977,308 @@ mod tests {
         assert_eq!(
             parsed,
             vec![
-                Axis::Pivot(PivotField::Repository),
-                Axis::Group(PivotField::Assignee),
-                Axis::Sort(PivotField::Assignee),
+                Axis::Pivot(PivotField::Repository.into()),
+                Axis::Group(PivotField::Assignee.into()),
+                Axis::Sort(vec![SortKey::ascending(PivotField::Assignee)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_recipe_date_fields() {
+        let parsed =
+            parse_recipe("Pivot(Updated Week) → Group(created_month) → Sort(Age, IterationStart)")
+                .unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Axis::Pivot(PivotField::UpdatedWeek.into()),
+                Axis::Group(PivotField::CreatedMonth.into()),
+                Axis::Sort(vec![
+                    SortKey::ascending(PivotField::UpdatedAge),
+                    SortKey::ascending(PivotField::IterationStart),
+                ]),
+            ]
+        );
+        assert_eq!(
+            recipe_to_string(&parsed),
+            "Pivot(UpdatedWeek) → Group(CreatedMonth) → Sort(UpdatedAge, IterationStart)"
+        );
+
+        // Filters only see the item's own fields.
+        assert_eq!(
+            parse_recipe("Filter(updatedage:old)")
+                .unwrap_err()
+                .to_string(),
+            "Filter(updatedage:old): Unknown field: updatedage"
+        );
+    }
+
+    #[test]
+    fn test_parse_recipe_ancestor_fields() {
+        let parsed =
+            parse_recipe("Pivot(Root) → Group(ancestor( Epic )) → Sort(Parent desc)").unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Axis::Pivot(PivotField::Root.into()),
+                Axis::Group(PivotField::Ancestor(Box::new(PivotField::Epic)).into()),
+                Axis::Sort(vec![SortKey::descending(PivotField::Parent)]),
+            ]
+        );
+        assert_eq!(
+            recipe_to_string(&parsed),
+            "Pivot(Root) → Group(Ancestor(Epic)) → Sort(Parent desc)"
+        );
+
+        assert_eq!(
+            serde_json::to_value(&parsed[1]).unwrap(),
+            serde_json::json!({ "kind": "group", "field": { "ancestor": "epic" } })
+        );
+
+        assert_eq!(
+            parse_recipe("Group(Ancestor(Root))")
+                .unwrap_err()
+                .to_string(),
+            "Unknown field: Ancestor(Root)"
+        );
+        assert_eq!(
+            parse_recipe("Group(Ancestor(Ancestor(Epic)))")
+                .unwrap_err()
+                .to_string(),
+            "Could not parse axis: \"Group(Ancestor(Ancestor(Epic)))\""
+        );
+    }
+
+    #[test]
+    fn test_parse_recipe_axis_options() {
+        let parsed = parse_recipe(
+            "Pivot(Iteration, empty) → Group(Epic, Collapse, empty) → Hierarchy(noghosts, depth = 2)",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Axis::Pivot(Bucketing {
+                    field: PivotField::Iteration,
+                    show_empty: true,
+                    collapse: false,
+                }),
+                Axis::Group(Bucketing {
+                    field: PivotField::Epic,
+                    show_empty: true,
+                    collapse: true,
+                }),
+                Axis::Hierarchy(Some(HierarchyOptions {
+                    depth: Some(2),
+                    ghosts: Some(false),
+                })),
+            ]
+        );
+        assert_eq!(
+            recipe_to_string(&parsed),
+            "Pivot(Iteration, empty) → Group(Epic, empty, collapse) → Hierarchy(depth=2, noghosts)"
+        );
+
+        assert_eq!(
+            parse_recipe("Group(Ancestor(Epic), collapse) → Hierarchy(ghosts)").unwrap(),
+            vec![
+                Axis::Group(Bucketing {
+                    field: PivotField::Ancestor(Box::new(PivotField::Epic)),
+                    show_empty: false,
+                    collapse: true,
+                }),
+                Axis::Hierarchy(Some(HierarchyOptions {
+                    depth: None,
+                    ghosts: Some(true),
+                })),
+            ]
+        );
+
+        assert_eq!(
+            parse_recipe("Pivot(Epic, bogus)").unwrap_err().to_string(),
+            "Unknown pivot option: bogus (use empty or collapse)"
+        );
+        assert_eq!(
+            parse_recipe("Hierarchy(depth=0)").unwrap_err().to_string(),
+            "Hierarchy depth must be a positive number: depth=0"
+        );
+        assert_eq!(
+            parse_recipe("Hierarchy(width=2)").unwrap_err().to_string(),
+            "Unknown Hierarchy option: width=2 (use depth=N, ghosts or noghosts)"
+        );
+    }
+
+    #[test]
+    fn test_axis_options_serialization() {
+        let recipe = parse_recipe("Pivot(Iteration, empty) → Hierarchy(depth=3)").unwrap();
+        let json = serde_json::to_value(&recipe).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {
+                    "kind": "pivot",
+                    "field": { "field": "iteration", "showEmpty": true, "collapse": false }
+                },
+                { "kind": "hierarchy", "field": { "depth": 3, "ghosts": null } },
+            ])
+        );
+        assert_eq!(serde_json::from_value::<Vec<Axis>>(json).unwrap(), recipe);
+
+        // Axes without options keep the form they had before there were any.
+        let plain = parse_recipe("Group(Epic) → Hierarchy").unwrap();
+        assert_eq!(
+            serde_json::to_value(&plain).unwrap(),
+            serde_json::json!([
+                { "kind": "group", "field": "epic" },
+                { "kind": "hierarchy" },
+            ])
+        );
+        let legacy: Vec<Axis> = serde_json::from_value(serde_json::json!([
+            { "kind": "group", "field": "epic" },
+            { "kind": "hierarchy" },
+            { "kind": "pivot", "field": { "field": "status", "collapse": true } },
+        ]))
+        .unwrap();
+        assert_eq!(
+            legacy,
+            vec![
+                Axis::Group(PivotField::Epic.into()),
+                Axis::Hierarchy(None),
+                Axis::Pivot(Bucketing {
+                    field: PivotField::Status,
+                    show_empty: false,
+                    collapse: true,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_recipe_multi_key_sort() {
+        let parsed =
+            parse_recipe("Sort(Priority desc, updated_at, Number DESC) → Hierarchy").unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Axis::Sort(vec![
+                    SortKey::descending(PivotField::Priority),
+                    SortKey::ascending(ItemAttribute::UpdatedAt),
+                    SortKey::descending(ItemAttribute::Number),
+                ]),
+                Axis::Hierarchy(None),
+            ]
+        );
+        assert_eq!(
+            recipe_to_string(&parsed),
+            "Sort(Priority desc, UpdatedAt, Number desc) → Hierarchy"
+        );
+
+        assert_eq!(
+            parse_recipe("Sort(Title asc, Repo)").unwrap(),
+            vec![Axis::Sort(vec![
+                SortKey::ascending(ItemAttribute::Title),
+                SortKey::ascending(PivotField::Repository),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_parse_recipe_sort_errors() {
+        assert_eq!(
+            parse_recipe("Sort(Priority, Bogus desc)")
+                .unwrap_err()
+                .to_string(),
+            "Unknown sort key: Bogus"
+        );
+        assert_eq!(
+            parse_recipe("Sort(Priority,)").unwrap_err().to_string(),
+            "Sort keys must not be empty: Sort(Priority,)"
+        );
+    }
+
+    #[test]
+    fn test_sort_axis_serialization() {
+        let axis = Axis::Sort(vec![
+            SortKey::descending(PivotField::Priority),
+            SortKey::ascending(ItemAttribute::UpdatedAt),
+        ]);
+        let json = serde_json::to_value(&axis).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "sort",
+                "field": [
+                    { "field": "priority", "descending": true },
+                    { "field": "updatedAt", "descending": false },
+                ]
+            })
+        );
+        assert_eq!(serde_json::from_value::<Axis>(json).unwrap(), axis);
+
+        let legacy: Axis =
+            serde_json::from_value(serde_json::json!({ "kind": "sort", "field": "epic" })).unwrap();
+        assert_eq!(
+            legacy,
+            Axis::Sort(vec![SortKey::ascending(PivotField::Epic)])
+        );
+    }
+
+    #[test]
+    fn test_parse_recipe_filter() {
+        let parsed = parse_recipe("Pivot(Epic) → Filter(Status != Done) → Hierarchy").unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Axis::Pivot(PivotField::Epic.into()),
+                Axis::Filter(parse_query("NOT status:Done").unwrap()),
+                Axis::Hierarchy(None),
             ]
         );
+        assert_eq!(
+            recipe_to_string(&parsed),
+            "Pivot(Epic) → Filter(NOT status:Done) → Hierarchy"
+        );
+
+        let nested = "Filter(epic:\"A, B\" AND (is:open OR has:parent)) → Hierarchy";
+        let parsed = parse_recipe(nested).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(recipe_to_string(&parsed), nested);
+    }
+
+    #[test]
+    fn test_parse_recipe_filter_errors() {
+        assert_eq!(
+            parse_recipe("Filter(bogus:1)").unwrap_err().to_string(),
+            "Filter(bogus:1): Unknown field: bogus"
+        );
+        assert_eq!(
+            parse_recipe("Filter").unwrap_err().to_string(),
+            "Filter requires an expression, e.g. Filter(Status != Done)"
+        );
+    }
+
+    #[test]
+    fn test_pivot_config_reads_dates() {
+        let config = |recipe| PivotConfig {
+            recipe,
+            ..PivotConfig::default()
+        };
+
+        assert!(!PivotConfig::default().reads_dates());
+        assert!(config(vec![Axis::Pivot(PivotField::UpdatedAge.into())]).reads_dates());
+        assert!(config(vec![Axis::Group(
+            PivotField::Ancestor(Box::new(PivotField::CreatedMonth)).into()
+        )])
+        .reads_dates());
+        assert!(config(vec![Axis::Sort(vec![SortKey::descending(
+            ItemAttribute::UpdatedAt
+        )])])
+        .reads_dates());
+        assert!(!config(vec![Axis::Sort(vec![SortKey::ascending(
+            ItemAttribute::Title
+        )])])
+        .reads_dates());
     }
 
     #[test]
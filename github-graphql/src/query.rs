@@ -0,0 +1,633 @@
+use std::fmt;
+
+use anyhow::{bail, Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::{DelayLoad, Fields, PullRequest, PullRequestState, WorkItem, WorkItemData},
+    pivot::{field_label, item_field_values, resolve_field, PivotField},
+};
+
+/// A filter expression, e.g. `epic:"DML Demo" AND (NOT status:Closed OR
+/// assignee:@me)`.
+///
+/// Terms are `field:value` (or `field=value` / `field!=value`), `is:open`,
+/// `is:closed`, `is:merged`, `is:issue`, `is:pr`, `is:draft`, `has:parent`,
+/// `has:sub-issues`, `has:<field>`, `title:text` and bare words, which match
+/// the title.  Terms are combined with `AND`, `OR` and `NOT` (or a leading
+/// `-`); adjacent terms are ANDed.  Quote values that contain spaces.
+///
+/// Queries serialize as their text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Query {
+    And(Vec<Query>),
+    Or(Vec<Query>),
+    Not(Box<Query>),
+    /// The field has the given value; field values are matched by name,
+    /// ignoring case.
+    Field {
+        field: PivotField,
+        value: String,
+    },
+    Is(IsFilter),
+    Has(HasFilter),
+    /// The title contains the text, ignoring case.
+    Title(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsFilter {
+    Open,
+    Closed,
+    Merged,
+    Issue,
+    PullRequest,
+    Draft,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HasFilter {
+    Parent,
+    SubIssues,
+    Field(PivotField),
+}
+
+/// What a query is evaluated against, besides the item itself.
+pub struct QueryContext<'a> {
+    pub fields: &'a Fields,
+    /// Login that `@me` refers to.  `@me` matches nothing when this isn't
+    /// known.
+    pub viewer: Option<&'a str>,
+}
+
+pub fn parse_query(text: &str) -> Result<Query> {
+    let tokens = tokenize(text)?;
+    if tokens.is_empty() {
+        bail!("Empty query");
+    }
+
+    let mut parser = Parser { tokens, pos: 0 };
+    let query = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        bail!("Unexpected {token}");
+    }
+    Ok(query)
+}
+
+impl Query {
+    pub fn matches(&self, item: &WorkItem, context: &QueryContext) -> bool {
+        match self {
+            Query::And(queries) => queries.iter().all(|q| q.matches(item, context)),
+            Query::Or(queries) => queries.iter().any(|q| q.matches(item, context)),
+            Query::Not(query) => !query.matches(item, context),
+            Query::Field { field, value } => field_matches(item, context, field, value),
+            Query::Is(filter) => is_matches(item, *filter),
+            Query::Has(HasFilter::Parent) => item.get_parent().is_some(),
+            Query::Has(HasFilter::SubIssues) => {
+                item.get_sub_issues().is_some_and(|ids| !ids.is_empty())
+            }
+            Query::Has(HasFilter::Field(field)) => {
+                !field_values(item, context.fields, field).is_empty()
+            }
+            Query::Title(text) => item.title.to_lowercase().contains(&text.to_lowercase()),
+        }
+    }
+
+    /// Whether the query refers to `@me`, and so needs the viewer's login.
+    pub fn uses_viewer(&self) -> bool {
+        match self {
+            Query::And(queries) | Query::Or(queries) => queries.iter().any(Query::uses_viewer),
+            Query::Not(query) => query.uses_viewer(),
+            Query::Field { value, .. } => value.eq_ignore_ascii_case("@me"),
+            Query::Is(_) | Query::Has(_) | Query::Title(_) => false,
+        }
+    }
+
+    fn is_atom(&self) -> bool {
+        !matches!(self, Query::And(_) | Query::Or(_))
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Query::And(queries) => {
+                let parts: Vec<_> = queries
+                    .iter()
+                    .map(|q| match q {
+                        Query::Or(_) => format!("({q})"),
+                        _ => q.to_string(),
+                    })
+                    .collect();
+                write!(f, "{}", parts.join(" AND "))
+            }
+            Query::Or(queries) => {
+                let parts: Vec<_> = queries.iter().map(Query::to_string).collect();
+                write!(f, "{}", parts.join(" OR "))
+            }
+            Query::Not(query) if query.is_atom() => write!(f, "NOT {query}"),
+            Query::Not(query) => write!(f, "NOT ({query})"),
+            Query::Field { field, value } => {
+                write!(
+                    f,
+                    "{}:{}",
+                    field_label(field).to_lowercase(),
+                    quote_if_needed(value)
+                )
+            }
+            Query::Is(filter) => write!(
+                f,
+                "is:{}",
+                match filter {
+                    IsFilter::Open => "open",
+                    IsFilter::Closed => "closed",
+                    IsFilter::Merged => "merged",
+                    IsFilter::Issue => "issue",
+                    IsFilter::PullRequest => "pr",
+                    IsFilter::Draft => "draft",
+                }
+            ),
+            Query::Has(HasFilter::Parent) => write!(f, "has:parent"),
+            Query::Has(HasFilter::SubIssues) => write!(f, "has:sub-issues"),
+            Query::Has(HasFilter::Field(field)) => {
+                write!(f, "has:{}", field_label(field).to_lowercase())
+            }
+            Query::Title(text) => write!(f, "{}", quote_if_needed(text)),
+        }
+    }
+}
+
+impl TryFrom<String> for Query {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        parse_query(&value)
+    }
+}
+
+impl From<Query> for String {
+    fn from(value: Query) -> Self {
+        value.to_string()
+    }
+}
+
+fn quote_if_needed(text: &str) -> String {
+    let plain = !text.is_empty()
+        && !is_keyword(text)
+        && !text.starts_with('-')
+        && text.chars().all(|c| !c.is_whitespace() && !is_special(c));
+    if plain {
+        text.to_owned()
+    } else {
+        format!("\"{text}\"")
+    }
+}
+
+fn is_keyword(text: &str) -> bool {
+    ["AND", "OR", "NOT"]
+        .iter()
+        .any(|keyword| text.eq_ignore_ascii_case(keyword))
+}
+
+fn is_special(c: char) -> bool {
+    matches!(c, '(' | ')' | '"' | ':' | '=' | '!')
+}
+
+fn field_matches(item: &WorkItem, context: &QueryContext, field: &PivotField, value: &str) -> bool {
+    let values = field_values(item, context.fields, field);
+
+    if *field == PivotField::Assignee {
+        let login = if value.eq_ignore_ascii_case("@me") {
+            match context.viewer {
+                Some(viewer) => viewer,
+                None => return false,
+            }
+        } else {
+            value.strip_prefix('@').unwrap_or(value)
+        };
+        return values.iter().any(|v| v.eq_ignore_ascii_case(login));
+    }
+
+    if *field == PivotField::Repository {
+        // Allow just the repository name as well as `owner/name`.
+        return values.iter().any(|v| {
+            v.eq_ignore_ascii_case(value)
+                || v.rsplit('/')
+                    .next()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(value))
+        });
+    }
+
+    values.iter().any(|v| v.eq_ignore_ascii_case(value))
+}
+
+fn is_matches(item: &WorkItem, filter: IsFilter) -> bool {
+    match filter {
+        IsFilter::Open => item.is_closed() == DelayLoad::Loaded(false),
+        IsFilter::Closed => item.is_closed() == DelayLoad::Loaded(true),
+        IsFilter::Merged => matches!(
+            &item.data,
+            WorkItemData::PullRequest(PullRequest {
+                state: DelayLoad::Loaded(PullRequestState::MERGED),
+                ..
+            })
+        ),
+        IsFilter::Issue => matches!(item.data, WorkItemData::Issue(_)),
+        IsFilter::PullRequest => matches!(item.data, WorkItemData::PullRequest(_)),
+        IsFilter::Draft => matches!(item.data, WorkItemData::DraftIssue),
+    }
+}
+
+/// The names of the item's values for `field`; empty if it has none.
+fn field_values<'a>(item: &'a WorkItem, fields: &'a Fields, field: &PivotField) -> Vec<&'a str> {
+    item_field_values(item, fields, field)
+        .into_iter()
+        .map(|value| value.name)
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    LParen,
+    RParen,
+    Minus,
+    Colon,
+    Equals,
+    NotEquals,
+    Word(String),
+    Quoted(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::LParen => write!(f, "'('"),
+            Token::RParen => write!(f, "')'"),
+            Token::Minus => write!(f, "'-'"),
+            Token::Colon => write!(f, "':'"),
+            Token::Equals => write!(f, "'='"),
+            Token::NotEquals => write!(f, "'!='"),
+            Token::Word(word) => write!(f, "{word:?}"),
+            Token::Quoted(text) => write!(f, "\"{text}\""),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            ':' => {
+                chars.next();
+                tokens.push(Token::Colon);
+            }
+            '=' => {
+                chars.next();
+                tokens.push(Token::Equals);
+            }
+            '!' => {
+                chars.next();
+                if chars.next_if_eq(&'=').is_none() {
+                    bail!("Expected '=' after '!'");
+                }
+                tokens.push(Token::NotEquals);
+            }
+            '"' => {
+                chars.next();
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => quoted.push(c),
+                        None => bail!("Unterminated quote"),
+                    }
+                }
+                tokens.push(Token::Quoted(quoted));
+            }
+            '-' if tokens.last().is_none_or(|token| {
+                !matches!(token, Token::Colon | Token::Equals | Token::NotEquals)
+            }) =>
+            {
+                chars.next();
+                tokens.push(Token::Minus);
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && !is_special(c)) {
+                    word.push(c);
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn parse_or(&mut self) -> Result<Query> {
+        let mut queries = vec![self.parse_and()?];
+        while self.peek_keyword("OR") {
+            self.next();
+            queries.push(self.parse_and()?);
+        }
+        Ok(if queries.len() == 1 {
+            queries.remove(0)
+        } else {
+            Query::Or(queries)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Query> {
+        let mut queries = vec![self.parse_unary()?];
+        loop {
+            if self.peek_keyword("AND") {
+                self.next();
+            } else if self.peek_keyword("OR") || matches!(self.peek(), None | Some(Token::RParen)) {
+                break;
+            }
+            queries.push(self.parse_unary()?);
+        }
+        Ok(if queries.len() == 1 {
+            queries.remove(0)
+        } else {
+            Query::And(queries)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Query> {
+        if self.peek_keyword("NOT") || self.peek() == Some(&Token::Minus) {
+            self.next();
+            return Ok(Query::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Query> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let query = self.parse_or()?;
+                if self.next() != Some(Token::RParen) {
+                    bail!("Missing ')'");
+                }
+                Ok(query)
+            }
+            Some(Token::Quoted(text)) => Ok(Query::Title(text)),
+            Some(Token::Word(word)) => match self.peek() {
+                Some(Token::Colon | Token::Equals | Token::NotEquals) => {
+                    let negate = self.next() == Some(Token::NotEquals);
+                    let value = match self.next() {
+                        Some(Token::Word(value) | Token::Quoted(value)) => value,
+                        _ => bail!("Missing value for {word}"),
+                    };
+                    let query = parse_term(&word, value)?;
+                    Ok(if negate {
+                        Query::Not(Box::new(query))
+                    } else {
+                        query
+                    })
+                }
+                _ => Ok(Query::Title(word)),
+            },
+            Some(token) => bail!("Unexpected {token}"),
+            None => bail!("Unexpected end of query"),
+        }
+    }
+}
+
+fn parse_term(key: &str, value: String) -> Result<Query> {
+    match key.to_ascii_lowercase().as_str() {
+        "is" => {
+            let filter = match value.to_ascii_lowercase().as_str() {
+                "open" => IsFilter::Open,
+                "closed" => IsFilter::Closed,
+                "merged" => IsFilter::Merged,
+                "issue" => IsFilter::Issue,
+                "pr" | "pullrequest" => IsFilter::PullRequest,
+                "draft" => IsFilter::Draft,
+                _ => bail!("Unknown is: value: {value}"),
+            };
+            Ok(Query::Is(filter))
+        }
+        "has" => {
+            let filter = match value.to_ascii_lowercase().as_str() {
+                "parent" => HasFilter::Parent,
+                "sub-issues" | "subissues" | "children" => HasFilter::SubIssues,
+                _ => match resolve_field(&value) {
+                    Some(field) => HasFilter::Field(field),
+                    None => bail!("Unknown has: value: {value}"),
+                },
+            };
+            Ok(Query::Has(filter))
+        }
+        "title" => Ok(Query::Title(value)),
+        _ => match resolve_field(key) {
+            Some(field) => Ok(Query::Field { field, value }),
+            None => bail!("Unknown field: {key}"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{test_helpers::TestData, IssueState, WorkItemId};
+
+    fn matching(data: &TestData, query: &str, viewer: Option<&str>) -> Vec<WorkItemId> {
+        let query = parse_query(query).unwrap();
+        let context = QueryContext {
+            fields: &data.fields,
+            viewer,
+        };
+        data.work_items
+            .iter()
+            .filter(|id| query.matches(data.work_items.get(id).unwrap(), &context))
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_query_precedence() {
+        let query = parse_query("epic:\"DML Demo\" AND (status!=Closed OR assignee:@me)").unwrap();
+        assert_eq!(
+            query,
+            Query::And(vec![
+                Query::Field {
+                    field: PivotField::Epic,
+                    value: "DML Demo".to_owned()
+                },
+                Query::Or(vec![
+                    Query::Not(Box::new(Query::Field {
+                        field: PivotField::Status,
+                        value: "Closed".to_owned()
+                    })),
+                    Query::Field {
+                        field: PivotField::Assignee,
+                        value: "@me".to_owned()
+                    },
+                ]),
+            ])
+        );
+        assert!(query.uses_viewer());
+    }
+
+    #[test]
+    fn test_parse_query_implicit_and_and_negation() {
+        let query = parse_query("is:open -has:parent crash").unwrap();
+        assert_eq!(
+            query,
+            Query::And(vec![
+                Query::Is(IsFilter::Open),
+                Query::Not(Box::new(Query::Has(HasFilter::Parent))),
+                Query::Title("crash".to_owned()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_query_round_trip() {
+        for text in [
+            "epic:\"DML Demo\" AND (NOT status:Closed OR assignee:@me)",
+            "is:open AND NOT has:parent AND crash",
+            "NOT (kind:Bug OR kind:Task)",
+            "has:sub-issues OR has:epic",
+            "repository:ghui AND \"two words\"",
+        ] {
+            let query = parse_query(text).unwrap();
+            assert_eq!(query.to_string(), text);
+            assert_eq!(parse_query(&query.to_string()).unwrap(), query);
+        }
+    }
+
+    #[test]
+    fn test_parse_query_rejects_derived_fields() {
+        // Queries only test an item's own fields; the ones derived from its
+        // dates or ancestors have no values to match.
+        for field in [
+            "updatedweek",
+            "UpdatedMonth",
+            "createdMonth",
+            "age",
+            "iterationstart",
+        ] {
+            assert_eq!(
+                parse_query(&format!("{field}:x")).unwrap_err().to_string(),
+                format!("Unknown field: {field}")
+            );
+            assert_eq!(
+                parse_query(&format!("has:{field}"))
+                    .unwrap_err()
+                    .to_string(),
+                format!("Unknown has: value: {field}")
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_query_errors() {
+        assert_eq!(
+            parse_query("bogus:1").unwrap_err().to_string(),
+            "Unknown field: bogus"
+        );
+        assert_eq!(
+            parse_query("(is:open").unwrap_err().to_string(),
+            "Missing ')'"
+        );
+        assert_eq!(
+            parse_query("epic:").unwrap_err().to_string(),
+            "Missing value for epic"
+        );
+        assert_eq!(
+            parse_query("is:sleepy").unwrap_err().to_string(),
+            "Unknown is: value: sleepy"
+        );
+        assert_eq!(
+            parse_query("\"open").unwrap_err().to_string(),
+            "Unterminated quote"
+        );
+    }
+
+    #[test]
+    fn test_query_matches() {
+        let mut data = TestData::default();
+
+        let child = data
+            .build()
+            .epic("DML Demo")
+            .status("Active")
+            .assignees(&["me"])
+            .add();
+        let closed = data
+            .build()
+            .epic("DML Demo")
+            .status("Closed")
+            .issue_state(IssueState::CLOSED)
+            .add();
+        let parent = data
+            .build()
+            .epic("MiniEngine Demo")
+            .sub_issues(&[&child])
+            .add();
+
+        assert_eq!(
+            matching(&data, "epic:\"dml demo\" status!=closed", None),
+            vec![child.clone()]
+        );
+        assert_eq!(
+            matching(&data, "is:closed OR has:sub-issues", None),
+            vec![closed.clone(), parent.clone()]
+        );
+        assert_eq!(matching(&data, "has:parent", None), vec![child.clone()]);
+        assert_eq!(
+            matching(&data, "assignee:@me", Some("me")),
+            vec![child.clone()]
+        );
+        assert!(matching(&data, "assignee:@me", None).is_empty());
+        assert_eq!(
+            matching(&data, "NOT has:assignee", None),
+            vec![closed, parent]
+        );
+    }
+
+    #[test]
+    fn test_query_serializes_as_text() {
+        let query = parse_query("is:open epic:X").unwrap();
+        let json = serde_json::to_string(&query).unwrap();
+        assert_eq!(json, "\"is:open AND epic:X\"");
+        assert_eq!(serde_json::from_str::<Query>(&json).unwrap(), query);
+        assert!(serde_json::from_str::<Query>("\"bogus:1\"").is_err());
+    }
+}